@@ -71,6 +71,7 @@ fun Event.getViewTitle(context: Context): String {
 		is Event.SeedCreated -> context.getString(R.string.log_title_seed_created)
 		is Event.SeedNameWasShown -> context.getString(R.string.log_title_seed_name_was_shown)
 		is Event.SeedRemoved -> context.getString(R.string.log_title_seed_removed)
+		is Event.SeedRenamed -> context.getString(R.string.log_title_seed_renamed)
 		is Event.SystemEntry -> context.getString(R.string.log_title_system_entry)
 		is Event.TransactionSignError -> context.getString(R.string.log_title_transaction_sign_error)
 		is Event.TransactionSigned -> context.getString(R.string.log_title_transaction_signed)
@@ -145,6 +146,11 @@ fun Event.getViewMessage(context: Context): String? {
 		is Event.SeedCreated -> this.seedCreated
 		is Event.SeedNameWasShown -> this.seedNameWasShown
 		is Event.SeedRemoved -> this.seedName
+		is Event.SeedRenamed -> context.getString(
+			R.string.log_message_seed_renamed,
+			this.seedName,
+			this.newSeedName
+		)
 		is Event.SystemEntry -> this.systemEntry
 		is Event.TransactionSignError -> this.signDisplay.userComment
 		is Event.TransactionSigned -> this.signDisplay.userComment
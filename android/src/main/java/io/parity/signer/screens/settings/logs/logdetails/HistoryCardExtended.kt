@@ -210,6 +210,14 @@ fun HistoryCardExtended(
 				line3 = eventVal.seedName
 			)
 		}
+		is Event.SeedRenamed -> {
+			HistoryCardTemplateOld(
+				image = Icons.Default.Edit,
+				line1 = timestamp,
+				line2 = "Seed renamed",
+				line3 = eventVal.seedName + " -> " + eventVal.newSeedName
+			)
+		}
 		is Event.SeedNameWasShown -> {
 			HistoryCardTemplateOld(
 				image = Icons.Default.Warning,
@@ -23,6 +23,7 @@ struct HistoryCardExtended: View {
              .resetDangerRecord,
              .seedCreated,
              .seedRemoved,
+             .seedRenamed,
              .seedNameWasShown,
              .networkSpecsSigned,
              .systemEntry,
@@ -22,6 +22,7 @@ extension Event {
              .resetDangerRecord,
              .seedCreated,
              .seedRemoved,
+             .seedRenamed,
              .seedNameWasShown,
              .networkSpecsSigned,
              .systemEntry,
@@ -42,6 +42,8 @@ extension Event {
             Localizable.HistoryCard.seedCreated.string
         case .seedRemoved:
             Localizable.HistoryCard.seedRemoved.string
+        case .seedRenamed:
+            Localizable.HistoryCard.seedRenamed.string
         case .seedNameWasShown:
             Localizable.HistoryCard.seedShown.string
         case .networkSpecsSigned:
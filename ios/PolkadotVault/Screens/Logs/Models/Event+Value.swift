@@ -32,6 +32,8 @@ extension Event {
              let .systemEntry(text),
              let .userEntry(text):
             text
+        case let .seedRenamed(seedName, newSeedName):
+            seedName + " -> " + newSeedName
         case let .networkSpecsSigned(value):
             value.specsToSend.title
         case let .metadataSigned(value):
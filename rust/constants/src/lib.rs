@@ -16,8 +16,8 @@
 //!   sign updates for a given network
 //! - [`METATREE`], with network metadata
 //! - [`ADDRTREE`], with user addresses public information
-//! - [`SETTREE`], containing general verifier, types information, and Vault
-//!   danger status
+//! - [`SETTREE`], containing general verifier, types information, Vault
+//!   danger status, and metadata storage setting
 //! - [`TRANSACTION`], used to store temporarily transaction data while the
 //!   user accepts or declines it
 //! - [`HISTORY`], with history log
@@ -67,6 +67,13 @@ pub mod test_values;
 /// Default folder for cold database generated during the Vault build
 pub const COLD_DB_NAME_RELEASE: &str = "../database/database_cold_release";
 
+/// Default manifest file for cold database generated during the Vault build
+pub const COLD_RELEASE_MANIFEST: &str = "../database/database_cold_release_manifest.json";
+
+/// Default canonical content dump file for cold database generated during the
+/// Vault build
+pub const COLD_RELEASE_DUMP: &str = "../database/database_cold_release_dump.scale";
+
 /// Tree name for the tree storing the network specs in cold database
 pub const SPECSTREE: &[u8] = b"chainspecs";
 
@@ -108,6 +115,22 @@ pub const DANGER: &[u8] = b"dangerous_encounter";
 /// Key in settings tree [`SETTREE`] for Vault database schema version
 pub const SCHEMA_VERSION: &[u8] = b"schema_version";
 
+/// Key in settings tree [`SETTREE`] for the form in which Vault stores
+/// the network metadata
+pub const META_STORAGE: &[u8] = b"meta_storage";
+
+/// Key prefix in settings tree [`SETTREE`] for the `bip39` language of the key
+/// set seed phrase, followed by the seed name
+pub const SEED_LANGUAGE: &[u8] = b"seed_language";
+
+/// Key prefix in settings tree [`SETTREE`] for the derivation templates,
+/// followed by the template name
+pub const DERIVATION_TEMPLATE: &[u8] = b"derivation_template";
+
+/// Key prefix in settings tree [`SETTREE`] for the user-assigned key labels,
+/// followed by the address key
+pub const KEY_LABEL: &[u8] = b"key_label";
+
 /// Key in transactions tree [`TRANSACTION`] for updates data
 pub const STUB: &[u8] = b"stub";
 
@@ -141,6 +164,10 @@ pub const META_HISTORY: &[u8] = b"metadata_history";
 /// necessary for RPC calls
 pub const ADDRESS_BOOK: &[u8] = b"address_book";
 
+/// Key in hot database settings tree [`SETTREE`] for the metadata retention
+/// policy
+pub const META_RETENTION: &[u8] = b"meta_retention";
+
 /// Default `color` to be used in generating network specs with no color
 /// information provided
 pub const COLOR: &str = "#660D35";
@@ -159,6 +186,12 @@ pub const EXPORT_FOLDER: &str = "../files/completed";
 pub const ALICE_SEED_PHRASE: &str =
     "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
 
+/// Maximum size of decompressed `load_metadata` content, in bytes
+///
+/// Protects against maliciously crafted compressed updates expanding into
+/// huge amount of data.
+pub const MAX_DECOMPRESSED_CONTENT_SIZE: usize = 16 * 1024 * 1024;
+
 /// Data chunk size for fountain QR code generation
 pub const CHUNK_SIZE: u16 = 1072;
 
@@ -0,0 +1,456 @@
+//! BIP32 derivation for Ethereum and BIP32-Ed25519 keys
+//!
+//! Ethereum wallets, such as MetaMask, derive `secp256k1` keys from the BIP39
+//! seed with BIP32 derivation paths, normally BIP44 `m/44'/60'/0'/0/<n>`.
+//! Substrate-style `//hard` junctions on `ecdsa` keys produce different keys
+//! for the same seed phrase, so BIP32 paths are supported separately for the
+//! networks with [`Encryption::Ethereum`].
+//!
+//! Networks with [`Encryption::Ed25519`] support BIP32-Ed25519 derivation,
+//! with hardened BIP44 paths such as `m/44'/354'/<account>'/0'/<index>'`.
+//! Compatibility with hardware wallets is not verified against their test
+//! vectors yet.
+//!
+//! Derivation scheme is selected by [`DerivationScheme`].
+//!
+//! Derived secret is passed around as a hexadecimal secret string, same as
+//! the Substrate-style secret strings. `secp256k1` secret key is used as
+//! `ecdsa` seed, `0x<secret key>`, in [`sp_core::crypto`]. BIP32-Ed25519
+//! derivation produces extended secret key, `0x<left half><right half>`, that
+//! is not an `ed25519` seed: public key and signatures are made from the
+//! extended key directly, see [`Ed25519Key`].
+//!
+//! BIP32 paths have no password part. BIP39 passphrase of the seed, if any, is
+//! used in BIP39 seed generation.
+use bip39::{Mnemonic, Seed};
+use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, scalar::Scalar as Ed25519Scalar};
+use definitions::{crypto::Encryption, users::DerivationScheme};
+use hmac::{Hmac, Mac};
+use secp256k1::{Scalar, SecretKey};
+use sha2::{Digest, Sha256, Sha512};
+use sp_core::{ecdsa, ed25519, Pair};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::identities::{bip39_language, is_bip32_path, mnemonic_language, split_seed_secret};
+use crate::{Error, Result};
+
+/// Index offset for hardened derivation.
+const HARDENED: u32 = 0x8000_0000;
+
+/// HMAC key for the master key generation.
+const MASTER_KEY: &[u8] = b"Bitcoin seed";
+
+/// HMAC key for the BIP32-Ed25519 master key generation.
+const ED25519_MASTER_KEY: &[u8] = b"ed25519 seed";
+
+/// Child key indices of BIP32 path, with hardened offset.
+fn bip32_indices(path: &str) -> Result<Vec<u32>> {
+    if !is_bip32_path(path) {
+        return Err(Error::InvalidDerivation(path.to_string()));
+    }
+    path.split('/')
+        .skip(1)
+        .map(|junction| {
+            let (number, hardened) = match junction.strip_suffix('\'') {
+                Some(number) => (number, true),
+                None => (junction, false),
+            };
+            let index = number
+                .parse::<u32>()
+                .ok()
+                .filter(|index| *index < HARDENED)
+                .ok_or_else(|| Error::InvalidDerivation(path.to_string()))?;
+            if hardened {
+                Ok(index + HARDENED)
+            } else {
+                Ok(index)
+            }
+        })
+        .collect()
+}
+
+/// BIP39 seed for the seed secret.
+///
+/// Seed secret is the seed phrase, with optional BIP39 passphrase, see
+/// [`split_seed_secret`]. Seed phrase must be a valid BIP39 seed phrase.
+fn bip39_seed(seed_secret: &str) -> Result<Seed> {
+    let (seed_phrase, passphrase) = split_seed_secret(seed_secret);
+    let language = mnemonic_language(seed_phrase).ok_or(Error::Bip32NoMnemonic)?;
+    let mnemonic = Mnemonic::from_phrase(seed_phrase, bip39_language(language))?;
+    Ok(Seed::new(&mnemonic, passphrase.unwrap_or_default()))
+}
+
+/// Derive `secp256k1` secret key for the seed secret and BIP32 path.
+///
+/// Output is a **secret** and must be zeroized after use.
+pub fn bip32_secret(seed_secret: &str, path: &str) -> Result<[u8; 32]> {
+    let indices = bip32_indices(path)?;
+    let seed = bip39_seed(seed_secret)?;
+
+    let (mut secret, mut chain_code) = hmac_sha512(MASTER_KEY, &[seed.as_bytes()]);
+    for index in indices {
+        let result = child_key(&secret, &chain_code, index);
+        secret.zeroize();
+        chain_code.zeroize();
+        (secret, chain_code) = result.map_err(|_| Error::Bip32InvalidKey(path.to_string()))?;
+    }
+    chain_code.zeroize();
+    Ok(secret)
+}
+
+/// Derive BIP32-Ed25519 extended secret key for the seed secret and BIP32
+/// path, as left and right halves.
+///
+/// All path junctions must be hardened.
+///
+/// Output is a **secret** and must be zeroized after use.
+pub fn bip32_ed25519_secret(seed_secret: &str, path: &str) -> Result<[u8; 64]> {
+    let indices = bip32_indices(path)?;
+    if indices.iter().any(|index| *index < HARDENED) {
+        return Err(Error::InvalidDerivation(path.to_string()));
+    }
+    let seed = bip39_seed(seed_secret)?;
+
+    let (mut key_left, mut key_right, mut chain_code) = ed25519_master_key(seed.as_bytes());
+    for index in indices {
+        let child = ed25519_child_key(&key_left, &key_right, &chain_code, index);
+        key_left.zeroize();
+        key_right.zeroize();
+        chain_code.zeroize();
+        (key_left, key_right, chain_code) = child;
+    }
+    let mut secret = [0; 64];
+    secret[..32].copy_from_slice(&key_left);
+    secret[32..].copy_from_slice(&key_right);
+    key_left.zeroize();
+    key_right.zeroize();
+    chain_code.zeroize();
+    Ok(secret)
+}
+
+/// Secret string with the BIP32-derived secret key.
+///
+/// Derivation scheme is selected by the encryption. `secp256k1` secret key
+/// is a seed secret string for [`sp_core::crypto`], BIP32-Ed25519 extended
+/// secret key is read by [`Ed25519Key::from_string`].
+///
+/// Output is a **secret** and must be zeroized after use.
+pub fn bip32_secret_string(
+    seed_secret: &str,
+    path: &str,
+    encryption: Encryption,
+) -> Result<String> {
+    let secret_string = match DerivationScheme::new(path, encryption) {
+        Some(DerivationScheme::Bip32Secp256k1) => {
+            let mut secret = bip32_secret(seed_secret, path)?;
+            let secret_string = format!("0x{}", hex::encode(secret));
+            secret.zeroize();
+            secret_string
+        }
+        Some(DerivationScheme::Bip32Ed25519) => {
+            let mut secret = bip32_ed25519_secret(seed_secret, path)?;
+            let secret_string = format!("0x{}", hex::encode(secret));
+            secret.zeroize();
+            secret_string
+        }
+        _ => {
+            return Err(Error::Bip32Encryption {
+                path: path.to_string(),
+                encryption,
+            })
+        }
+    };
+    Ok(secret_string)
+}
+
+/// BIP32-Ed25519 extended secret key.
+///
+/// Left half is the secret scalar, already clamped, and right half is used
+/// for signature nonces, same as the halves of the hashed seed in regular
+/// `ed25519` keys. Extended key has no seed, and could not be made into
+/// [`ed25519::Pair`].
+pub struct ExtendedEd25519 {
+    key_left: [u8; 32],
+    key_right: [u8; 32],
+}
+
+impl Drop for ExtendedEd25519 {
+    fn drop(&mut self) {
+        self.key_left.zeroize();
+        self.key_right.zeroize();
+    }
+}
+
+impl ExtendedEd25519 {
+    /// Extended key from the secret string made by [`bip32_secret_string`],
+    /// `0x<left half><right half>`.
+    ///
+    /// Other secret strings produce `None`.
+    pub fn from_secret_string(secret_string: &str) -> Option<Self> {
+        let secret_hex = secret_string.strip_prefix("0x")?;
+        if secret_hex.len() != 128 {
+            return None;
+        }
+        let mut secret = Zeroizing::new([0; 64]);
+        hex::decode_to_slice(secret_hex, &mut secret[..]).ok()?;
+        let mut extended = Self {
+            key_left: [0; 32],
+            key_right: [0; 32],
+        };
+        extended.key_left.copy_from_slice(&secret[..32]);
+        extended.key_right.copy_from_slice(&secret[32..]);
+        Some(extended)
+    }
+
+    /// Public key, left half multiplied by the base point.
+    pub fn public(&self) -> ed25519::Public {
+        let mut scalar = Ed25519Scalar::from_bits(self.key_left);
+        let public = (&scalar * &ED25519_BASEPOINT_TABLE).compress();
+        scalar.zeroize();
+        ed25519::Public::from_raw(public.to_bytes())
+    }
+
+    /// Sign the message, same as `ed25519` signing with the hashed seed
+    /// halves replaced by the extended key halves.
+    pub fn sign(&self, message: &[u8]) -> ed25519::Signature {
+        let public = self.public();
+        let mut nonce = sha512_scalar(&[&self.key_right, message]);
+        let nonce_point = (&nonce * &ED25519_BASEPOINT_TABLE).compress();
+        let challenge = sha512_scalar(&[nonce_point.as_bytes(), public.as_ref(), message]);
+        let mut scalar = Ed25519Scalar::from_bits(self.key_left);
+        let mut signature = [0; 64];
+        signature[..32].copy_from_slice(nonce_point.as_bytes());
+        signature[32..].copy_from_slice((challenge * scalar + nonce).as_bytes());
+        nonce.zeroize();
+        scalar.zeroize();
+        ed25519::Signature::from_raw(signature)
+    }
+}
+
+/// `ed25519` key for the secret string: either Substrate-style pair, or
+/// BIP32-Ed25519 extended key.
+pub enum Ed25519Key {
+    Pair(ed25519::Pair),
+    Extended(ExtendedEd25519),
+}
+
+impl Ed25519Key {
+    /// Key for the secret string, see
+    /// [`seed_secret_with_path`](crate::identities::seed_secret_with_path),
+    /// and the password.
+    ///
+    /// BIP32 paths have no password part, password is used only for
+    /// Substrate-style pairs.
+    pub fn from_string(secret_string: &str, pwd: Option<&str>) -> Result<Self> {
+        match ExtendedEd25519::from_secret_string(secret_string) {
+            Some(extended) => Ok(Self::Extended(extended)),
+            None => ed25519::Pair::from_string(secret_string, pwd)
+                .map(Self::Pair)
+                .map_err(Error::SecretStringError),
+        }
+    }
+
+    pub fn public(&self) -> ed25519::Public {
+        match self {
+            Ed25519Key::Pair(pair) => pair.public(),
+            Ed25519Key::Extended(extended) => extended.public(),
+        }
+    }
+
+    pub fn sign(&self, message: &[u8]) -> ed25519::Signature {
+        match self {
+            Ed25519Key::Pair(pair) => pair.sign(message),
+            Ed25519Key::Extended(extended) => extended.sign(message),
+        }
+    }
+}
+
+/// `SHA512` of concatenated data, reduced into `ed25519` scalar.
+fn sha512_scalar(data: &[&[u8]]) -> Ed25519Scalar {
+    let mut hasher = Sha512::new();
+    for part in data {
+        hasher.update(part);
+    }
+    let mut output = [0; 64];
+    output.copy_from_slice(&hasher.finalize());
+    let scalar = Ed25519Scalar::from_bytes_mod_order_wide(&output);
+    output.zeroize();
+    scalar
+}
+
+/// Private child key derivation, `CKDpriv` of BIP32.
+fn child_key(
+    secret: &[u8; 32],
+    chain_code: &[u8; 32],
+    index: u32,
+) -> std::result::Result<([u8; 32], [u8; 32]), secp256k1::Error> {
+    let parent = SecretKey::from_slice(secret)?;
+    let (mut tweak, child_chain_code) = if index >= HARDENED {
+        hmac_sha512(chain_code, &[&[0u8], secret, &index.to_be_bytes()])
+    } else {
+        let pair =
+            ecdsa::Pair::from_seed_slice(secret).map_err(|_| secp256k1::Error::InvalidSecretKey)?;
+        hmac_sha512(chain_code, &[&pair.public().0, &index.to_be_bytes()])
+    };
+    let child = Scalar::from_be_bytes(tweak)
+        .map_err(|_| secp256k1::Error::InvalidTweak)
+        .and_then(|scalar| parent.add_tweak(&scalar));
+    tweak.zeroize();
+    Ok((child?.secret_bytes(), child_chain_code))
+}
+
+/// BIP32-Ed25519 master key: extended secret key, as two halves, and chain
+/// code.
+fn ed25519_master_key(seed: &[u8]) -> ([u8; 32], [u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha256>::new_from_slice(ED25519_MASTER_KEY)
+        .expect("HMAC accepts keys of any length");
+    mac.update(&[1]);
+    mac.update(seed);
+    let mut chain_code = [0; 32];
+    chain_code.copy_from_slice(&mac.finalize().into_bytes());
+
+    // third highest bit of the last byte must be clear, repeat until it is
+    let (mut key_left, mut key_right) = hmac_sha512(ED25519_MASTER_KEY, &[seed]);
+    while key_left[31] & 0b0010_0000 != 0 {
+        let next = hmac_sha512(ED25519_MASTER_KEY, &[&key_left, &key_right]);
+        key_left.zeroize();
+        key_right.zeroize();
+        (key_left, key_right) = next;
+    }
+    key_left[0] &= 0b1111_1000;
+    key_left[31] &= 0b0111_1111;
+    key_left[31] |= 0b0100_0000;
+    (key_left, key_right, chain_code)
+}
+
+/// Hardened private child key derivation of BIP32-Ed25519.
+fn ed25519_child_key(
+    key_left: &[u8; 32],
+    key_right: &[u8; 32],
+    chain_code: &[u8; 32],
+    index: u32,
+) -> ([u8; 32], [u8; 32], [u8; 32]) {
+    let index = index.to_le_bytes();
+    let (mut z_left, mut z_right) = hmac_sha512(chain_code, &[&[0u8], key_left, key_right, &index]);
+    let (_, child_chain_code) = hmac_sha512(chain_code, &[&[1u8], key_left, key_right, &index]);
+
+    // left half is increased by 8 times first 28 bytes of `z_left`
+    let mut z_left_8 = [0; 32];
+    let mut carry = 0;
+    for (i, byte) in z_left[..28].iter().enumerate() {
+        z_left_8[i] = (byte << 3) | carry;
+        carry = byte >> 5;
+    }
+    z_left_8[28] = carry;
+    let child_left = add_le(key_left, &z_left_8);
+    let child_right = add_le(key_right, &z_right);
+    z_left.zeroize();
+    z_left_8.zeroize();
+    z_right.zeroize();
+    (child_left, child_right, child_chain_code)
+}
+
+/// Sum of little-endian 256-bit numbers, modulo `2^256`.
+fn add_le(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut sum = [0; 32];
+    let mut carry = 0;
+    for ((digit, a), b) in sum.iter_mut().zip(a).zip(b) {
+        let full = *a as u16 + *b as u16 + carry;
+        *digit = full as u8;
+        carry = full >> 8;
+    }
+    sum
+}
+
+/// `HMAC-SHA512` of concatenated data, split into two halves.
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in data {
+        mac.update(part);
+    }
+    let mut output = mac.finalize().into_bytes();
+    let mut left = [0; 32];
+    let mut right = [0; 32];
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    output.as_mut_slice().zeroize();
+    (left, right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bip32_paths() {
+        assert!(is_bip32_path("m/44'/60'/0'/0/0"));
+        assert!(is_bip32_path("m/0"));
+        assert!(!is_bip32_path("m"));
+        assert!(!is_bip32_path("m/"));
+        assert!(!is_bip32_path("//m/44'"));
+        assert!(!is_bip32_path("m/44'/60'///password"));
+        assert!(bip32_indices("m/2147483648").is_err());
+        assert_eq!(
+            bip32_indices("m/44'/60'/0'/0/1").unwrap(),
+            vec![44 + HARDENED, 60 + HARDENED, HARDENED, 0, 1]
+        );
+    }
+
+    #[test]
+    fn bip44_ethereum_key() {
+        // well-known test mnemonic, address is same as in MetaMask
+        let seed_phrase = "abandon abandon abandon abandon abandon abandon abandon abandon \
+            abandon abandon abandon about";
+        let secret = bip32_secret(seed_phrase, "m/44'/60'/0'/0/0").unwrap();
+        assert_eq!(
+            hex::encode(secret),
+            "1ab42cc412b618bdea3a599e3c9bae199ebf030895b039e9db1e30dafb12b727"
+        );
+    }
+
+    #[test]
+    fn bip32_ed25519_key() {
+        let seed_phrase = "abandon abandon abandon abandon abandon abandon abandon abandon \
+            abandon abandon abandon about";
+        let secret = bip32_ed25519_secret(seed_phrase, "m/44'/354'/0'/0'/0'").unwrap();
+        assert_eq!(
+            hex::encode(secret),
+            "e8c68348586d53e4e8d1a864b0e4e17c75e4eb06e0c63c1432bef2ba29e69d41\
+            a7ba8e8af8b5b0bd7f28eb2730191afb9387a2dcb23740ab6555e39e31bd6b6a"
+        );
+        assert!(bip32_ed25519_secret(seed_phrase, "m/44'/354'/0'/0/0").is_err());
+
+        let key = Ed25519Key::from_string(
+            &bip32_secret_string(seed_phrase, "m/44'/354'/0'/0'/0'", Encryption::Ed25519).unwrap(),
+            None,
+        )
+        .unwrap();
+        assert!(matches!(key, Ed25519Key::Extended(_)));
+        assert_eq!(
+            hex::encode(key.public()),
+            "65d498f6a0bcc8859e997a1bc1b6c3d67f7faa5bc748e21d79b099676ffd79e1"
+        );
+        let signature = key.sign(b"message");
+        assert!(ed25519::Pair::verify(&signature, b"message", &key.public()));
+    }
+
+    #[test]
+    fn extended_key_signature() {
+        // RFC 8032 test 1: seed `9d61b19d...`, its `SHA512` halves with the
+        // left half clamped are the extended key of the same signing key
+        let extended = ExtendedEd25519::from_secret_string(
+            "0x307c83864f2833cb427a2ef1c00a013cfdff2768d980c0a3a520f006904de94f\
+            9b4f0afe280b746a778684e75442502057b7473a03f08f96f5a38e9287e01f8f",
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(extended.public()),
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+        assert_eq!(
+            hex::encode(extended.sign(b"")),
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+            5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+        );
+    }
+}
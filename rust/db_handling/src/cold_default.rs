@@ -294,8 +294,19 @@ pub fn populate_cold(database: &sled::Db, general_verifier: Verifier) -> Result<
 }
 
 /// Generate **not initiated** release cold database.
+///
+/// All trees are dropped (and the default tree is cleared) beforehand, so that
+/// the database content depends only on the defaults and not on what was in
+/// the database directory before.
 #[cfg(feature = "active")]
 pub(crate) fn populate_cold_release(database: &sled::Db) -> Result<()> {
+    for tree_name in database.tree_names() {
+        // default tree can not be dropped, it is cleared below
+        if tree_name != database.name() {
+            database.drop_tree(tree_name)?;
+        }
+    }
+    database.clear()?;
     cold_database_no_init(database, Purpose::Release)
 }
 
@@ -505,7 +505,7 @@ impl TrDbColdStub {
         let meta_key = MetaKey::from_parts(&meta_values.name, meta_values.version);
         self.metadata_stub = self
             .metadata_stub
-            .new_addition(meta_key.key(), meta_values.meta.to_vec());
+            .new_addition(meta_key.key(), meta_values.to_stored());
         self.history_stub.push(Event::MetadataAdded {
             meta_values_display: MetaValuesDisplay::get(meta_values),
         });
@@ -166,6 +166,10 @@ pub enum Error {
     #[error("Seed name is empty.")]
     EmptySeedName,
 
+    /// Key set could not be renamed, the new seed name is already in use.
+    #[error("Key set with seed name {0} already exists.")]
+    SeedNameExists(String),
+
     /// Error in [`SecretString`](https://docs.rs/sp-core/6.0.0/sp_core/crypto/type.SecretString.html).
     ///
     /// `SecretString` consists of combined seed phrase and derivation.
@@ -371,6 +375,74 @@ pub enum Error {
     #[error("Wrong password.")]
     WrongPassword,
 
+    /// Derivation path with password was used for a seed with BIP39
+    /// passphrase. Passphrase takes the place of the password in the secret
+    /// string, so the keys of such seeds can not have own password.
+    #[error("Keys of a seed with passphrase can not have password.")]
+    PasswordWithPassphrase,
+
+    /// BIP32 derivation requires a valid BIP39 seed phrase.
+    #[error("BIP32 derivation is possible only for BIP39 seed phrase.")]
+    Bip32NoMnemonic,
+
+    /// BIP32 derivation produced invalid `secp256k1` key. Per BIP32, the next
+    /// index should be used instead.
+    #[error("BIP32 derivation {0} produces invalid key, please use next index.")]
+    Bip32InvalidKey(String),
+
+    /// BIP32 derivation was used for a network with encryption that has no
+    /// BIP32 derivation scheme, or the path is not valid for the scheme.
+    #[error(
+        "BIP32 derivation {path} is not supported for {} encryption.",
+        .encryption.show()
+    )]
+    Bip32Encryption {
+        path: String,
+        encryption: Encryption,
+    },
+
+    /// Key with BIP32-Ed25519 derivation has extended secret key, that is not
+    /// an `ed25519` seed, and could not be exported as one.
+    #[error("Secret of a key with BIP32-Ed25519 derivation could not be exported.")]
+    Bip32Ed25519SecretExport,
+
+    /// Keystore is not a supported Polkadot-JS keystore.
+    #[error("Keystore not supported: {0}.")]
+    KeystoreUnsupported(String),
+
+    /// Keystore could not be decrypted with the password.
+    #[error("Keystore could not be decrypted, wrong password.")]
+    KeystorePassword,
+
+    /// Decrypted keystore content is not a valid secret for the encryption.
+    #[error("Keystore secret is damaged or does not match the encryption.")]
+    KeystoreSecret,
+
+    /// Secret of the seed does not produce the expected key, i.e. the seed
+    /// phrase is not the one of the key.
+    #[error(
+        "Seed phrase does not match the key with public key {}.",
+        hex::encode(multisigner_to_public(.0))
+    )]
+    KeystoreKeyMismatch(MultiSigner),
+
+    /// Keystore key is not found among the Vault keys. Vault stores only the
+    /// seed phrases, and can not import keys not derived from known seeds.
+    #[error(
+        "Keystore key with public key {} is not found among the Vault keys.",
+        hex::encode(multisigner_to_public(.0))
+    )]
+    KeystoreKeyNotFound(MultiSigner),
+
+    /// Derivation template has empty name or the pattern is not a valid
+    /// derivation path without password.
+    #[error("Derivation template {name} with pattern {pattern} is invalid.")]
+    DerivationTemplateInvalid { name: String, pattern: String },
+
+    /// Derivation template with the name is not in the database.
+    #[error("Derivation template {0} not found.")]
+    DerivationTemplateNotFound(String),
+
     #[error("Missing information about whether the path {0} is passworded.")]
     MissingPasswordInfo(String),
 
@@ -1,12 +1,10 @@
 //! Common helper functions for database operations
 use definitions::crypto::Encryption;
-use parity_scale_codec::Decode;
-#[cfg(feature = "active")]
-use parity_scale_codec::Encode;
+use parity_scale_codec::{Decode, Encode};
 use sled::{Batch, Db, Tree};
 use sp_core::H256;
 
-use constants::{ADDRTREE, DANGER, GENERALVERIFIER, SCHEMA_VERSION, VERIFIERS};
+use constants::{ADDRTREE, DANGER, GENERALVERIFIER, META_STORAGE, SCHEMA_VERSION, VERIFIERS};
 use constants::{METATREE, SETTREE, SPECSTREE, TYPES};
 
 use definitions::network_specs::NetworkSpecs;
@@ -19,8 +17,11 @@ use definitions::{
     network_specs::{CurrentVerifier, ValidCurrentVerifier, Verifier},
 };
 use definitions::{
-    keyring::MetaKey, metadata::MetaValues, network_specs::OrderedNetworkSpecs,
-    qr_transfers::ContentLoadTypes, types::TypeEntry,
+    keyring::MetaKey,
+    metadata::{MetaStorage, MetaValues},
+    network_specs::OrderedNetworkSpecs,
+    qr_transfers::ContentLoadTypes,
+    types::TypeEntry,
 };
 #[cfg(feature = "active")]
 use definitions::{
@@ -32,7 +33,6 @@ use sp_runtime::MultiSigner;
 use crate::identities::find_address_details_for_multisigner;
 use crate::{Error, Result};
 
-#[cfg(feature = "active")]
 use crate::db_transactions::TrDbCold;
 use crate::manage_history::events_to_batch;
 
@@ -683,6 +683,28 @@ pub fn get_danger_status(database: &sled::Db) -> Result<bool> {
     Ok(DangerRecord::from_ivec(&a).device_was_online()?)
 }
 
+/// Get the form in which the network metadata is stored in the Vault database.
+///
+/// Databases without the setting store full metadata.
+pub fn get_meta_storage(database: &sled::Db) -> Result<MetaStorage> {
+    let settings = open_tree(database, SETTREE)?;
+    let res = settings
+        .get(META_STORAGE)?
+        .map(|meta_storage_encoded| <MetaStorage>::decode(&mut &meta_storage_encoded[..]))
+        .transpose()?;
+    Ok(res.unwrap_or_default())
+}
+
+/// Set the form in which the network metadata loaded afterwards is stored in
+/// the Vault database.
+pub fn set_meta_storage(database: &sled::Db, meta_storage: MetaStorage) -> Result<()> {
+    let mut settings_batch = Batch::default();
+    settings_batch.insert(META_STORAGE, meta_storage.encode());
+    TrDbCold::new().set_settings(settings_batch).apply(database)
+}
+
+/// Check that the seed phrase is a valid `bip39` seed phrase, in any of the
+/// supported languages.
 pub fn validate_mnemonic(mnemonic: &str) -> bool {
-    bip39::Mnemonic::validate(mnemonic, bip39::Language::English).is_ok()
+    crate::identities::mnemonic_language(mnemonic).is_some()
 }
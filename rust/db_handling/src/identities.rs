@@ -7,9 +7,12 @@
 //! Required data to generate address:
 //!
 //! - seed phrase (secret words set, must be always zeroized after use and is
-//!   never stored in Rust database)
+//!   never stored in Rust database), optionally with BIP39 passphrase,
+//!   passed together with the seed phrase as `<seed phrase>///<passphrase>`
 //! - derivation path (`/soft//hard///password`); password part, i.e. whatever
 //!   follows `///` is not stored in Rust database and must be zeroized after use
+//!   or BIP32 derivation path (`m/44'/60'/0'/0/0`), for Ethereum and ed25519
+//!   networks only
 //! - [`NetworkSpecsKey`](definitions::keyring::NetworkSpecsKey)
 //!
 //! The combination of seed phrase and derivation path allows to generate key
@@ -51,6 +54,8 @@ use zeroize::Zeroize;
 use constants::ADDRTREE;
 #[cfg(feature = "active")]
 use constants::ALICE_SEED_PHRASE;
+#[cfg(feature = "active")]
+use constants::{KEY_LABEL, SEED_LANGUAGE, SETTREE};
 use definitions::derivations::SeedKeysPreview;
 use definitions::dynamic_derivations::{
     DynamicDerivationResponseInfo, DynamicDerivationsAddressRequestV1,
@@ -64,13 +69,14 @@ use definitions::helpers::{
 };
 use definitions::navigation::{DDDetail, DDKeySet, DDPreview, ExportedSet};
 use definitions::network_specs::NetworkSpecs;
+use definitions::payload::{PayloadCrypto, PayloadKind, Prelude};
 #[cfg(feature = "active")]
 use definitions::{
     crypto::Encryption,
     helpers::multisigner_to_public,
     history::{Event, IdentityHistory},
     keyring::{AddressKey, NetworkSpecsKey},
-    users::AddressDetails,
+    users::{AddressDetails, DerivationScheme, SeedLanguage},
 };
 use definitions::{
     helpers::make_identicon_from_multisigner,
@@ -80,6 +86,7 @@ use qrcode_rtx::make_data_packs;
 
 #[cfg(feature = "active")]
 use crate::{
+    bip32::{bip32_secret_string, Ed25519Key},
     db_transactions::TrDbCold,
     helpers::{get_all_networks, make_batch_clear_tree, open_tree, upd_id_batch},
     manage_history::events_to_batch,
@@ -95,6 +102,8 @@ lazy_static! {
 // removed seed phrase part
 // last '+' used to be '*', but empty password is an error
     static ref REG_PATH: Regex = Regex::new(r"^(?P<path>(//?[^/]+)*)(///(?P<password>.+))?$").expect("known value");
+// BIP32 derivation path, as in Ethereum wallets
+    static ref REG_BIP32_PATH: Regex = Regex::new(r"^m(/[0-9]+'?)+$").expect("known value");
 }
 
 #[derive(Clone, Encode, Decode)]
@@ -144,6 +153,7 @@ pub struct TransactionBulkV1 {
 pub enum ExportAddrs {
     V1(ExportAddrsV1),
     V2(ExportAddrsV2),
+    V3(ExportAddrsV3),
 }
 
 #[derive(Clone, Encode, Decode, Debug, Eq, PartialEq)]
@@ -177,10 +187,48 @@ impl From<ExportAddrsV2> for ExportAddrsV1 {
     }
 }
 
+/// Key set export with key labels.
+#[derive(Clone, Encode, Decode, Debug, Eq, PartialEq)]
+pub struct ExportAddrsV3 {
+    pub addr: SeedInfoV3,
+    features: Vec<VaultFeatures>,
+}
+
+impl ExportAddrsV3 {
+    pub fn new(addr: SeedInfoV3) -> Self {
+        Self {
+            addr,
+            features: vec![
+                VaultFeatures::BulkOperations,
+                VaultFeatures::DynamicDerivations,
+                VaultFeatures::KeyLabels,
+            ],
+        }
+    }
+}
+
+impl From<ExportAddrsV3> for ExportAddrsV1 {
+    fn from(val: ExportAddrsV3) -> Self {
+        ExportAddrsV1 {
+            addrs: vec![SeedInfo {
+                name: val.addr.name,
+                multisigner: val.addr.multisigner,
+                derived_keys: val
+                    .addr
+                    .derived_keys
+                    .into_iter()
+                    .map(|labeled| labeled.addr_info)
+                    .collect(),
+            }],
+        }
+    }
+}
+
 #[derive(Clone, Encode, Decode, Debug, Eq, PartialEq)]
 pub enum VaultFeatures {
     BulkOperations,
     DynamicDerivations,
+    KeyLabels,
 }
 
 #[derive(Clone, Encode, Decode, Debug, Eq, PartialEq)]
@@ -214,6 +262,27 @@ pub struct AddrInfo {
     pub genesis_hash: H256,
 }
 
+#[derive(Clone, Encode, Decode, Debug, Eq, PartialEq)]
+pub struct SeedInfoV3 {
+    /// Name of the seed.
+    pub name: String,
+
+    /// Public key of the root key.
+    pub multisigner: MultiSigner,
+
+    /// Derived keys, with labels.
+    pub derived_keys: Vec<AddrInfoV3>,
+}
+
+#[derive(Clone, Encode, Decode, Debug, Eq, PartialEq)]
+pub struct AddrInfoV3 {
+    /// Address information, same as in earlier versions.
+    pub addr_info: AddrInfo,
+
+    /// User-assigned key label, if any.
+    pub label: Option<String>,
+}
+
 /// Export info about keys and their addresses known to Vault
 pub fn export_key_set_addrs(
     database: &sled::Db,
@@ -264,20 +333,27 @@ pub fn export_key_set_addrs(
                 &key.0,
                 Some(specs.specs.base58prefix),
             );
-            derived_keys.push(AddrInfo {
-                address_or_pubkey: address_or_pub_key.clone(),
-                derivation_path: if key.1.path.is_empty() {
-                    None
-                } else {
-                    Some(key.1.path.to_owned())
+            let label = get_key_label(
+                database,
+                &AddressKey::new(key.0.clone(), Some(specs.specs.genesis_hash)),
+            )?;
+            derived_keys.push(AddrInfoV3 {
+                addr_info: AddrInfo {
+                    address_or_pubkey: address_or_pub_key.clone(),
+                    derivation_path: if key.1.path.is_empty() {
+                        None
+                    } else {
+                        Some(key.1.path.to_owned())
+                    },
+                    encryption: key.1.encryption,
+                    genesis_hash: specs.specs.genesis_hash,
                 },
-                encryption: key.1.encryption,
-                genesis_hash: specs.specs.genesis_hash,
+                label,
             });
         }
     }
 
-    Ok(ExportAddrs::V2(ExportAddrsV2::new(SeedInfo {
+    Ok(ExportAddrs::V3(ExportAddrsV3::new(SeedInfoV3 {
         name: seed_name.to_owned(),
         multisigner: root_multisigner,
         derived_keys,
@@ -331,16 +407,79 @@ pub fn import_all_addrs(
         .apply(database)
 }
 
+/// Separator between the seed phrase and the BIP39 passphrase in the seed
+/// secret, same as the password separator in the secret string.
+const PASSPHRASE_SEPARATOR: &str = "///";
+
+/// Split seed secret into the seed phrase and the optional BIP39 passphrase.
+///
+/// Seed secret, as passed from the secure storage, is the seed phrase,
+/// optionally followed by BIP39 passphrase (the "25th word") in format
+/// `<seed phrase>///<passphrase>`. Passphrase changes the mini-secret of the
+/// seed, and thus all keys of the seed.
+pub fn split_seed_secret(seed_secret: &str) -> (&str, Option<&str>) {
+    match seed_secret.split_once(PASSPHRASE_SEPARATOR) {
+        Some((seed_phrase, passphrase)) => (seed_phrase, Some(passphrase)),
+        None => (seed_secret, None),
+    }
+}
+
+/// Combine seed secret and derivation path into secret string, for
+/// [`sp_core::crypto`] key generation.
+///
+/// BIP39 passphrase of the seed goes into the password part of the secret
+/// string, after the derivation path. Keys of seeds with passphrase can not
+/// have own password.
+///
+/// For BIP32 derivation path the secret string is the derived secret key, see
+/// [`bip32`](crate::bip32), and the derivation scheme depends on the
+/// encryption.
+///
+/// Resulting string contains secrets and must be zeroized after use.
+pub fn seed_secret_with_path(
+    seed_secret: &str,
+    path: &str,
+    encryption: Encryption,
+) -> Result<String> {
+    let (seed_phrase, passphrase) = split_seed_secret(seed_secret);
+    if seed_phrase.is_empty() {
+        return Err(Error::EmptySeed);
+    }
+    if is_bip32_path(path) {
+        return bip32_secret_string(seed_secret, path, encryption);
+    }
+    let mut english_phrase = english_seed_phrase(seed_phrase)?;
+    let seed_phrase = english_phrase.as_deref().unwrap_or(seed_phrase);
+    // create fixed-length string to avoid reallocations
+    let mut full_address = String::with_capacity(
+        seed_phrase.len()
+            + path.len()
+            + PASSPHRASE_SEPARATOR.len()
+            + passphrase.map_or(0, str::len),
+    );
+    full_address.push_str(seed_phrase);
+    full_address.push_str(path);
+    english_phrase.zeroize();
+    if let Some(passphrase) = passphrase {
+        if path.contains(PASSPHRASE_SEPARATOR) {
+            full_address.zeroize();
+            return Err(Error::PasswordWithPassphrase);
+        }
+        full_address.push_str(PASSPHRASE_SEPARATOR);
+        full_address.push_str(passphrase);
+    }
+    Ok(full_address)
+}
+
 /// Get public key from seed phrase and derivation path
 fn full_address_to_multisigner(
     mut full_address: String,
     encryption: Encryption,
 ) -> Result<MultiSigner> {
     let multisigner_result = match encryption {
-        Encryption::Ed25519 => match ed25519::Pair::from_string(&full_address, None) {
-            Ok(a) => Ok(MultiSigner::Ed25519(a.public())),
-            Err(e) => Err(Error::SecretStringError(e)),
-        },
+        Encryption::Ed25519 => {
+            Ed25519Key::from_string(&full_address, None).map(|a| MultiSigner::Ed25519(a.public()))
+        }
         Encryption::Sr25519 => match sr25519::Pair::from_string(&full_address, None) {
             Ok(a) => Ok(MultiSigner::Sr25519(a.public())),
             Err(e) => Err(Error::SecretStringError(e)),
@@ -363,7 +502,14 @@ pub fn validate_key_password(
     password: &str,
 ) -> Result<bool> {
     let address = get_address_details(database, address_key)?;
-    let full_address = format!("{}{}///{}", seed_phrase, address.path, password);
+    // empty password is same as no password, and is allowed for the seeds
+    // with passphrase
+    let path = if password.is_empty() {
+        address.path.to_string()
+    } else {
+        format!("{}///{}", address.path, password)
+    };
+    let full_address = seed_secret_with_path(seed_phrase, &path, address.encryption)?;
     let expected = full_address_to_multisigner(full_address, address.encryption)?;
     Ok(&expected == address_key.multi_signer())
 }
@@ -516,7 +662,8 @@ fn dynamic_derivations_response_qr(
     seed_phrase: &str,
 ) -> Result<Vec<QrData>> {
     let response = dynamic_derivations_response(request, seed_phrase)?;
-    let data = [&[0x53, 0xff, 0xdf], response.encode().as_slice()].concat();
+    let prelude = Prelude::new(PayloadCrypto::Unsigned, PayloadKind::DynamicDerivations);
+    let data = [prelude.to_bytes().as_slice(), response.encode().as_slice()].concat();
     make_data_packs(&data, 128).map_err(|e| Error::DataPacking(e.to_string()))
 }
 
@@ -529,10 +676,7 @@ pub fn dynamic_derivations_response(
     let mut derivations = vec![];
     for derivation_request in &seed_request.dynamic_derivations {
         let path = derivation_request.derivation_path.as_str();
-        // create fixed-length string to avoid reallocations
-        let mut full_address = String::with_capacity(seed_phrase.len() + path.len());
-        full_address.push_str(seed_phrase);
-        full_address.push_str(path);
+        let full_address = seed_secret_with_path(seed_phrase, path, derivation_request.encryption)?;
 
         derivations.push(DynamicDerivationResponseInfo {
             derivation_path: derivation_request.derivation_path.clone(),
@@ -569,12 +713,8 @@ pub fn derive_single_key(
     let seed_phrase = seeds.get(&seed_name).ok_or_else(|| Error::NoSeedFound {
         multisigner: root_multisigner.clone(),
     })?;
-    // create fixed-length string to avoid reallocations
-    let mut full_address = String::with_capacity(seed_phrase.len() + derivation_path.len());
-    full_address.push_str(seed_phrase);
-    full_address.push_str(derivation_path);
-
     let (_, encryption) = network_key.genesis_hash_encryption()?;
+    let full_address = seed_secret_with_path(seed_phrase, derivation_path, encryption)?;
     let multi_signer = full_address_to_multisigner(full_address, encryption)?;
 
     let address_details = AddressDetails {
@@ -597,9 +737,22 @@ pub fn inject_derivations_has_pwd(
     let mut ecdsa_signers = HashMap::new();
 
     for (k, v) in &seeds {
-        let sr25519_public = sr25519::Pair::from_phrase(v, None).unwrap().0.public();
-        let ed25519_public = ed25519::Pair::from_phrase(v, None).unwrap().0.public();
-        let ecdsa_public = ecdsa::Pair::from_phrase(v, None).unwrap().0.public();
+        let (seed_phrase, passphrase) = split_seed_secret(v);
+        let mut english_phrase = english_seed_phrase(seed_phrase)?;
+        let seed_phrase = english_phrase.as_deref().unwrap_or(seed_phrase);
+        let sr25519_public = sr25519::Pair::from_phrase(seed_phrase, passphrase)
+            .unwrap()
+            .0
+            .public();
+        let ed25519_public = ed25519::Pair::from_phrase(seed_phrase, passphrase)
+            .unwrap()
+            .0
+            .public();
+        let ecdsa_public = ecdsa::Pair::from_phrase(seed_phrase, passphrase)
+            .unwrap()
+            .0
+            .public();
+        english_phrase.zeroize();
         sr25519_signers.insert(sr25519_public, k);
         ed25519_signers.insert(ed25519_public, k);
         ecdsa_signers.insert(ecdsa_public, k);
@@ -626,10 +779,7 @@ pub fn inject_derivations_has_pwd(
                 continue;
             };
 
-            // create fixed-length string to avoid reallocation
-            let mut full_address = String::with_capacity(seed_phrase.len() + path.len());
-            full_address.push_str(seed_phrase);
-            full_address.push_str(&path);
+            let full_address = seed_secret_with_path(seed_phrase, &path, derived_key.encryption)?;
 
             let multisigner_pwdless =
                 full_address_to_multisigner(full_address, derived_key.encryption)?;
@@ -706,11 +856,196 @@ pub fn get_addresses_by_seed_name(
 ///
 /// Its zeroization and safe handling are delegated to hardware.
 pub fn generate_random_phrase(words_number: u32) -> Result<String> {
+    generate_random_phrase_in(words_number, SeedLanguage::English)
+}
+
+/// Generate random phrase with given number of words, from the `bip39`
+/// wordlist of the given language.
+///
+/// The output is a **secret seed phrase**.
+pub fn generate_random_phrase_in(words_number: u32, language: SeedLanguage) -> Result<String> {
     let mnemonic_type = MnemonicType::for_word_count(words_number as usize)?;
-    let mnemonic = Mnemonic::new(mnemonic_type, Language::English);
+    let mnemonic = Mnemonic::new(mnemonic_type, bip39_language(language));
     Ok(mnemonic.into_phrase())
 }
 
+/// All supported `bip39` languages, in the order of detection priority.
+pub const SEED_LANGUAGES: [SeedLanguage; 8] = [
+    SeedLanguage::English,
+    SeedLanguage::ChineseSimplified,
+    SeedLanguage::ChineseTraditional,
+    SeedLanguage::French,
+    SeedLanguage::Italian,
+    SeedLanguage::Japanese,
+    SeedLanguage::Korean,
+    SeedLanguage::Spanish,
+];
+
+/// `bip39` wordlist language for [`SeedLanguage`].
+pub(crate) fn bip39_language(language: SeedLanguage) -> Language {
+    match language {
+        SeedLanguage::English => Language::English,
+        SeedLanguage::ChineseSimplified => Language::ChineseSimplified,
+        SeedLanguage::ChineseTraditional => Language::ChineseTraditional,
+        SeedLanguage::French => Language::French,
+        SeedLanguage::Italian => Language::Italian,
+        SeedLanguage::Japanese => Language::Japanese,
+        SeedLanguage::Korean => Language::Korean,
+        SeedLanguage::Spanish => Language::Spanish,
+    }
+}
+
+/// Detect the language of the seed phrase.
+///
+/// Seed phrase is checked against the wordlists in [`SEED_LANGUAGES`] order,
+/// the first language in which the seed phrase is valid is returned.
+pub fn mnemonic_language(seed_phrase: &str) -> Option<SeedLanguage> {
+    mnemonic_language_in(seed_phrase, &SEED_LANGUAGES)
+}
+
+/// Detect the language of the seed phrase among the given languages.
+pub fn mnemonic_language_in(seed_phrase: &str, languages: &[SeedLanguage]) -> Option<SeedLanguage> {
+    languages
+        .iter()
+        .find(|language| Mnemonic::validate(seed_phrase, bip39_language(**language)).is_ok())
+        .copied()
+}
+
+/// Re-encode the seed phrase in a language other than English into English
+/// seed phrase.
+///
+/// Keys are generated from the seed phrase entropy, and the entropy does not
+/// depend on the wordlist. [`sp_core::crypto`] accepts only English seed
+/// phrases, so the seed phrases in other languages are replaced with English
+/// seed phrase with the same entropy.
+///
+/// Returns `None` if the seed phrase is English or is not a valid `bip39`
+/// seed phrase in any language. Output is a **secret seed phrase**.
+fn english_seed_phrase(seed_phrase: &str) -> Result<Option<String>> {
+    match mnemonic_language(seed_phrase) {
+        Some(SeedLanguage::English) | None => Ok(None),
+        Some(language) => {
+            let mnemonic = Mnemonic::from_phrase(seed_phrase, bip39_language(language))?;
+            let english = Mnemonic::from_entropy(mnemonic.entropy(), Language::English)?;
+            Ok(Some(english.into_phrase()))
+        }
+    }
+}
+
+/// Settings tree key for the language of the key set with given seed name.
+fn seed_language_key(seed_name: &str) -> Vec<u8> {
+    [SEED_LANGUAGE, seed_name.as_bytes()].concat()
+}
+
+/// Settings tree `Batch` recording the seed phrase language for new key set.
+///
+/// Nothing is recorded if the language could not be detected.
+fn seed_language_batch(seed_name: &str, seed_phrase: &str) -> Batch {
+    let mut settings_batch = Batch::default();
+    let (seed_phrase, _) = split_seed_secret(seed_phrase);
+    if let Some(language) = mnemonic_language(seed_phrase) {
+        settings_batch.insert(seed_language_key(seed_name), language.encode());
+    }
+    settings_batch
+}
+
+/// Get the `bip39` language of the key set seed phrase.
+///
+/// `None` if the language was not recorded, i.e. the key set was created
+/// before the languages were recorded, or the seed phrase is not a valid
+/// `bip39` seed phrase.
+pub fn get_seed_language(database: &sled::Db, seed_name: &str) -> Result<Option<SeedLanguage>> {
+    let settings = open_tree(database, SETTREE)?;
+    Ok(settings
+        .get(seed_language_key(seed_name))?
+        .map(|language_encoded| SeedLanguage::decode(&mut &language_encoded[..]))
+        .transpose()?)
+}
+
+/// Settings tree key for the label of the key with given address key.
+fn key_label_key(address_key: &AddressKey) -> Vec<u8> {
+    [KEY_LABEL, &address_key.key()].concat()
+}
+
+/// Get the user-assigned label of the key.
+///
+/// `None` if the key has no label.
+pub fn get_key_label(database: &sled::Db, address_key: &AddressKey) -> Result<Option<String>> {
+    let settings = open_tree(database, SETTREE)?;
+    Ok(settings
+        .get(key_label_key(address_key))?
+        .map(|label_encoded| String::decode(&mut &label_encoded[..]))
+        .transpose()?)
+}
+
+/// Set the user-assigned label of the key, or remove it if the label is `None`
+/// or empty.
+///
+/// Key must be in the database. Labels are not secret and are not recorded in
+/// the history log.
+pub fn set_key_label(
+    database: &sled::Db,
+    address_key: &AddressKey,
+    label: Option<String>,
+) -> Result<()> {
+    get_address_details(database, address_key)?;
+    let mut settings_batch = Batch::default();
+    match label.filter(|label| !label.is_empty()) {
+        Some(label) => settings_batch.insert(key_label_key(address_key), label.encode()),
+        None => settings_batch.remove(key_label_key(address_key)),
+    }
+    TrDbCold::new().set_settings(settings_batch).apply(database)
+}
+
+/// Rename the key set.
+///
+/// All keys of the key set and the seed phrase language record get the new
+/// seed name in a single database transaction. Key labels are not affected.
+/// Earlier history log entries keep the old seed name, and the renaming itself
+/// is recorded in the history log.
+///
+/// Complementary action in frontend is renaming the seed data in the device
+/// key management system.
+pub fn rename_key_set(database: &sled::Db, seed_name: &str, new_seed_name: &str) -> Result<()> {
+    if new_seed_name.is_empty() {
+        return Err(Error::EmptySeedName);
+    }
+    if !get_addresses_by_seed_name(database, new_seed_name)?.is_empty() {
+        return Err(Error::SeedNameExists(new_seed_name.to_owned()));
+    }
+
+    let mut identity_batch = Batch::default();
+    let mut found = false;
+    let identities = open_tree(database, ADDRTREE)?;
+    for (address_key_vec, address_details_encoded) in identities.iter().flatten() {
+        let mut address_details = AddressDetails::decode(&mut &address_details_encoded[..])?;
+        if address_details.seed_name == seed_name {
+            address_details.seed_name = new_seed_name.to_owned();
+            identity_batch.insert(address_key_vec, address_details.encode());
+            found = true;
+        }
+    }
+    if !found {
+        return Err(Error::NoKnownSeeds);
+    }
+
+    let mut settings_batch = Batch::default();
+    if let Some(language) = get_seed_language(database, seed_name)? {
+        settings_batch.remove(seed_language_key(seed_name));
+        settings_batch.insert(seed_language_key(new_seed_name), language.encode());
+    }
+
+    let events = vec![Event::SeedRenamed {
+        seed_name: seed_name.to_owned(),
+        new_seed_name: new_seed_name.to_owned(),
+    }];
+    TrDbCold::new()
+        .set_addresses(identity_batch) // modify addresses
+        .set_history(events_to_batch(database, events)?) // add corresponding history
+        .set_settings(settings_batch) // move seed phrase language record
+        .apply(database)
+}
+
 /// Check that key with a given path should be marked as a progeny of a key with
 /// exposed secret.
 ///
@@ -853,21 +1188,25 @@ pub(crate) fn create_address(
     if seed_phrase.is_empty() {
         return Err(Error::EmptySeed);
     }
-    // create fixed-length string to avoid reallocations
-    let mut full_address = String::with_capacity(seed_phrase.len() + path.len());
-    full_address.push_str(seed_phrase);
-    full_address.push_str(path);
-
     let encryption = network_specs
         .map(|ns| ns.encryption)
         .unwrap_or(Encryption::Sr25519);
+    if is_bip32_path(path) && !is_bip32_path_supported(path, encryption) {
+        return Err(Error::Bip32Encryption {
+            path: path.to_string(),
+            encryption,
+        });
+    }
 
+    let full_address = seed_secret_with_path(seed_phrase, path, encryption)?;
     let multisigner = full_address_to_multisigner(full_address, encryption)?;
 
     // TODO regex elements may keep the line with password somewhere, how to
     // zeroize then? checked regex crate and it appears that only references are
     // moved around; need to double-check later;
     let (cropped_path, has_pwd) = match REG_PATH.captures(path) {
+        // BIP32 path has no password and is stored as is
+        _ if is_bip32_path(path) => (path, false),
         Some(caps) => match caps.name("path") {
             Some(a) => (a.as_str(), caps.name("password").is_some()),
             None => ("", caps.name("password").is_some()),
@@ -1124,6 +1463,7 @@ pub fn try_create_seed(
     TrDbCold::new()
         .set_addresses(upd_id_batch(Batch::default(), prep_data.address_prep)) // add addresses just made in populate_addresses
         .set_history(events_to_batch(database, events)?) // add corresponding history
+        .set_settings(seed_language_batch(seed_name, seed_phrase)) // record seed phrase language
         .apply(database)
 }
 
@@ -1168,6 +1508,7 @@ pub fn create_key_set(
     TrDbCold::new()
         .set_addresses(upd_id_batch(Batch::default(), prep_data.address_prep)) // add addresses just made
         .set_history(events_to_batch(database, events)?) // add corresponding history
+        .set_settings(seed_language_batch(seed_name, seed_phrase)) // record seed phrase language
         .apply(database)
 }
 
@@ -1198,13 +1539,18 @@ pub fn remove_key(
 /// [`AddressDetails`] associated with each of the provided `MultiSigner`
 /// values. If no networks associated with [`AddressKey`] remain, i.e.
 /// `network_id` set becomes empty, whole associated entry is removed.
+///
+/// If no addresses remain for a seed name, the recorded seed phrase language
+/// is removed as well, as in [`remove_seed`].
 pub fn remove_keys_set(
     database: &sled::Db,
     multiselect: &[MultiSigner],
     network_specs_key: &NetworkSpecsKey,
 ) -> Result<()> {
     let mut id_batch = Batch::default();
+    let mut settings_batch = Batch::default();
     let mut events: Vec<Event> = Vec::new();
+    let mut seed_names: Vec<String> = Vec::new();
     let network_specs = get_network_specs(database, network_specs_key)?;
     for multisigner in multiselect.iter() {
         let public_key = multisigner_to_public(multisigner);
@@ -1220,14 +1566,30 @@ pub fn remove_keys_set(
         );
         events.push(Event::IdentityRemoved { identity_history });
         if address_details.network_id.as_ref() == Some(network_specs_key) {
-            id_batch.remove(address_key.key())
+            id_batch.remove(address_key.key());
+            settings_batch.remove(key_label_key(&address_key));
+            if !seed_names.contains(&address_details.seed_name) {
+                seed_names.push(address_details.seed_name)
+            }
         } else {
             id_batch.insert(address_key.key(), address_details.encode())
         }
     }
+    for seed_name in seed_names.iter() {
+        let key_set_removed = get_addresses_by_seed_name(database, seed_name)?.iter().all(
+            |(multisigner, address_details)| {
+                address_details.network_id.as_ref() == Some(network_specs_key)
+                    && multiselect.contains(multisigner)
+            },
+        );
+        if key_set_removed {
+            settings_batch.remove(seed_language_key(seed_name));
+        }
+    }
     TrDbCold::new()
         .set_addresses(id_batch) // modify existing address entries
         .set_history(events_to_batch(database, events)?) // add corresponding history
+        .set_settings(settings_batch) // remove labels and languages of removed keys
         .apply(database)
 }
 
@@ -1333,13 +1695,39 @@ pub fn create_increment_set(
         .apply(database)
 }
 
+/// Check if the derivation path is BIP32 path, `m/<index>['][/<index>[']...]`.
+///
+/// BIP32 paths are used only for the networks with [`Encryption::Ethereum`]
+/// and [`Encryption::Ed25519`], see [`is_bip32_path_supported`].
+pub fn is_bip32_path(path: &str) -> bool {
+    REG_BIP32_PATH.is_match(path)
+}
+
+/// Check if BIP32 path could be used with the encryption.
+///
+/// Ethereum keys use BIP32 `secp256k1` derivation, ed25519 keys use
+/// BIP32-Ed25519 derivation, with hardened junctions only.
+pub fn is_bip32_path_supported(path: &str, encryption: Encryption) -> bool {
+    match DerivationScheme::new(path, encryption) {
+        Some(DerivationScheme::Bip32Secp256k1) => true,
+        Some(DerivationScheme::Bip32Ed25519) => path
+            .split('/')
+            .skip(1)
+            .all(|junction| junction.ends_with('\'')),
+        Some(DerivationScheme::Substrate) | None => false,
+    }
+}
+
 /// Check derivation format and determine if it has a password.
 ///
 /// Invalid derivation produces an error. Valid derivation is checked for
-/// password with regex.
+/// password with regex. BIP32 derivation never has a password.
 ///
 // TODO regex and secrets, see `create_address` comments.
 pub fn is_passworded(path: &str) -> Result<bool> {
+    if is_bip32_path(path) {
+        return Ok(false);
+    }
     let passworded = REG_PATH
         .captures(path)
         .map(|caps| caps.name("password").is_some())
@@ -1400,6 +1788,17 @@ pub fn derivation_check(
         // Proposed derivation has password, no checks could be made, proceed.
         Ok(true) => Ok(DerivationCheck::Password),
 
+        // BIP32 derivation is not suitable for all networks.
+        Ok(false)
+            if is_bip32_path(path)
+                && !is_bip32_path_supported(
+                    path,
+                    network_specs_key.genesis_hash_encryption()?.1,
+                ) =>
+        {
+            Ok(DerivationCheck::BadFormat)
+        }
+
         // Proposed derivation has no password, checking the database for exact
         // coincidence.
         Ok(false) => {
@@ -1437,6 +1836,9 @@ pub fn derivation_check(
 ///
 // TODO regex and secrets, see `create_address` comments.
 pub fn cut_path(path: &str) -> Result<(String, String)> {
+    if is_bip32_path(path) {
+        return Err(Error::LostPwd);
+    }
     let caps = REG_PATH
         .captures(path)
         .ok_or_else(|| Error::InvalidDerivation(path.to_string()))?;
@@ -1578,6 +1980,9 @@ pub fn remove_seed(database: &sled::Db, seed_name: &str) -> Result<()> {
         return Err(Error::NoKnownSeeds);
     }
 
+    let mut settings_batch = Batch::default();
+    settings_batch.remove(seed_language_key(seed_name));
+
     // Associated `Event` set
     let mut events = vec![Event::SeedRemoved {
         seed_name: seed_name.to_owned(),
@@ -1591,6 +1996,7 @@ pub fn remove_seed(database: &sled::Db, seed_name: &str) -> Result<()> {
 
             // removal of all addresses corresponging to `AddressKey`
             identity_batch.remove(address_key.key());
+            settings_batch.remove(key_label_key(&address_key));
 
             let public_key = multisigner_to_public(multisigner);
             let (genesis_hash_vec, _) = id.genesis_hash_encryption()?;
@@ -1606,11 +2012,13 @@ pub fn remove_seed(database: &sled::Db, seed_name: &str) -> Result<()> {
         } else {
             let address_key = AddressKey::new(multisigner.clone(), None);
             identity_batch.remove(address_key.key());
+            settings_batch.remove(key_label_key(&address_key));
         }
     }
     TrDbCold::new()
         .set_addresses(identity_batch) // modify addresses
         .set_history(events_to_batch(database, events)?) // add corresponding history
+        .set_settings(settings_batch) // remove seed phrase language and key labels records
         .apply(database)
 }
 
@@ -1631,7 +2039,7 @@ pub fn remove_seed(database: &sled::Db, seed_name: &str) -> Result<()> {
 /// ensured by the checksum matching.
 pub fn check_derivation_set(derivations: &[String]) -> Result<()> {
     for path in derivations.iter() {
-        if REG_PATH.captures(path).is_none() {
+        if REG_PATH.captures(path).is_none() && !is_bip32_path(path) {
             return Err(Error::InvalidDerivation(path.to_string()));
         }
     }
@@ -1645,8 +2053,10 @@ fn prepare_secret_key_for_export(
 ) -> Result<Vec<u8>> {
     match multisigner {
         MultiSigner::Ed25519(public) => {
-            let ed25519_pair =
-                ed25519::Pair::from_string(full_address, pwd).map_err(Error::SecretStringError)?;
+            let ed25519_pair = match Ed25519Key::from_string(full_address, pwd)? {
+                Ed25519Key::Pair(pair) => pair,
+                Ed25519Key::Extended(_) => return Err(Error::Bip32Ed25519SecretExport),
+            };
             if public != &ed25519_pair.public() {
                 return Err(Error::WrongPassword);
             }
@@ -1702,25 +2112,10 @@ pub fn export_secret_key(
     seed_phrase: &str,
     mut key_password: Option<String>,
 ) -> Result<MKeyDetails> {
-    let public_key = &unhex(public_key)?;
     let network_specs_key = &NetworkSpecsKey::from_hex(network_specs_key_hex)?;
-    let network_specs = get_network_specs(database, network_specs_key)?;
-    let multisigner = &get_multisigner(public_key, &network_specs.specs.encryption)?;
-    let address_key = AddressKey::new(multisigner.clone(), Some(network_specs.specs.genesis_hash));
-    let address_details = get_address_details(database, &address_key)?;
-    if address_details.seed_name != expected_seed_name {
-        return Err(Error::SeedNameNotMatching {
-            address_key,
-            expected_seed_name: expected_seed_name.to_string(),
-            real_seed_name: address_details.seed_name,
-        });
-    }
-    if address_details.network_id.as_ref() != Some(network_specs_key) {
-        return Err(Error::NetworkSpecsKeyForAddressNotFound {
-            network_specs_key: network_specs_key.to_owned(),
-            address_key,
-        });
-    }
+    let (multisigner, address_details, network_specs) =
+        key_to_export(database, public_key, expected_seed_name, network_specs_key)?;
+    let multisigner = &multisigner;
     let public_key = multisigner_to_public(multisigner);
 
     let style = address_details.identicon_style();
@@ -1730,39 +2125,19 @@ pub fn export_secret_key(
         identicon: make_identicon_from_multisigner(multisigner, style),
         seed_name: address_details.seed_name.to_string(),
         secret_exposed: true,
+        label: get_key_label(
+            database,
+            &AddressKey::new(multisigner.clone(), Some(network_specs.genesis_hash)),
+        )?,
     };
 
     let network_info = MSCNetworkInfo {
-        network_title: network_specs.specs.name,
-        network_logo: network_specs.specs.logo,
+        network_title: network_specs.name.to_string(),
+        network_logo: network_specs.logo.to_string(),
         network_specs_key: network_specs_key_hex.to_owned(),
     };
 
-    let database_addresses = get_addresses_by_seed_name(database, expected_seed_name)?;
-
-    let exposed_addresses = exposed_set(
-        &address_details.path,
-        address_details.has_pwd,
-        database_addresses,
-    );
-
-    let mut identity_batch = Batch::default();
-
-    for (x_multisigner, x_address_details) in exposed_addresses.into_iter() {
-        let mut new_address_details = x_address_details;
-        new_address_details.secret_exposed = true;
-        if let Some(id) = &new_address_details.network_id {
-            let network_specs = get_network_specs(database, id)?;
-            identity_batch.insert(
-                AddressKey::new(
-                    x_multisigner.clone(),
-                    Some(network_specs.specs.genesis_hash),
-                )
-                .key(),
-                new_address_details.encode(),
-            )
-        }
-    }
+    let identity_batch = exposed_addresses_batch(database, &address_details)?;
 
     let history_batch = events_to_batch(
         database,
@@ -1772,7 +2147,7 @@ pub fn export_secret_key(
                 &address_details.encryption,
                 &public_key,
                 &address_details.path,
-                network_specs.specs.genesis_hash,
+                network_specs.genesis_hash,
             ),
         }],
     )?;
@@ -1780,7 +2155,7 @@ pub fn export_secret_key(
     let qr = generate_secret_qr(
         multisigner,
         &address_details,
-        &network_specs.specs.genesis_hash,
+        &network_specs.genesis_hash,
         seed_phrase,
         key_password.as_deref(),
     )?;
@@ -1797,13 +2172,78 @@ pub fn export_secret_key(
         network_info,
         base58: print_multisigner_as_base58_or_eth_address(
             multisigner,
-            Some(network_specs.specs.base58prefix),
+            Some(network_specs.base58prefix),
             address_details.encryption,
         ),
         address,
     })
 }
 
+/// Get the key for secret export, by hexadecimal public key.
+///
+/// Checks that the key belongs to the expected seed and is available in the
+/// network.
+pub(crate) fn key_to_export(
+    database: &sled::Db,
+    public_key: &str,
+    expected_seed_name: &str,
+    network_specs_key: &NetworkSpecsKey,
+) -> Result<(MultiSigner, AddressDetails, NetworkSpecs)> {
+    let public_key = &unhex(public_key)?;
+    let network_specs = get_network_specs(database, network_specs_key)?.specs;
+    let multisigner = get_multisigner(public_key, &network_specs.encryption)?;
+    let address_key = AddressKey::new(multisigner.clone(), Some(network_specs.genesis_hash));
+    let address_details = get_address_details(database, &address_key)?;
+    if address_details.seed_name != expected_seed_name {
+        return Err(Error::SeedNameNotMatching {
+            address_key,
+            expected_seed_name: expected_seed_name.to_string(),
+            real_seed_name: address_details.seed_name,
+        });
+    }
+    if address_details.network_id.as_ref() != Some(network_specs_key) {
+        return Err(Error::NetworkSpecsKeyForAddressNotFound {
+            network_specs_key: network_specs_key.to_owned(),
+            address_key,
+        });
+    }
+    Ok((multisigner, address_details, network_specs))
+}
+
+/// Prepare [`Batch`] marking as exposed the key with secret known outside of
+/// Vault, and all the keys of the same seed that could be derived from it.
+pub(crate) fn exposed_addresses_batch(
+    database: &sled::Db,
+    address_details: &AddressDetails,
+) -> Result<Batch> {
+    let database_addresses = get_addresses_by_seed_name(database, &address_details.seed_name)?;
+
+    let exposed_addresses = exposed_set(
+        &address_details.path,
+        address_details.has_pwd,
+        database_addresses,
+    );
+
+    let mut identity_batch = Batch::default();
+
+    for (x_multisigner, x_address_details) in exposed_addresses.into_iter() {
+        let mut new_address_details = x_address_details;
+        new_address_details.secret_exposed = true;
+        if let Some(id) = &new_address_details.network_id {
+            let network_specs = get_network_specs(database, id)?;
+            identity_batch.insert(
+                AddressKey::new(
+                    x_multisigner.clone(),
+                    Some(network_specs.specs.genesis_hash),
+                )
+                .key(),
+                new_address_details.encode(),
+            )
+        }
+    }
+    Ok(identity_batch)
+}
+
 fn generate_secret_qr(
     multisigner: &MultiSigner,
     address_details: &AddressDetails,
@@ -1811,11 +2251,11 @@ fn generate_secret_qr(
     seed_phrase: &str,
     pwd: Option<&str>,
 ) -> Result<QrData> {
-    // create fixed-length string to avoid reallocations
-
-    let mut full_address = String::with_capacity(seed_phrase.len() + address_details.path.len());
-    full_address.push_str(seed_phrase);
-    full_address.push_str(&address_details.path);
+    let mut full_address = seed_secret_with_path(
+        seed_phrase,
+        &address_details.path,
+        address_details.encryption,
+    )?;
 
     let mut secret = match prepare_secret_key_for_export(multisigner, &full_address, pwd) {
         Ok(a) => {
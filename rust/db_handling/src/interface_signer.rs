@@ -1,9 +1,8 @@
 //! Utils to communicate with the Vault frontend
-use bip39::{Language, Mnemonic};
 use definitions::helpers::IdenticonStyle;
 use hex;
 use parity_scale_codec::Encode;
-use sp_core::{blake2_256, sr25519, Pair};
+use sp_core::{sr25519, Pair};
 use sp_runtime::MultiSigner;
 use std::collections::{HashMap, HashSet};
 use zeroize::{Zeroize, ZeroizeOnDrop};
@@ -26,7 +25,7 @@ use definitions::{
     },
     network_specs::{OrderedNetworkSpecs, ValidCurrentVerifier},
     qr_transfers::ContentLoadTypes,
-    users::AddressDetails,
+    users::{AddressDetails, SeedLanguage},
 };
 
 use crate::helpers::{
@@ -35,8 +34,8 @@ use crate::helpers::{
     try_get_types,
 };
 use crate::identities::{
-    derivation_check, generate_random_phrase, get_addresses_by_seed_name, get_all_addresses,
-    DerivationCheck,
+    bip39_language, derivation_check, generate_random_phrase, get_addresses_by_seed_name,
+    get_all_addresses, get_key_label, mnemonic_language_in, DerivationCheck, SEED_LANGUAGES,
 };
 use crate::{db_transactions::TrDbCold, helpers::get_valid_current_verifier};
 use crate::{Error, Result};
@@ -138,6 +137,7 @@ pub fn print_all_identities(database: &sled::Db) -> Result<Vec<MRawKey>> {
                     let public_key = multisigner_to_public(&multisigner); // to display
                     let style = address_details.identicon_style();
                     let identicon = make_identicon_from_multisigner(&multisigner, style);
+                    let label = get_key_label(database, &address_key).ok().flatten();
                     Some(MRawKey {
                         address: Address {
                             identicon,
@@ -145,6 +145,7 @@ pub fn print_all_identities(database: &sled::Db) -> Result<Vec<MRawKey>> {
                             path: address_details.path,
                             secret_exposed: address_details.secret_exposed,
                             seed_name: address_details.seed_name,
+                            label,
                         },
                         address_key: hex::encode(address_key.key()),
                         public_key: hex::encode(public_key),
@@ -162,22 +163,30 @@ pub fn keys_by_seed_name(database: &sled::Db, seed_name: &str) -> Result<MKeysNe
         .into_iter()
         .partition(|(_, address)| address.is_root());
 
-    let root = root.first().map(|root| {
-        let address = Address {
-            has_pwd: false,
-            path: String::new(),
-            seed_name: seed_name.to_string(),
-            identicon: make_identicon_from_multisigner(&root.0, root.1.identicon_style()),
-            secret_exposed: root.1.secret_exposed,
-        };
-        // TODO: root always prefix 42 for substrate.
-        let address_key = hex::encode(AddressKey::new(root.0.clone(), None).key());
-        MAddressCard {
-            base58: print_multisigner_as_base58_or_eth_address(&root.0, None, root.1.encryption),
-            address_key,
-            address,
-        }
-    });
+    let root = root
+        .first()
+        .map(|root| -> Result<MAddressCard> {
+            let address_key = AddressKey::new(root.0.clone(), None);
+            let address = Address {
+                has_pwd: false,
+                path: String::new(),
+                seed_name: seed_name.to_string(),
+                identicon: make_identicon_from_multisigner(&root.0, root.1.identicon_style()),
+                secret_exposed: root.1.secret_exposed,
+                label: get_key_label(database, &address_key)?,
+            };
+            // TODO: root always prefix 42 for substrate.
+            Ok(MAddressCard {
+                base58: print_multisigner_as_base58_or_eth_address(
+                    &root.0,
+                    None,
+                    root.1.encryption,
+                ),
+                address_key: hex::encode(address_key.key()),
+                address,
+            })
+        })
+        .transpose()?;
 
     let mut set = vec![];
     for (multisigner, address_details) in derived.into_iter() {
@@ -191,20 +200,20 @@ pub fn keys_by_seed_name(database: &sled::Db, seed_name: &str) -> Result<MKeysNe
                 Some(network_specs.specs.base58prefix),
                 network_specs.specs.encryption,
             );
-            let address_key = hex::encode(
-                AddressKey::new(multisigner.clone(), Some(network_specs.specs.genesis_hash)).key(),
-            );
+            let address_key =
+                AddressKey::new(multisigner.clone(), Some(network_specs.specs.genesis_hash));
             let address = Address {
                 path: address_details.path,
                 has_pwd: address_details.has_pwd,
                 identicon,
                 secret_exposed: address_details.secret_exposed,
                 seed_name: seed_name.to_owned(),
+                label: get_key_label(database, &address_key)?,
             };
             let key = MKeysCard {
                 address,
                 base58,
-                address_key,
+                address_key: hex::encode(address_key.key()),
                 swiped: false,
             };
             let network_specs_key = NetworkSpecsKey::from_parts(
@@ -369,6 +378,7 @@ pub fn export_key(
         identicon,
         seed_name: address_details.seed_name,
         secret_exposed: address_details.secret_exposed,
+        label: get_key_label(database, &address_key)?,
     };
 
     let network_info = MSCNetworkInfo {
@@ -511,22 +521,20 @@ fn dynamic_path_check_unhexed(
                         &multisigner,
                         address_details.identicon_style(),
                     );
-                    let address_key = hex::encode(
-                        AddressKey::new(
-                            multisigner,
-                            Some(ordered_network_specs.specs.genesis_hash),
-                        )
-                        .key(),
+                    let address_key = AddressKey::new(
+                        multisigner,
+                        Some(ordered_network_specs.specs.genesis_hash),
                     );
                     let collision_display = MAddressCard {
                         base58: address_base58,
-                        address_key,
+                        address_key: hex::encode(address_key.key()),
                         address: Address {
                             path: address_details.path,
                             has_pwd: address_details.has_pwd,
                             identicon,
                             seed_name: seed_name.to_string(),
                             secret_exposed: address_details.secret_exposed,
+                            label: get_key_label(database, &address_key).ok().flatten(),
                         },
                     };
                     NavDerivationCheck {
@@ -577,8 +585,8 @@ pub fn network_details_by_key(
     let meta: Vec<_> = get_meta_values_by_name(database, &name)?
         .into_iter()
         .map(|m| {
-            let meta_hash = blake2_256(&m.meta);
-            let meta_id_pic = pic_meta(&meta_hash);
+            let meta_hash = m.hash();
+            let meta_id_pic = pic_meta(meta_hash.as_bytes());
 
             MMetadataRecord {
                 specname: m.name,
@@ -638,8 +646,8 @@ pub fn metadata_details(
         })
         .collect();
 
-    let meta_hash = blake2_256(&meta_values.meta);
-    let meta_id_pic = pic_meta(&meta_hash);
+    let meta_hash = meta_values.hash();
+    let meta_id_pic = pic_meta(meta_hash.as_bytes());
     Ok(MManageMetadata {
         name: network_specs.name,
         version: network_version.to_string(),
@@ -707,13 +715,36 @@ pub fn purge_transactions(database: &sled::Db) -> Result<()> {
 ///
 /// List length limit is [`MAX_WORDS_DISPLAY`].
 pub fn guess(word_part: &str) -> Vec<&'static str> {
-    let dictionary = Language::English.wordlist();
-    let words = dictionary.get_words_by_prefix(word_part);
-    if words.len() > MAX_WORDS_DISPLAY {
-        words[..MAX_WORDS_DISPLAY].to_vec()
-    } else {
-        words.to_vec()
+    guess_in(word_part, &[SeedLanguage::English])
+}
+
+/// Get possible options of `bip39` words in given languages that start with
+/// user-entered word part.
+///
+/// Words are listed in the order of languages, without repetitions. List
+/// length limit is [`MAX_WORDS_DISPLAY`].
+pub fn guess_in(word_part: &str, languages: &[SeedLanguage]) -> Vec<&'static str> {
+    let mut words: Vec<&'static str> = Vec::new();
+    for language in languages.iter() {
+        let dictionary = bip39_language(*language).wordlist();
+        for word in dictionary.get_words_by_prefix(word_part).iter() {
+            if words.len() == MAX_WORDS_DISPLAY {
+                return words;
+            }
+            if !words.contains(word) {
+                words.push(word)
+            }
+        }
     }
+    words
+}
+
+/// Check that the word is in `bip39` wordlist of the language.
+fn in_wordlist(language: SeedLanguage, word: &str) -> bool {
+    bip39_language(language)
+        .wordlist()
+        .get_words_by_prefix(word)
+        .contains(&word)
 }
 
 /// Maximum word count in `bip39` standard.
@@ -782,10 +813,10 @@ impl SeedDraft {
                     let word = user_text.trim();
                     if self.added(word, None) {
                         self.user_input.clear() // added the word successfully, clear `user_input`
-                    } else if !guess(word).is_empty() {
+                    } else if !self.guess(word).is_empty() {
                         self.user_input = String::from(word) // did not add the word, there are still possible variants, keep trimmed `user_input`
                     }
-                } else if !guess(user_text).is_empty() {
+                } else if !self.guess(user_text).is_empty() {
                     self.user_input = String::from(user_text)
                 }
             }
@@ -803,11 +834,12 @@ impl SeedDraft {
     pub fn added(&mut self, word: &str, position: Option<u32>) -> bool {
         // maximum number of the words is not reached
         if self.saved.len() < BIP_CAP {
-            let guesses = guess(word);
+            let languages = self.languages();
+            let guesses = guess_in(word, &languages);
             let definitive_guess = {
                 if guesses.len() == 1 {
                     Some(guesses[0]) // only one possible variant
-                } else if guesses.contains(&word) {
+                } else if languages.iter().any(|x| in_wordlist(*x, word)) {
                     Some(word) // exactly matching variant
                 } else {
                     None // no definitive match, no addition
@@ -858,9 +890,29 @@ impl SeedDraft {
         self.saved.iter().map(|w| w.word().to_string()).collect()
     }
 
+    /// Languages with all saved words in `bip39` wordlist.
+    ///
+    /// Draft could be in any of the supported languages, and words in
+    /// different languages could coincide, so the language is not fixed
+    /// until the draft is finalized.
+    pub fn languages(&self) -> Vec<SeedLanguage> {
+        SEED_LANGUAGES
+            .into_iter()
+            .filter(|language| self.saved.iter().all(|x| in_wordlist(*language, x.word())))
+            .collect()
+    }
+
+    /// Get possible options of `bip39` words that start with user-entered
+    /// word part, in the languages of the saved words.
+    pub fn guess(&self, word_part: &str) -> Vec<&'static str> {
+        guess_in(word_part, &self.languages())
+    }
+
     /// Combines all draft elements into seed phrase proposal,
     /// and checks its validity.
     /// If valid, outputs secret seed phrase.
+    ///
+    /// Seed phrase language is detected among the draft languages.
     pub fn try_finalize(&self) -> Option<String> {
         let mut seed_phrase_proposal = String::with_capacity((WORD_LENGTH + 1) * BIP_CAP);
         for (i, x) in self.saved.iter().enumerate() {
@@ -869,7 +921,7 @@ impl SeedDraft {
             }
             seed_phrase_proposal.push_str(x.word());
         }
-        if Mnemonic::validate(&seed_phrase_proposal, Language::English).is_ok() {
+        if mnemonic_language_in(&seed_phrase_proposal, &self.languages()).is_some() {
             Some(seed_phrase_proposal)
         } else {
             seed_phrase_proposal.zeroize();
@@ -0,0 +1,647 @@
+//! Encrypted JSON keystore export and import
+//!
+//! Single derived key could be moved from Vault into Polkadot-JS or subkey as
+//! a password-protected JSON keystore, same as Polkadot-JS account export:
+//!
+//! - content is PKCS8 header, secret key, PKCS8 divider and public key
+//! - content is encrypted with xsalsa20-poly1305, with the key derived from
+//!   the password with scrypt (keystore version `3`)
+//! - encrypted content is base64-encoded in `encoded` field
+//!
+//! Keystore JSON is exported as multi-frame QR code, see [`export_keystore`].
+//! Exported key and all keys that could be derived from it are marked as
+//! having their secret exposed.
+//!
+//! Vault stores no secrets other than the seed phrases, so the keystore
+//! imported into Vault must be the one of the key already known to Vault,
+//! see [`import_keystore`]. Such key was produced outside of Vault, so it is
+//! marked as exposed as well.
+//!
+//! Keystore JSON, [`Keystore`], keystore encryption, [`encrypt`] and
+//! [`decrypt`], and PKCS8 content parsing, [`KeystorePair`], are shared with
+//! the `generate_message` verifier keystores.
+use base64::{engine::general_purpose::STANDARD, Engine};
+use crypto_secretbox::{
+    aead::{Aead, KeyInit},
+    Key, Nonce, XSalsa20Poly1305,
+};
+use serde::{Deserialize, Serialize};
+use sp_core::{ecdsa, ed25519, sr25519, Pair};
+use sp_runtime::MultiSigner;
+use zeroize::{Zeroize, Zeroizing};
+
+use definitions::{
+    crypto::Encryption,
+    helpers::{
+        make_identicon_from_multisigner, multisigner_to_public,
+        print_multisigner_as_base58_or_eth_address,
+    },
+    history::{Event, IdentityHistory},
+    keyring::{AddressKey, NetworkSpecsKey},
+    navigation::{Address, MAddressCard, MKeystoreExport, QrData},
+    users::{AddressDetails, DerivationScheme},
+};
+use qrcode_rtx::make_data_packs;
+
+use crate::{
+    bip32::Ed25519Key,
+    db_transactions::TrDbCold,
+    helpers::get_network_specs,
+    identities::{
+        exposed_addresses_batch, get_all_addresses, get_key_label, key_to_export,
+        seed_secret_with_path,
+    },
+    manage_history::events_to_batch,
+};
+use crate::{Error, Result};
+
+/// Salt length for scrypt key derivation
+const SALT_LENGTH: usize = 32;
+
+/// Length of salt followed by scrypt parameters `N`, `p`, `r`
+const SCRYPT_LENGTH: usize = SALT_LENGTH + 3 * 4;
+
+/// Nonce length for xsalsa20-poly1305
+const NONCE_LENGTH: usize = 24;
+
+/// Scrypt parameters for exported keystores, same as in Polkadot-JS
+const SCRYPT_N: u32 = 1 << 15;
+const SCRYPT_P: u32 = 1;
+const SCRYPT_R: u32 = 8;
+
+/// PKCS8 header in Polkadot-JS keystore content
+const PKCS8_HEADER: [u8; 16] = [48, 83, 2, 1, 1, 48, 5, 6, 3, 43, 101, 112, 4, 34, 4, 32];
+
+/// PKCS8 divider between the secret and the public key in Polkadot-JS keystore
+/// content
+const PKCS8_DIVIDER: [u8; 5] = [161, 35, 3, 33, 0];
+
+/// Keystore version
+pub const VERSION: &str = "3";
+
+/// Keystore encryption, key derivation followed by cipher
+pub const ENCRYPTION_TYPE: [&str; 2] = ["scrypt", "xsalsa20-poly1305"];
+
+/// Polkadot-JS keystore JSON.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Keystore {
+    /// Base64-encoded encrypted content
+    pub encoded: String,
+
+    pub encoding: KeystoreEncoding,
+
+    /// Account address, informational only
+    #[serde(default)]
+    pub address: String,
+
+    #[serde(default)]
+    pub meta: KeystoreMeta,
+}
+
+/// Keystore content and encryption description.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct KeystoreEncoding {
+    /// Content type, followed by encryption
+    ///
+    /// Vault imports and exports `pkcs8` content only. `generate_message`
+    /// verifier keystores could also have `seed` content, i.e. secret URI.
+    pub content: Vec<String>,
+
+    /// Encryption type, `scrypt` and `xsalsa20-poly1305`
+    #[serde(rename = "type")]
+    pub encryption_type: Vec<String>,
+
+    pub version: String,
+}
+
+impl Keystore {
+    /// Check keystore version and encryption, get content type and key
+    /// encryption, as declared in the keystore.
+    pub fn content_description(&self) -> Result<(&str, &str)> {
+        if self.encoding.version != VERSION || self.encoding.encryption_type != ENCRYPTION_TYPE {
+            return Err(Error::KeystoreUnsupported(format!(
+                "version {} with {} encryption",
+                self.encoding.version,
+                self.encoding.encryption_type.join(", ")
+            )));
+        }
+        match self.encoding.content.as_slice() {
+            [content, encryption] => Ok((content, encryption)),
+            _ => Err(Error::KeystoreUnsupported(String::from(
+                "unexpected content description",
+            ))),
+        }
+    }
+}
+
+/// Polkadot-JS account metadata.
+///
+/// Other metadata fields are ignored on import.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeystoreMeta {
+    /// Hexadecimal genesis hash of the network, with `0x` prefix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genesis_hash: Option<String>,
+
+    /// Account name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Export the key as password-protected Polkadot-JS keystore, in multi-frame
+/// QR code.
+///
+/// Key is identified by hexadecimal public key and hexadecimal
+/// [`NetworkSpecsKey`], and must belong to the expected seed. Key password,
+/// if the key has one, is checked and is not exported. Keystore is encrypted
+/// with `keystore_password`.
+///
+/// Keystore frames are made with [`QrData::Sensitive`] coloration, same as
+/// the secret key QR code. Exported key and the keys derived from it are marked
+/// as exposed and `SecretWasExported` event is recorded.
+///
+/// Keys with BIP32-Ed25519 derivation have extended secret key that has no
+/// place in `ed25519` PKCS8 content, and are not exported.
+pub fn export_keystore(
+    database: &sled::Db,
+    public_key: &str,
+    expected_seed_name: &str,
+    network_specs_key_hex: &str,
+    seed_phrase: &str,
+    mut key_password: Option<String>,
+    keystore_password: &str,
+) -> Result<MKeystoreExport> {
+    let network_specs_key = &NetworkSpecsKey::from_hex(network_specs_key_hex)?;
+    let (multisigner, address_details, network_specs) =
+        key_to_export(database, public_key, expected_seed_name, network_specs_key)?;
+    if address_details.derivation_scheme() == DerivationScheme::Bip32Ed25519 {
+        return Err(Error::Bip32Ed25519SecretExport);
+    }
+    let pwd = if address_details.has_pwd {
+        key_password.as_deref()
+    } else {
+        None
+    };
+
+    let mut full_address = seed_secret_with_path(
+        seed_phrase,
+        &address_details.path,
+        address_details.encryption,
+    )?;
+    let content = pkcs8_content(&multisigner, &full_address, pwd);
+    full_address.zeroize();
+    key_password.zeroize();
+    let content = content?;
+
+    let keystore = Keystore {
+        encoded: STANDARD.encode(encrypt(&content, keystore_password)?),
+        encoding: KeystoreEncoding {
+            content: vec![String::from("pkcs8"), address_details.encryption.show()],
+            encryption_type: ENCRYPTION_TYPE.iter().map(|a| a.to_string()).collect(),
+            version: VERSION.to_string(),
+        },
+        address: print_multisigner_as_base58_or_eth_address(
+            &multisigner,
+            Some(network_specs.base58prefix),
+            address_details.encryption,
+        ),
+        meta: KeystoreMeta {
+            genesis_hash: Some(format!("0x{}", hex::encode(network_specs.genesis_hash))),
+            name: Some(format!(
+                "{}{}",
+                address_details.seed_name, address_details.path
+            )),
+        },
+    };
+    let json =
+        serde_json::to_vec(&keystore).map_err(|e| Error::KeystoreUnsupported(e.to_string()))?;
+    let frames = make_data_packs(&json, 128)
+        .map_err(|e| Error::DataPacking(e.to_string()))?
+        .into_iter()
+        .map(|frame| match frame {
+            QrData::Regular { data } | QrData::Sensitive { data } => QrData::Sensitive { data },
+        })
+        .collect();
+
+    let history_batch = events_to_batch(
+        database,
+        vec![Event::SecretWasExported {
+            identity_history: IdentityHistory::get(
+                &address_details.seed_name,
+                &address_details.encryption,
+                &multisigner_to_public(&multisigner),
+                &address_details.path,
+                network_specs.genesis_hash,
+            ),
+        }],
+    )?;
+    TrDbCold::new()
+        .set_addresses(exposed_addresses_batch(database, &address_details)?) // modify addresses
+        .set_history(history_batch) // add corresponding history
+        .apply(database)?;
+
+    Ok(MKeystoreExport { frames })
+}
+
+/// Import Polkadot-JS keystore of a key known to Vault.
+///
+/// `payload` is keystore JSON, either as is or hexadecimal, as it comes from
+/// the multi-frame QR code decoding. Keystore is decrypted with
+/// `keystore_password`, the secret is used only to get the public key and is
+/// zeroized right away.
+///
+/// Vault key with the same public key and encryption is marked as exposed
+/// together with the keys derived from it, since its secret was known
+/// outside of Vault. If the key is available in several networks, the
+/// network from keystore metadata is preferred.
+pub fn import_keystore(
+    database: &sled::Db,
+    payload: &str,
+    keystore_password: &str,
+) -> Result<MAddressCard> {
+    let keystore = keystore_from_payload(payload)?;
+    let encryption = keystore_encryption(&keystore)?;
+    let encoded = STANDARD
+        .decode(&keystore.encoded)
+        .map_err(|_| Error::KeystoreUnsupported(String::from("content is not base64")))?;
+    let content = decrypt(&encoded, keystore_password)?;
+    let multisigner = KeystorePair::from_pkcs8(&content, encryption)?.multisigner();
+
+    let keystore_genesis_hash = keystore
+        .meta
+        .genesis_hash
+        .as_deref()
+        .map(|a| a.trim_start_matches("0x").to_lowercase());
+    let mut found = Vec::new();
+    for (a, address_details) in get_all_addresses(database)?.into_iter() {
+        if a == multisigner && address_details.encryption == encryption {
+            let genesis_hash = match address_details.network_id {
+                Some(ref network_specs_key) => Some(network_specs_key.genesis_hash_encryption()?.0),
+                None => None,
+            };
+            found.push((address_details, genesis_hash));
+        }
+    }
+    let preferred = found
+        .iter()
+        .position(|(_, genesis_hash)| genesis_hash.map(hex::encode) == keystore_genesis_hash);
+    let (address_details, genesis_hash) = match preferred {
+        Some(i) => found.swap_remove(i),
+        None => found
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::KeystoreKeyNotFound(multisigner.clone()))?,
+    };
+
+    TrDbCold::new()
+        .set_addresses(exposed_addresses_batch(database, &address_details)?) // modify addresses
+        .apply(database)?;
+
+    let base58prefix = match address_details.network_id {
+        Some(ref network_specs_key) => Some(
+            get_network_specs(database, network_specs_key)?
+                .specs
+                .base58prefix,
+        ),
+        None => None,
+    };
+    let address_key = AddressKey::new(multisigner.clone(), genesis_hash);
+    Ok(MAddressCard {
+        base58: print_multisigner_as_base58_or_eth_address(&multisigner, base58prefix, encryption),
+        address_key: hex::encode(address_key.key()),
+        address: Address {
+            path: address_details.path.to_string(),
+            has_pwd: address_details.has_pwd,
+            identicon: make_identicon_from_multisigner(
+                &multisigner,
+                address_details.identicon_style(),
+            ),
+            seed_name: address_details.seed_name,
+            secret_exposed: true,
+            label: get_key_label(database, &address_key)?,
+        },
+    })
+}
+
+/// Parse keystore JSON, as is or hexadecimal.
+fn keystore_from_payload(payload: &str) -> Result<Keystore> {
+    let payload = payload.trim();
+    let json = match hex::decode(payload.trim_start_matches("0x")) {
+        Ok(a) => a,
+        Err(_) => payload.as_bytes().to_vec(),
+    };
+    serde_json::from_slice(&json).map_err(|e| Error::KeystoreUnsupported(e.to_string()))
+}
+
+/// Check keystore version and encryption, get key encryption.
+fn keystore_encryption(keystore: &Keystore) -> Result<Encryption> {
+    match keystore.content_description()? {
+        ("pkcs8", encryption) => Encryption::try_from(encryption.to_owned())
+            .map_err(|e| Error::KeystoreUnsupported(e.to_string())),
+        (content, _) => Err(Error::KeystoreUnsupported(format!(
+            "content type {content}"
+        ))),
+    }
+}
+
+/// Polkadot-JS PKCS8 content for the key.
+///
+/// Secret key is 64 bytes long for `sr25519` (in ed25519-compatible expanded
+/// form) and for `ed25519` (seed followed by public key), and 32 bytes long for
+/// `ecdsa`.
+///
+/// Secret string `full_address` is checked to produce the expected public key.
+/// For the key with password the mismatch means wrong password, otherwise the
+/// seed phrase is not the one of the key.
+fn pkcs8_content(
+    multisigner: &MultiSigner,
+    full_address: &str,
+    pwd: Option<&str>,
+) -> Result<Zeroizing<Vec<u8>>> {
+    let mismatch = || match pwd {
+        Some(_) => Error::WrongPassword,
+        None => Error::KeystoreKeyMismatch(multisigner.to_owned()),
+    };
+    let (secret, public) = match multisigner {
+        MultiSigner::Ed25519(public) => {
+            let pair = match Ed25519Key::from_string(full_address, pwd)? {
+                Ed25519Key::Pair(pair) => pair,
+                Ed25519Key::Extended(_) => return Err(Error::Bip32Ed25519SecretExport),
+            };
+            if public != &pair.public() {
+                return Err(mismatch());
+            }
+            let mut secret = Zeroizing::new(pair.seed().to_vec());
+            secret.extend_from_slice(public.as_ref());
+            (secret, public.as_ref().to_vec())
+        }
+        MultiSigner::Sr25519(public) => {
+            let pair =
+                sr25519::Pair::from_string(full_address, pwd).map_err(Error::SecretStringError)?;
+            if public != &pair.public() {
+                return Err(mismatch());
+            }
+            let raw = Zeroizing::new(pair.to_raw_vec());
+            let secret_key =
+                schnorrkel::SecretKey::from_bytes(&raw).map_err(|_| Error::KeystoreSecret)?;
+            let secret = Zeroizing::new(secret_key.to_ed25519_bytes().to_vec());
+            (secret, public.as_ref().to_vec())
+        }
+        MultiSigner::Ecdsa(public) => {
+            let pair =
+                ecdsa::Pair::from_string(full_address, pwd).map_err(Error::SecretStringError)?;
+            if public != &pair.public() {
+                return Err(mismatch());
+            }
+            let secret = Zeroizing::new(pair.seed().to_vec());
+            (secret, public.as_ref().to_vec())
+        }
+    };
+    Ok(Zeroizing::new(
+        [
+            &PKCS8_HEADER[..],
+            &secret[..],
+            &PKCS8_DIVIDER[..],
+            &public[..],
+        ]
+        .concat(),
+    ))
+}
+
+/// Secret key from Polkadot-JS PKCS8 content.
+///
+/// Content is PKCS8 header, secret key, PKCS8 divider and public key. Secret
+/// key is 64 bytes long for `sr25519` (in ed25519-compatible expanded form)
+/// and for `ed25519` (seed followed by public key), and 32 bytes long for
+/// `ecdsa`.
+pub fn pkcs8_secret(content: &[u8], encryption: Encryption) -> Result<&[u8]> {
+    let secret_length = match encryption {
+        Encryption::Ed25519 | Encryption::Sr25519 => 64,
+        Encryption::Ecdsa | Encryption::Ethereum => 32,
+    };
+    let divider_start = PKCS8_HEADER.len() + secret_length;
+    if !content.starts_with(&PKCS8_HEADER)
+        || content.get(divider_start..divider_start + PKCS8_DIVIDER.len())
+            != Some(&PKCS8_DIVIDER[..])
+    {
+        return Err(Error::KeystoreSecret);
+    }
+    Ok(&content[PKCS8_HEADER.len()..divider_start])
+}
+
+/// Key pair from Polkadot-JS PKCS8 content.
+pub enum KeystorePair {
+    Ed25519(ed25519::Pair),
+    Sr25519(sr25519::Pair),
+    Ecdsa(ecdsa::Pair),
+}
+
+impl KeystorePair {
+    /// Make pair from Polkadot-JS PKCS8 content, see [`pkcs8_secret`].
+    ///
+    /// `Ethereum` encryption uses `ecdsa` pair.
+    pub fn from_pkcs8(content: &[u8], encryption: Encryption) -> Result<Self> {
+        let secret = pkcs8_secret(content, encryption)?;
+        match encryption {
+            Encryption::Ed25519 => ed25519::Pair::from_seed_slice(&secret[..32])
+                .map(Self::Ed25519)
+                .map_err(|_| Error::KeystoreSecret),
+            Encryption::Sr25519 => {
+                let secret_key = schnorrkel::SecretKey::from_ed25519_bytes(secret)
+                    .map_err(|_| Error::KeystoreSecret)?;
+                let secret_bytes = Zeroizing::new(secret_key.to_bytes());
+                sr25519::Pair::from_seed_slice(secret_bytes.as_ref())
+                    .map(Self::Sr25519)
+                    .map_err(|_| Error::KeystoreSecret)
+            }
+            Encryption::Ecdsa | Encryption::Ethereum => ecdsa::Pair::from_seed_slice(secret)
+                .map(Self::Ecdsa)
+                .map_err(|_| Error::KeystoreSecret),
+        }
+    }
+
+    /// Public key of the pair.
+    pub fn multisigner(&self) -> MultiSigner {
+        match self {
+            KeystorePair::Ed25519(pair) => MultiSigner::Ed25519(pair.public()),
+            KeystorePair::Sr25519(pair) => MultiSigner::Sr25519(pair.public()),
+            KeystorePair::Ecdsa(pair) => MultiSigner::Ecdsa(pair.public()),
+        }
+    }
+}
+
+/// Derive key from password with scrypt parameters at the start of
+/// `encoded`.
+///
+/// Scrypt parameters come from the keystore and are not trusted: large `N` or
+/// `r` would make the key derivation take unlimited memory and time. Only the
+/// Polkadot-JS parameters [`SCRYPT_N`], [`SCRYPT_P`], [`SCRYPT_R`] are
+/// accepted.
+fn scrypt_key(encoded: &[u8], password: &str) -> Result<Zeroizing<[u8; 32]>> {
+    let param = |i: usize| {
+        let start = SALT_LENGTH + 4 * i;
+        u32::from_le_bytes(encoded[start..start + 4].try_into().expect("fixed length"))
+    };
+    let (n, p, r) = (param(0), param(1), param(2));
+    if (n, p, r) != (SCRYPT_N, SCRYPT_P, SCRYPT_R) {
+        return Err(Error::KeystoreUnsupported(format!(
+            "scrypt parameters N {n}, p {p}, r {r}"
+        )));
+    }
+    let params = scrypt::Params::new(SCRYPT_N.trailing_zeros() as u8, SCRYPT_R, SCRYPT_P, 32)
+        .map_err(|_| Error::KeystoreUnsupported(String::from("scrypt parameters")))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    scrypt::scrypt(
+        password.as_bytes(),
+        &encoded[..SALT_LENGTH],
+        &params,
+        key.as_mut(),
+    )
+    .map_err(|_| Error::KeystoreUnsupported(String::from("scrypt parameters")))?;
+    Ok(key)
+}
+
+/// Decrypt keystore content: salt and scrypt parameters, nonce, and
+/// xsalsa20-poly1305 ciphertext.
+pub fn decrypt(encoded: &[u8], password: &str) -> Result<Zeroizing<Vec<u8>>> {
+    if encoded.len() < SCRYPT_LENGTH + NONCE_LENGTH {
+        return Err(Error::KeystoreUnsupported(String::from(
+            "content too short",
+        )));
+    }
+    let key = scrypt_key(encoded, password)?;
+    let cipher = XSalsa20Poly1305::new(Key::from_slice(key.as_ref()));
+    let nonce = Nonce::from_slice(&encoded[SCRYPT_LENGTH..SCRYPT_LENGTH + NONCE_LENGTH]);
+    cipher
+        .decrypt(nonce, &encoded[SCRYPT_LENGTH + NONCE_LENGTH..])
+        .map(Zeroizing::new)
+        .map_err(|_| Error::KeystorePassword)
+}
+
+/// Encrypt keystore content with the password, using random salt and nonce.
+pub fn encrypt(content: &[u8], password: &str) -> Result<Vec<u8>> {
+    let mut encoded = rand::random::<[u8; SALT_LENGTH]>().to_vec();
+    for param in [SCRYPT_N, SCRYPT_P, SCRYPT_R] {
+        encoded.extend_from_slice(&param.to_le_bytes());
+    }
+    let key = scrypt_key(&encoded, password)?;
+    let cipher = XSalsa20Poly1305::new(Key::from_slice(key.as_ref()));
+    let nonce = rand::random::<[u8; NONCE_LENGTH]>();
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), content)
+        .expect("short content is always encrypted");
+    encoded.extend_from_slice(&nonce);
+    encoded.extend_from_slice(&ciphertext);
+    Ok(encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use constants::ALICE_SEED_PHRASE;
+    use definitions::network_specs::Verifier;
+
+    use crate::{cold_default::populate_cold, identities::get_addresses_by_seed_name};
+
+    fn alice_keystore(suri: &str, password: &str) -> Keystore {
+        let multisigner =
+            MultiSigner::Sr25519(sr25519::Pair::from_string(suri, None).unwrap().public());
+        let content = pkcs8_content(&multisigner, suri, None).unwrap();
+        Keystore {
+            encoded: STANDARD.encode(encrypt(&content, password).unwrap()),
+            encoding: KeystoreEncoding {
+                content: vec![String::from("pkcs8"), String::from("sr25519")],
+                encryption_type: ENCRYPTION_TYPE.iter().map(|a| a.to_string()).collect(),
+                version: VERSION.to_string(),
+            },
+            address: String::new(),
+            meta: KeystoreMeta::default(),
+        }
+    }
+
+    #[test]
+    fn pkcs8_round_trip() {
+        let suri = format!("{ALICE_SEED_PHRASE}//Alice");
+        for encryption in [Encryption::Ed25519, Encryption::Sr25519, Encryption::Ecdsa] {
+            let multisigner = match encryption {
+                Encryption::Ed25519 => {
+                    MultiSigner::Ed25519(ed25519::Pair::from_string(&suri, None).unwrap().public())
+                }
+                Encryption::Sr25519 => {
+                    MultiSigner::Sr25519(sr25519::Pair::from_string(&suri, None).unwrap().public())
+                }
+                _ => MultiSigner::Ecdsa(ecdsa::Pair::from_string(&suri, None).unwrap().public()),
+            };
+            let content = pkcs8_content(&multisigner, &suri, None).unwrap();
+            assert_eq!(
+                KeystorePair::from_pkcs8(&content, encryption)
+                    .unwrap()
+                    .multisigner(),
+                multisigner
+            );
+        }
+    }
+
+    #[test]
+    fn pkcs8_key_mismatch() {
+        let multisigner = MultiSigner::Sr25519(
+            sr25519::Pair::from_string(&format!("{ALICE_SEED_PHRASE}//Alice"), None)
+                .unwrap()
+                .public(),
+        );
+        let bob = format!("{ALICE_SEED_PHRASE}//Bob");
+        assert!(matches!(
+            pkcs8_content(&multisigner, &bob, None),
+            Err(Error::KeystoreKeyMismatch(a)) if a == multisigner
+        ));
+        assert!(matches!(
+            pkcs8_content(&multisigner, &bob, Some("password")),
+            Err(Error::WrongPassword)
+        ));
+    }
+
+    #[test]
+    fn import_known_key() {
+        let dbname = tempfile::tempdir().unwrap();
+        let db = sled::open(&dbname).unwrap();
+        populate_cold(&db, Verifier { v: None }).unwrap();
+
+        let keystore = alice_keystore(&format!("{ALICE_SEED_PHRASE}//Alice"), "password");
+        let payload = hex::encode(serde_json::to_vec(&keystore).unwrap());
+        assert!(matches!(
+            import_keystore(&db, &payload, "wrong password"),
+            Err(Error::KeystorePassword)
+        ));
+
+        let address_card = import_keystore(&db, &payload, "password").unwrap();
+        assert_eq!(address_card.address.path, "//Alice");
+        assert_eq!(
+            address_card.base58,
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+        for (_, address_details) in get_addresses_by_seed_name(&db, "Alice").unwrap() {
+            assert_eq!(
+                address_details.secret_exposed,
+                address_details.path == "//Alice"
+            );
+        }
+    }
+    #[test]
+    fn reject_custom_scrypt_parameters() {
+        let dbname = tempfile::tempdir().unwrap();
+        let db = sled::open(&dbname).unwrap();
+        populate_cold(&db, Verifier { v: None }).unwrap();
+
+        let mut keystore = alice_keystore(&format!("{ALICE_SEED_PHRASE}//Alice"), "password");
+        let mut encoded = STANDARD.decode(&keystore.encoded).unwrap();
+        // `N` is `2^31`, memory-hard enough to never finish on the device
+        encoded[SALT_LENGTH..SALT_LENGTH + 4].copy_from_slice(&(1u32 << 31).to_le_bytes());
+        keystore.encoded = STANDARD.encode(encoded);
+        let payload = hex::encode(serde_json::to_vec(&keystore).unwrap());
+        assert!(matches!(
+            import_keystore(&db, &payload, "password"),
+            Err(Error::KeystoreUnsupported(_))
+        ));
+    }
+}
@@ -27,6 +27,9 @@
 #[cfg(feature = "active")]
 use constants::{COLD_DB_NAME_RELEASE, HOT_DB_NAME};
 
+#[cfg(feature = "active")]
+pub mod bip32;
+
 pub mod cold_default;
 
 pub mod db_transactions;
@@ -40,6 +43,12 @@ pub mod identities;
 
 pub mod interface_signer;
 
+#[cfg(feature = "active")]
+pub mod keystore;
+
+#[cfg(feature = "active")]
+pub mod templates;
+
 pub mod manage_history;
 
 mod error;
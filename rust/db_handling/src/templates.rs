@@ -0,0 +1,186 @@
+//! Derivation templates for bulk key creation
+//!
+//! Derivation template is a named derivation path pattern, with placeholders
+//! for the network name and the key index, see [`DerivationTemplate`].
+//! Templates are recorded in the settings tree of the cold database.
+//!
+//! Template is applied to a key set across the selected networks and key
+//! indices at once, for example `//staking//{index}` with indices `0..5` in
+//! ten networks makes fifty keys, all in a single database transaction, see
+//! [`apply_derivation_template`]. Resulting derivations could be checked
+//! beforehand without the seed phrase, see [`template_derivations`].
+//!
+//! Templates never contain passwords.
+use sled::Batch;
+use std::ops::Range;
+
+use constants::{DERIVATION_TEMPLATE, SETTREE};
+use definitions::{keyring::NetworkSpecsKey, users::DerivationTemplate};
+use parity_scale_codec::{Decode, Encode};
+
+use crate::{
+    db_transactions::TrDbCold,
+    helpers::{get_network_specs, open_tree, upd_id_batch},
+    identities::{create_address, derivation_check, is_passworded, DerivationCheck},
+    manage_history::events_to_batch,
+};
+use crate::{Error, Result};
+
+/// Network name used to check the template pattern.
+const SAMPLE_NETWORK_NAME: &str = "network";
+
+/// Derivation produced by the template for a single network and key index.
+pub struct TemplateDerivation {
+    /// network in which the key would be created
+    pub network_specs_key: NetworkSpecsKey,
+
+    /// derivation path, with placeholders filled in
+    pub path: String,
+
+    /// derivation status, as in [`derivation_check`]
+    pub check: DerivationCheck,
+}
+
+/// Settings tree key for the derivation template with given name.
+fn derivation_template_key(name: &str) -> Vec<u8> {
+    [DERIVATION_TEMPLATE, name.as_bytes()].concat()
+}
+
+/// Add derivation template into the database, or replace the template with
+/// the same name.
+///
+/// Template name must not be empty. Template pattern with placeholders
+/// filled in must be a valid derivation path without password.
+pub fn add_derivation_template(database: &sled::Db, template: &DerivationTemplate) -> Result<()> {
+    let sample_path = template.path(SAMPLE_NETWORK_NAME, 0);
+    if template.name.is_empty()
+        || sample_path.is_empty()
+        || !matches!(is_passworded(&sample_path), Ok(false))
+    {
+        return Err(Error::DerivationTemplateInvalid {
+            name: template.name.to_owned(),
+            pattern: template.pattern.to_owned(),
+        });
+    }
+    let mut settings_batch = Batch::default();
+    settings_batch.insert(derivation_template_key(&template.name), template.encode());
+    TrDbCold::new().set_settings(settings_batch).apply(database)
+}
+
+/// Get derivation template with given name from the database.
+pub fn get_derivation_template(database: &sled::Db, name: &str) -> Result<DerivationTemplate> {
+    let settings = open_tree(database, SETTREE)?;
+    match settings.get(derivation_template_key(name))? {
+        Some(template_encoded) => Ok(DerivationTemplate::decode(&mut &template_encoded[..])?),
+        None => Err(Error::DerivationTemplateNotFound(name.to_string())),
+    }
+}
+
+/// Get all derivation templates from the database, sorted by name.
+pub fn get_derivation_templates(database: &sled::Db) -> Result<Vec<DerivationTemplate>> {
+    let settings = open_tree(database, SETTREE)?;
+    let mut templates = Vec::new();
+    for (_, template_encoded) in settings.scan_prefix(DERIVATION_TEMPLATE).flatten() {
+        templates.push(DerivationTemplate::decode(&mut &template_encoded[..])?);
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(templates)
+}
+
+/// Remove derivation template with given name from the database.
+///
+/// Keys created with the template are not affected.
+pub fn remove_derivation_template(database: &sled::Db, name: &str) -> Result<()> {
+    get_derivation_template(database, name)?;
+    let mut settings_batch = Batch::default();
+    settings_batch.remove(derivation_template_key(name));
+    TrDbCold::new().set_settings(settings_batch).apply(database)
+}
+
+/// Derivations that the template would produce for the key set in selected
+/// networks, with their [`derivation_check`] status.
+///
+/// This function **does not** use the seed phrase and makes no changes in
+/// the database, it is a dry run of [`apply_derivation_template`].
+///
+/// Template without the index placeholder produces a single derivation for
+/// each network, regardless of the indices. Networks selected more than once
+/// are used once.
+pub fn template_derivations(
+    database: &sled::Db,
+    seed_name: &str,
+    template: &DerivationTemplate,
+    network_specs_keys: &[NetworkSpecsKey],
+    indices: Range<u32>,
+) -> Result<Vec<TemplateDerivation>> {
+    let indices = if template.is_indexed() { indices } else { 0..1 };
+    let mut derivations = Vec::new();
+    for (i, network_specs_key) in network_specs_keys.iter().enumerate() {
+        // same network selected twice
+        if network_specs_keys[..i].contains(network_specs_key) {
+            continue;
+        }
+        let network_specs = get_network_specs(database, network_specs_key)?;
+        for index in indices.clone() {
+            let path = template.path(&network_specs.specs.name, index);
+            let check = derivation_check(database, seed_name, &path, network_specs_key)?;
+            derivations.push(TemplateDerivation {
+                network_specs_key: network_specs_key.to_owned(),
+                path,
+                check,
+            });
+        }
+    }
+    Ok(derivations)
+}
+
+/// Create keys for the key set in selected networks with the derivation
+/// template.
+///
+/// All keys are created in a single database transaction. If any derivation
+/// has bad format or already exists, or any key could not be created, no keys
+/// are created at all.
+pub fn apply_derivation_template(
+    database: &sled::Db,
+    seed_name: &str,
+    seed_phrase: &str,
+    template_name: &str,
+    network_specs_keys: &[NetworkSpecsKey],
+    indices: Range<u32>,
+) -> Result<()> {
+    let template = get_derivation_template(database, template_name)?;
+    let derivations =
+        template_derivations(database, seed_name, &template, network_specs_keys, indices)?;
+
+    let mut identity_adds = Vec::new();
+    let mut current_events = Vec::new();
+    for derivation in derivations.into_iter() {
+        match derivation.check {
+            DerivationCheck::BadFormat => return Err(Error::InvalidDerivation(derivation.path)),
+            DerivationCheck::NoPassword(Some((multisigner, address_details))) => {
+                return Err(Error::DerivationExists {
+                    multisigner,
+                    address_details: Box::new(address_details),
+                    network_specs_key: derivation.network_specs_key,
+                })
+            }
+            _ => (),
+        }
+        let network_specs = get_network_specs(database, &derivation.network_specs_key)?;
+        let prep_data = create_address(
+            database,
+            &identity_adds,
+            &derivation.path,
+            Some(&network_specs.specs),
+            seed_name,
+            seed_phrase,
+        )?;
+        identity_adds = prep_data.address_prep;
+        current_events.extend_from_slice(&prep_data.history_prep);
+    }
+    let id_batch = upd_id_batch(Batch::default(), identity_adds);
+    TrDbCold::new()
+        .set_addresses(id_batch) // add created addresses
+        .set_history(events_to_batch(database, current_events)?) // add corresponding history
+        .apply(database)
+}
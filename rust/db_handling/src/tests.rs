@@ -4,9 +4,14 @@ use pretty_assertions::assert_eq;
 use constants::ALICE_SEED_PHRASE;
 
 use crate::helpers::validate_mnemonic;
+use definitions::users::SeedLanguage;
+
 use crate::{
-    identities::{check_derivation_set, generate_random_phrase, is_passworded},
-    interface_signer::{guess, SeedDraft},
+    identities::{
+        check_derivation_set, generate_random_phrase, generate_random_phrase_in, is_passworded,
+        SEED_LANGUAGES,
+    },
+    interface_signer::{guess, guess_in, SeedDraft},
 };
 
 #[test]
@@ -153,6 +158,43 @@ fn alice_recalls_seed_phrase_3() {
     assert_eq!(print, expected_print);
 }
 
+#[test]
+fn word_search_all_languages() {
+    assert_eq!(guess_in("", &SEED_LANGUAGES), guess(""));
+    assert_eq!(guess_in("sen", &[SeedLanguage::English]), guess("sen"));
+    assert!(guess_in("котик", &SEED_LANGUAGES).is_empty());
+}
+
+#[test]
+fn seed_draft_languages() {
+    let mut seed_draft = SeedDraft::initiate();
+    assert_eq!(seed_draft.languages(), SEED_LANGUAGES.to_vec());
+    seed_draft.added("bottom", None);
+    assert_eq!(seed_draft.languages(), vec![SeedLanguage::English]);
+    assert_eq!(seed_draft.guess("dri"), guess("dri"));
+}
+
+#[test]
+fn recall_seed_phrase_in_any_language() {
+    for language in SEED_LANGUAGES {
+        let seed_phrase = generate_random_phrase_in(12, language).unwrap();
+        assert!(validate_mnemonic(&seed_phrase));
+        let mut seed_draft = SeedDraft::initiate();
+        for word in seed_phrase.split_whitespace() {
+            assert!(
+                seed_draft.added(word, None),
+                "{language:?} word {word} not added"
+            );
+        }
+        assert!(seed_draft.languages().contains(&language));
+        let ready_seed = seed_draft.try_finalize().unwrap();
+        assert_eq!(
+            ready_seed.split_whitespace().collect::<Vec<_>>(),
+            seed_phrase.split_whitespace().collect::<Vec<_>>()
+        );
+    }
+}
+
 #[test]
 fn test_generate_random_seed_phrase() {
     let random_phrase = generate_random_phrase(24).unwrap();
@@ -1,10 +1,12 @@
 use constants::test_values::alice_ethereum_polkadot;
 use db_handling::cold_default::populate_all_network_specs;
+use parity_scale_codec::Encode;
 use pretty_assertions::{assert_eq, assert_ne};
 use sled::{Batch, Tree};
 use sp_core::ecdsa::Public as EcdsaPublic;
 use sp_core::sr25519::Public;
 use sp_core::H256;
+use sp_core::{ed25519, sr25519, Pair};
 use sp_runtime::MultiSigner;
 use std::collections::HashMap;
 use std::{convert::TryInto, str::FromStr};
@@ -26,17 +28,25 @@ use definitions::{
     metadata::MetaValues,
     navigation::{
         Address, DerivationCheck as NavDerivationCheck, DerivationDestination, DerivationEntry,
-        DerivationPack, Identicon, MBackup, MDeriveKey, MKeyDetails, MMMNetwork, MMNetwork,
-        MManageMetadata, MMetadataRecord, MNetworkDetails, MNetworkMenu, MRawKey, MSCNetworkInfo,
-        MTypesInfo, MVerifier, Network, NetworkSpecs, SeedNameCard,
+        DerivationPack, ExportedSet, Identicon, MBackup, MDeriveKey, MKeyDetails, MMMNetwork,
+        MMNetwork, MManageMetadata, MMetadataRecord, MNetworkDetails, MNetworkMenu, MRawKey,
+        MSCNetworkInfo, MTypesInfo, MVerifier, Network, NetworkSpecs, SeedNameCard,
     },
     network_specs::{OrderedNetworkSpecs, ValidCurrentVerifier, Verifier, VerifierValue},
-    users::AddressDetails,
+    users::{AddressDetails, DerivationScheme, DerivationTemplate, SeedLanguage},
 };
 
+use db_handling::bip32::Ed25519Key;
 use db_handling::identities::{
-    create_key_set, dynamic_derivations_response, get_all_addresses,
-    process_dynamic_derivations_v1, validate_key_password,
+    create_key_set, cut_path, dynamic_derivations_response, export_key_set_addrs,
+    generate_random_phrase_in, get_all_addresses, get_key_label, get_seed_language, is_bip32_path,
+    is_passworded, process_dynamic_derivations_v1, rename_key_set, seed_secret_with_path,
+    set_key_label, split_seed_secret, validate_key_password, ExportAddrs,
+};
+use db_handling::keystore::export_keystore;
+use db_handling::templates::{
+    add_derivation_template, apply_derivation_template, get_derivation_template,
+    get_derivation_templates, remove_derivation_template, template_derivations,
 };
 use db_handling::{
     cold_default::{
@@ -54,7 +64,7 @@ use db_handling::{
     },
     interface_signer::{
         addresses_set_seed_name_network, backup_prep, derive_prep, dynamic_path_check, export_key,
-        first_network, get_all_seed_names_with_identicons, metadata_details,
+        first_network, get_all_seed_names_with_identicons, keys_by_seed_name, metadata_details,
         network_details_by_key, print_all_identities, show_all_networks,
         show_all_networks_with_flag, show_types_status,
     },
@@ -188,6 +198,7 @@ fn print_all_ids() {
             has_pwd: false,
             path: "//Alice".to_string(),
             secret_exposed: false,
+            label: None,
         },
         network_logo: "westend".to_owned(),
     }];
@@ -325,6 +336,7 @@ fn export_alice_westend() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
         network_info: MSCNetworkInfo {
             network_title: "westend".to_string(),
@@ -387,6 +399,7 @@ fn export_alice_mythos() {
             path: derivation_path.to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
         network_info: MSCNetworkInfo {
             network_title: "mythos".to_string(),
@@ -683,6 +696,7 @@ fn path_is_known() {
                 },
                 seed_name: "Alice".to_string(),
                 secret_exposed: false,
+                label: None,
             },
         }),
         error: None,
@@ -1151,6 +1165,204 @@ fn increment_identities_3() {
     assert!(path_set.contains(&String::from("//Alice//1//2")));
 }
 
+#[test]
+fn rename_key_set_with_labels() {
+    let dbname = tempdir().unwrap();
+    let db = sled::open(dbname).unwrap();
+
+    populate_cold_no_metadata(&db, Verifier { v: None }).unwrap();
+    try_create_seed(&db, "Alice", ALICE_SEED_PHRASE, true).unwrap();
+    let westend_id = NetworkSpecsKey::from_parts(&westend_genesis(), &Encryption::Sr25519);
+    try_create_address(&db, "Alice", ALICE_SEED_PHRASE, "//Alice", &westend_id).unwrap();
+    let multisigner = MultiSigner::Sr25519(
+        sr25519::Pair::from_string(&format!("{ALICE_SEED_PHRASE}//Alice"), None)
+            .unwrap()
+            .public(),
+    );
+    let address_key = AddressKey::new(multisigner, Some(westend_genesis()));
+
+    set_key_label(&db, &address_key, Some("validator".to_string())).unwrap();
+    assert_eq!(
+        get_key_label(&db, &address_key).unwrap(),
+        Some("validator".to_string())
+    );
+    let keys = keys_by_seed_name(&db, "Alice").unwrap();
+    let labeled: Vec<&str> = keys
+        .set
+        .iter()
+        .filter_map(|card| card.key.address.label.as_deref())
+        .collect();
+    assert_eq!(labeled, vec!["validator"]);
+
+    let bob_seed_phrase = "abandon abandon abandon abandon abandon abandon abandon abandon \
+        abandon abandon abandon about";
+    try_create_seed(&db, "Bob", bob_seed_phrase, false).unwrap();
+    assert!(matches!(
+        rename_key_set(&db, "Alice", ""),
+        Err(Error::EmptySeedName)
+    ));
+    assert!(matches!(
+        rename_key_set(&db, "Alice", "Bob"),
+        Err(Error::SeedNameExists(_))
+    ));
+    assert!(matches!(
+        rename_key_set(&db, "Carol", "Dave"),
+        Err(Error::NoKnownSeeds)
+    ));
+
+    let keys_number = get_addresses_by_seed_name(&db, "Alice").unwrap().len();
+    rename_key_set(&db, "Alice", "Alice Polkadot").unwrap();
+    assert!(get_addresses_by_seed_name(&db, "Alice").unwrap().is_empty());
+    assert_eq!(
+        get_addresses_by_seed_name(&db, "Alice Polkadot")
+            .unwrap()
+            .len(),
+        keys_number
+    );
+    assert_eq!(get_seed_language(&db, "Alice").unwrap(), None);
+    assert_eq!(
+        get_seed_language(&db, "Alice Polkadot").unwrap(),
+        Some(SeedLanguage::English)
+    );
+    let history = get_history(&db).unwrap();
+    assert!(entries_contain_event(
+        &history,
+        &Event::SeedRenamed {
+            seed_name: "Alice".to_string(),
+            new_seed_name: "Alice Polkadot".to_string(),
+        }
+    ));
+
+    // label is exported
+    let exported = match export_key_set_addrs(&db, "Alice Polkadot", ExportedSet::All).unwrap() {
+        ExportAddrs::V3(exported) => exported,
+        _ => panic!("Key set must be exported with labels."),
+    };
+    assert_eq!(exported.addr.name, "Alice Polkadot");
+    assert!(exported
+        .addr
+        .derived_keys
+        .iter()
+        .any(
+            |key| key.addr_info.derivation_path.as_deref() == Some("//Alice")
+                && key.label.as_deref() == Some("validator")
+        ));
+
+    remove_seed(&db, "Alice Polkadot").unwrap();
+    assert_eq!(get_key_label(&db, &address_key).unwrap(), None);
+}
+
+#[test]
+fn derivation_template_networks() {
+    let dbname = tempdir().unwrap();
+    let db = sled::open(dbname).unwrap();
+
+    populate_cold_no_metadata(&db, Verifier { v: None }).unwrap();
+    let chainspecs = default_chainspecs();
+    let network_ids: Vec<NetworkSpecsKey> = chainspecs[..3]
+        .iter()
+        .map(|network| {
+            NetworkSpecsKey::from_parts(&network.specs.genesis_hash, &Encryption::Sr25519)
+        })
+        .collect();
+    let westend_id = NetworkSpecsKey::from_parts(&westend_genesis(), &Encryption::Sr25519);
+
+    let staking = DerivationTemplate {
+        name: "staking".to_string(),
+        pattern: "//staking//{index}".to_string(),
+    };
+    let by_network = DerivationTemplate {
+        name: "by network".to_string(),
+        pattern: "//{network}".to_string(),
+    };
+    add_derivation_template(&db, &staking).unwrap();
+    add_derivation_template(&db, &by_network).unwrap();
+    let passworded = DerivationTemplate {
+        name: "secret".to_string(),
+        pattern: "//staking///secret".to_string(),
+    };
+    assert!(matches!(
+        add_derivation_template(&db, &passworded),
+        Err(Error::DerivationTemplateInvalid { .. })
+    ));
+    assert_eq!(
+        get_derivation_templates(&db).unwrap(),
+        vec![by_network.clone(), staking.clone()]
+    );
+
+    try_create_address(&db, "Alice", ALICE_SEED_PHRASE, "//staking//1", &westend_id).unwrap();
+
+    // dry run finds the existing key
+    let derivations = template_derivations(&db, "Alice", &staking, &network_ids, 0..3).unwrap();
+    assert_eq!(derivations.len(), 9);
+    let existing: Vec<(&NetworkSpecsKey, &str)> = derivations
+        .iter()
+        .filter(|derivation| matches!(derivation.check, DerivationCheck::NoPassword(Some(_))))
+        .map(|derivation| (&derivation.network_specs_key, derivation.path.as_str()))
+        .collect();
+    assert_eq!(existing, vec![(&westend_id, "//staking//1")]);
+
+    // nothing is created if any derivation exists
+    assert!(matches!(
+        apply_derivation_template(
+            &db,
+            "Alice",
+            ALICE_SEED_PHRASE,
+            "staking",
+            &network_ids,
+            0..3
+        ),
+        Err(Error::DerivationExists { .. })
+    ));
+    assert_eq!(get_multisigner_path_set(&db).len(), 1);
+
+    apply_derivation_template(
+        &db,
+        "Alice",
+        ALICE_SEED_PHRASE,
+        "staking",
+        &network_ids,
+        2..4,
+    )
+    .unwrap();
+    assert_eq!(get_multisigner_path_set(&db).len(), 7);
+
+    // template without index makes one key per network, repeated network is
+    // used once
+    let mut repeated_ids = network_ids.clone();
+    repeated_ids.push(westend_id.to_owned());
+    apply_derivation_template(
+        &db,
+        "Alice",
+        ALICE_SEED_PHRASE,
+        "by network",
+        &repeated_ids,
+        0..5,
+    )
+    .unwrap();
+    let path_set: Vec<String> = get_multisigner_path_set(&db)
+        .into_iter()
+        .map(|(_, path)| path)
+        .collect();
+    assert_eq!(path_set.len(), 10);
+    for path in [
+        "//polkadot",
+        "//kusama",
+        "//westend",
+        "//staking//2",
+        "//staking//3",
+    ] {
+        assert!(path_set.contains(&path.to_string()), "{path} not found");
+    }
+
+    remove_derivation_template(&db, "staking").unwrap();
+    assert!(matches!(
+        get_derivation_template(&db, "staking"),
+        Err(Error::DerivationTemplateNotFound(_))
+    ));
+    assert_eq!(get_derivation_templates(&db).unwrap(), vec![by_network]);
+}
+
 #[test]
 fn creating_derivation_1() {
     let dbname = tempdir().unwrap();
@@ -2352,3 +2564,356 @@ fn test_validate_key_password() {
     assert!(!validate_key_password(&db, &address_key, ALICE_SEED_PHRASE, "wrong_pass").unwrap());
     assert!(validate_key_password(&db, &address_key, ALICE_SEED_PHRASE, "password").unwrap());
 }
+
+#[test]
+fn seed_with_passphrase() {
+    let dbname = tempdir().unwrap();
+    let db = sled::open(&dbname).unwrap();
+    populate_cold_no_metadata(&db, Verifier { v: None }).unwrap();
+
+    let seed_secret = format!("{ALICE_SEED_PHRASE}///passphrase");
+    assert_eq!(
+        split_seed_secret(&seed_secret),
+        (ALICE_SEED_PHRASE, Some("passphrase"))
+    );
+    assert_eq!(
+        seed_secret_with_path(&seed_secret, "//westend", Encryption::Sr25519).unwrap(),
+        format!("{ALICE_SEED_PHRASE}//westend///passphrase")
+    );
+
+    let westend_hex = "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e";
+    let westend_specs_key = NetworkSpecsKey::from_hex(westend_hex).unwrap();
+    create_key_set(&db, "Alice", &seed_secret, vec![westend_hex.to_string()]).unwrap();
+
+    // passphrase changes all keys of the seed, root key included
+    let root_public = sr25519::Pair::from_phrase(ALICE_SEED_PHRASE, Some("passphrase"))
+        .unwrap()
+        .0
+        .public();
+    let westend_public =
+        sr25519::Pair::from_string(&format!("{ALICE_SEED_PHRASE}//westend///passphrase"), None)
+            .unwrap()
+            .public();
+    let mut multisigners: Vec<MultiSigner> = get_addresses_by_seed_name(&db, "Alice")
+        .unwrap()
+        .into_iter()
+        .map(|(multisigner, _)| multisigner)
+        .collect();
+    multisigners.sort();
+    let mut expected_multisigners = vec![
+        MultiSigner::Sr25519(root_public),
+        MultiSigner::Sr25519(westend_public),
+    ];
+    expected_multisigners.sort();
+    assert_eq!(multisigners, expected_multisigners);
+    assert_ne!(
+        root_public,
+        sr25519::Pair::from_phrase(ALICE_SEED_PHRASE, None)
+            .unwrap()
+            .0
+            .public()
+    );
+
+    let address_key = AddressKey::new(
+        MultiSigner::Sr25519(westend_public),
+        Some(westend_genesis()),
+    );
+    assert!(validate_key_password(&db, &address_key, &seed_secret, "").unwrap());
+    assert!(!validate_key_password(&db, &address_key, ALICE_SEED_PHRASE, "").unwrap());
+
+    // keys of the seed with passphrase can not have password
+    assert!(matches!(
+        try_create_address(
+            &db,
+            "Alice",
+            &seed_secret,
+            "//Alice///password",
+            &westend_specs_key,
+        ),
+        Err(Error::PasswordWithPassphrase)
+    ));
+}
+
+#[test]
+fn key_set_in_other_language() {
+    let dbname = tempdir().unwrap();
+    let db = sled::open(&dbname).unwrap();
+    populate_cold_no_metadata(&db, Verifier { v: None }).unwrap();
+
+    let seed_phrase = generate_random_phrase_in(12, SeedLanguage::Italian).unwrap();
+    let mnemonic = bip39::Mnemonic::from_phrase(&seed_phrase, bip39::Language::Italian).unwrap();
+    let english_phrase =
+        bip39::Mnemonic::from_entropy(mnemonic.entropy(), bip39::Language::English)
+            .unwrap()
+            .into_phrase();
+
+    let westend_hex = "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e";
+    create_key_set(&db, "Alice", &seed_phrase, vec![westend_hex.to_string()]).unwrap();
+    assert_eq!(
+        get_seed_language(&db, "Alice").unwrap(),
+        Some(SeedLanguage::Italian)
+    );
+
+    // keys are same as for the English seed phrase with the same entropy
+    let root_public = sr25519::Pair::from_phrase(&english_phrase, None)
+        .unwrap()
+        .0
+        .public();
+    let westend_public = sr25519::Pair::from_string(&format!("{english_phrase}//westend"), None)
+        .unwrap()
+        .public();
+    let mut multisigners: Vec<MultiSigner> = get_addresses_by_seed_name(&db, "Alice")
+        .unwrap()
+        .into_iter()
+        .map(|(multisigner, _)| multisigner)
+        .collect();
+    multisigners.sort();
+    let mut expected_multisigners = vec![
+        MultiSigner::Sr25519(root_public),
+        MultiSigner::Sr25519(westend_public),
+    ];
+    expected_multisigners.sort();
+    assert_eq!(multisigners, expected_multisigners);
+
+    remove_seed(&db, "Alice").unwrap();
+    assert_eq!(get_seed_language(&db, "Alice").unwrap(), None);
+}
+
+#[test]
+fn seed_language_removed_with_last_key() {
+    let dbname = tempdir().unwrap();
+    let db = sled::open(&dbname).unwrap();
+    populate_cold_no_metadata(&db, Verifier { v: None }).unwrap();
+
+    let seed_phrase = generate_random_phrase_in(12, SeedLanguage::French).unwrap();
+    try_create_seed(&db, "Alice", &seed_phrase, false).unwrap();
+    let addresses = get_addresses_by_seed_name(&db, "Alice").unwrap();
+    assert!(addresses.len() > 1);
+
+    // language is kept while the key set has keys
+    for (multisigner, address_details) in addresses.iter() {
+        assert_eq!(
+            get_seed_language(&db, "Alice").unwrap(),
+            Some(SeedLanguage::French)
+        );
+        let network_specs_key = address_details.network_id.as_ref().unwrap();
+        remove_key(&db, multisigner, network_specs_key).unwrap();
+    }
+    assert!(get_addresses_by_seed_name(&db, "Alice").unwrap().is_empty());
+    assert_eq!(get_seed_language(&db, "Alice").unwrap(), None);
+}
+
+#[test]
+fn bip32_ethereum_key() {
+    let dbname = tempdir().unwrap();
+    let db = sled::open(dbname).unwrap();
+    populate_all_network_specs(&db).unwrap();
+
+    // well-known test seed phrase, same address is produced by MetaMask
+    let seed_phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+        abandon abandon about";
+    try_create_seed(&db, "Abandon", seed_phrase, false).unwrap();
+
+    let path = "m/44'/60'/0'/0/0";
+    assert!(is_bip32_path(path));
+    assert!(!is_passworded(path).unwrap());
+    assert!(matches!(cut_path(path), Err(Error::LostPwd)));
+
+    let mythos_key = NetworkSpecsKey::from_parts(&mythos_genesis(), &Encryption::Ethereum);
+    let westend_key = NetworkSpecsKey::from_parts(&westend_genesis(), &Encryption::Sr25519);
+    assert!(matches!(
+        derivation_check(&db, "Abandon", path, &mythos_key).unwrap(),
+        DerivationCheck::NoPassword(None)
+    ));
+    assert!(matches!(
+        derivation_check(&db, "Abandon", path, &westend_key).unwrap(),
+        DerivationCheck::BadFormat
+    ));
+    assert!(matches!(
+        try_create_address(&db, "Abandon", seed_phrase, path, &westend_key),
+        Err(Error::InvalidDerivation(_))
+    ));
+
+    try_create_address(&db, "Abandon", seed_phrase, path, &mythos_key).unwrap();
+    let (multisigner, address_details) =
+        match derivation_check(&db, "Abandon", path, &mythos_key).unwrap() {
+            DerivationCheck::NoPassword(Some(found)) => found,
+            _ => panic!("BIP32 key must be found."),
+        };
+    assert_eq!(address_details.path, path);
+    assert!(!address_details.has_pwd);
+    assert_eq!(
+        hex::encode(multisigner_to_public(&multisigner)),
+        "0237b0bb7a8288d38ed49a524b5dc98cff3eb5ca824c9f9dc0dfdb3d9cd600f299"
+    );
+    assert_eq!(
+        definitions::helpers::print_multisigner_as_base58_or_eth_address(
+            &multisigner,
+            Some(29972),
+            Encryption::Ethereum
+        ),
+        "0x9858effd232b4033e47d90003d41ec34ecaeda94"
+    );
+
+    // Substrate-style derivations are still available for Ethereum networks
+    assert!(matches!(
+        derivation_check(&db, "Abandon", "//mythos", &mythos_key).unwrap(),
+        DerivationCheck::NoPassword(None)
+    ));
+}
+
+#[test]
+fn bip32_ed25519_key() {
+    let dbname = tempdir().unwrap();
+    let db = sled::open(dbname).unwrap();
+    populate_cold_no_metadata(&db, Verifier { v: None }).unwrap();
+
+    // ed25519 version of westend network
+    let mut westend_ed25519 = default_chainspecs()
+        .into_iter()
+        .find(|network| network.specs.name == "westend")
+        .unwrap();
+    westend_ed25519.specs.encryption = Encryption::Ed25519;
+    let network_specs_key = NetworkSpecsKey::from_parts(&westend_genesis(), &Encryption::Ed25519);
+    let mut batch = Batch::default();
+    batch.insert(network_specs_key.key(), westend_ed25519.encode());
+    TrDbCold::new().set_network_specs(batch).apply(&db).unwrap();
+
+    let seed_phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+        abandon abandon about";
+    try_create_seed(&db, "Abandon", seed_phrase, false).unwrap();
+
+    // BIP32-Ed25519 paths, hardened junctions only
+    let path = "m/44'/354'/0'/0'/0'";
+    assert!(matches!(
+        derivation_check(&db, "Abandon", path, &network_specs_key).unwrap(),
+        DerivationCheck::NoPassword(None)
+    ));
+    assert!(matches!(
+        derivation_check(&db, "Abandon", "m/44'/354'/0'/0/0", &network_specs_key).unwrap(),
+        DerivationCheck::BadFormat
+    ));
+
+    try_create_address(&db, "Abandon", seed_phrase, path, &network_specs_key).unwrap();
+    let (multisigner, address_details) =
+        match derivation_check(&db, "Abandon", path, &network_specs_key).unwrap() {
+            DerivationCheck::NoPassword(Some(found)) => found,
+            _ => panic!("BIP32-Ed25519 key must be found."),
+        };
+    assert_eq!(
+        address_details.derivation_scheme(),
+        DerivationScheme::Bip32Ed25519
+    );
+    assert_eq!(
+        hex::encode(multisigner_to_public(&multisigner)),
+        "65d498f6a0bcc8859e997a1bc1b6c3d67f7faa5bc748e21d79b099676ffd79e1"
+    );
+
+    // same extended secret key is used for signing
+    let secret = seed_secret_with_path(seed_phrase, path, Encryption::Ed25519).unwrap();
+    let key = Ed25519Key::from_string(&secret, None).unwrap();
+    assert_eq!(MultiSigner::Ed25519(key.public()), multisigner);
+    assert!(ed25519::Pair::verify(
+        &key.sign(b"message"),
+        b"message",
+        &key.public()
+    ));
+
+    // extended secret key is not an ed25519 seed and could not be exported
+    assert!(matches!(
+        export_keystore(
+            &db,
+            &hex::encode(multisigner_to_public(&multisigner)),
+            "Abandon",
+            &hex::encode(network_specs_key.key()),
+            seed_phrase,
+            None,
+            "password",
+        ),
+        Err(Error::Bip32Ed25519SecretExport)
+    ));
+    assert!(matches!(
+        export_secret_key(
+            &db,
+            &hex::encode(multisigner_to_public(&multisigner)),
+            "Abandon",
+            &hex::encode(network_specs_key.key()),
+            seed_phrase,
+            None,
+        ),
+        Err(Error::Bip32Ed25519SecretExport)
+    ));
+
+    // paths not starting with `m/` are derived with Substrate junctions in
+    // the same ed25519 network
+    let substrate_path = "//m//44";
+    try_create_address(
+        &db,
+        "Abandon",
+        seed_phrase,
+        substrate_path,
+        &network_specs_key,
+    )
+    .unwrap();
+    let (multisigner, address_details) =
+        match derivation_check(&db, "Abandon", substrate_path, &network_specs_key).unwrap() {
+            DerivationCheck::NoPassword(Some(found)) => found,
+            _ => panic!("Substrate key must be found."),
+        };
+    assert_eq!(
+        address_details.derivation_scheme(),
+        DerivationScheme::Substrate
+    );
+    let pair = ed25519::Pair::from_string(&format!("{seed_phrase}{substrate_path}"), None).unwrap();
+    assert_eq!(MultiSigner::Ed25519(pair.public()), multisigner);
+}
+
+#[test]
+fn export_alice_keystore() {
+    let dbname = tempdir().unwrap();
+    let db = sled::open(dbname).unwrap();
+    populate_cold(&db, Verifier { v: None }).unwrap();
+
+    let westend_hex = "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e";
+    let alice_public = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+    assert!(matches!(
+        export_keystore(
+            &db,
+            alice_public,
+            "Bob",
+            westend_hex,
+            ALICE_SEED_PHRASE,
+            None,
+            "password"
+        ),
+        Err(Error::SeedNameNotMatching { .. })
+    ));
+
+    let keystore = export_keystore(
+        &db,
+        alice_public,
+        "Alice",
+        westend_hex,
+        ALICE_SEED_PHRASE,
+        None,
+        "password",
+    )
+    .unwrap();
+    assert!(!keystore.frames.is_empty());
+    assert!(keystore
+        .frames
+        .iter()
+        .all(|frame| matches!(frame, definitions::navigation::QrData::Sensitive { .. })));
+
+    for (_, address_details) in get_addresses_by_seed_name(&db, "Alice").unwrap() {
+        assert_eq!(
+            address_details.secret_exposed,
+            address_details.path == "//Alice"
+        );
+    }
+    let history = get_history(&db).unwrap();
+    assert!(history.iter().any(|(_, entry)| entry
+        .events
+        .iter()
+        .any(|event| matches!(event, Event::SecretWasExported { .. }))));
+}
@@ -115,6 +115,35 @@ pub enum Error {
 
     #[error("Cannot convert {0} to valid encryption.")]
     UnknownEncryption(String),
+
+    /// Compressed content has unknown compression code.
+    #[error("Compression with code {0} is not supported.")]
+    UnknownCompression(u8),
+
+    /// Decompressed content exceeds the allowed size.
+    #[error("Decompressed content exceeds the limit of {0} bytes.")]
+    DecompressedTooLarge(usize),
+
+    /// Payload is shorter than its prelude and announced parts.
+    #[error("Payload is too short.")]
+    PayloadTooShort,
+
+    /// Payload does not start with `0x53` Substrate prefix.
+    #[error("Payload is expected to start with 0x53, this one starts with 0x{0:02x}.")]
+    PayloadNotSubstrate(u8),
+
+    /// Payload has unknown payload type code.
+    #[error("Payload type with code 0x{0:02x} is not supported.")]
+    PayloadKindNotSupported(u8),
+
+    /// Payload has unknown encryption code.
+    #[error("Payload encryption with code 0x{0:02x} is not supported.")]
+    PayloadEncryptionNotSupported(u8),
+
+    /// Payload type is not an update, i.e. not `load_metadata`, `load_types`
+    /// or `add_specs`.
+    #[error("Payload type with code 0x{0:02x} is not an update.")]
+    PayloadNotUpdate(u8),
 }
 
 /// Error decoding transfer content
@@ -149,7 +178,7 @@ impl TransferContent {
 /// Intrinsic problems of the metadata making it unsuitable for Vault use
 #[derive(Debug, PartialEq, thiserror::Error)]
 pub enum MetadataError {
-    /// Supported are `V12`, `V13`, and `V14` versions of
+    /// Supported are `V12`, `V13`, `V14`, and `V15` versions of
     /// [`RuntimeMetadata`](https://docs.rs/frame-metadata/15.0.0/frame_metadata/enum.RuntimeMetadata.html).
     ///
     /// Any other version results in error.
@@ -192,4 +221,15 @@ pub enum MetadataError {
     /// [`RuntimeMetadata`](https://docs.rs/frame-metadata/15.0.0/frame_metadata/enum.RuntimeMetadata.html)
     #[error("metadata body is not decodable")]
     UnableToDecode,
+
+    /// Only metadata with `V14` version of
+    /// [`RuntimeMetadata`](https://docs.rs/frame-metadata/15.0.0/frame_metadata/enum.RuntimeMetadata.html)
+    /// could be trimmed.
+    #[error("only V14 metadata could be trimmed")]
+    TrimUnsupported,
+
+    /// Trimmed metadata does not produce the same network information or the
+    /// same metadata digest as the full metadata it was made from.
+    #[error("trimmed metadata does not match the full metadata")]
+    TrimmedMismatch,
 }
@@ -13,8 +13,8 @@ use crate::error::MetadataError;
 // are published
 #[derive(Debug, thiserror::Error)]
 pub enum Wasm {
-    /// Failed to make `Metadata_metadata` call on data extracted from `wasm`
-    /// file.
+    /// Failed to make `Metadata_metadata` or `Core_version` call on data
+    /// extracted from `wasm` file.
     #[error(transparent)]
     Executor(#[from] sc_executor_common::error::Error),
 
@@ -22,6 +22,10 @@ pub enum Wasm {
     #[error("metadata from file could not be decoded")]
     DecodingMetadata,
 
+    /// Runtime version extracted from `wasm` code could not be decoded.
+    #[error("runtime version from wasm code could not be decoded")]
+    DecodingRuntimeVersion,
+
     /// Metadata extracted from `wasm` file is not suitable to be used in
     /// Vault.
     ///
@@ -56,7 +56,7 @@ impl MetaValuesDisplay {
         Self {
             name: meta_values.name.to_string(),
             version: meta_values.version,
-            meta_hash: blake2_256(&meta_values.meta).into(),
+            meta_hash: meta_values.hash(),
         }
     }
 }
@@ -91,7 +91,7 @@ impl MetaValuesExport {
         Self {
             name: meta_values.name.to_string(),
             version: meta_values.version,
-            meta_hash: blake2_256(&meta_values.meta).into(),
+            meta_hash: meta_values.hash(),
             signed_by: signed_by.to_owned(),
         }
     }
@@ -504,6 +504,12 @@ pub enum Event {
 
     /// Database was initiated
     DatabaseInitiated,
+
+    /// User renamed a seed
+    SeedRenamed {
+        seed_name: String,
+        new_seed_name: String,
+    },
 }
 
 /// History log individual entry
@@ -529,6 +535,7 @@ pub fn all_events_preview() -> Vec<Event> {
         optional_base58prefix: Some(42),
         warn_incomplete_extensions: false,
         meta: Vec::new(),
+        full_hash: None,
     };
     let public = [
         142, 175, 4, 21, 22, 135, 115, 99, 38, 201, 254, 161, 126, 37, 252, 82, 135, 97, 54, 147,
@@ -691,5 +698,9 @@ pub fn all_events_preview() -> Vec<Event> {
         },
         Event::HistoryCleared,
         Event::DatabaseInitiated,
+        Event::SeedRenamed {
+            seed_name: String::from("Alice"),
+            new_seed_name: String::from("Alice Polkadot"),
+        },
     ]
 }
@@ -34,6 +34,8 @@ pub mod metadata;
 
 pub mod network_specs;
 
+pub mod payload;
+
 pub mod qr_transfers;
 
 pub mod types;
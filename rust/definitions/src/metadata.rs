@@ -15,8 +15,17 @@
 //!
 //! Metadata is stored both in cold and in hot databases tree `METATREE` as
 //! SCALE-encoded [`MetaValues`] under key [`MetaKey`].  
+//!
+//! Cold database could store metadata in trimmed form, without documentation,
+//! storage entries, events, errors and the types not needed for transaction
+//! decoding, see [`MetaStorage`] and [`MetaValues::trim`].
 
-use frame_metadata::{decode_different::DecodeDifferent, v14::RuntimeMetadataV14, RuntimeMetadata};
+use frame_metadata::{
+    decode_different::DecodeDifferent,
+    v14::{ExtrinsicMetadata, PalletMetadata, RuntimeMetadataV14, SignedExtensionMetadata},
+    v15::RuntimeMetadataV15,
+    RuntimeMetadata,
+};
 use parity_scale_codec::{Decode, Encode};
 #[cfg(feature = "active")]
 use sc_executor_common::{
@@ -25,15 +34,15 @@ use sc_executor_common::{
 };
 #[cfg(feature = "active")]
 use sc_executor_wasmi::create_runtime;
+use scale_info::{form::PortableForm, Field, Path, PortableRegistry, Type, TypeDef, TypeDefTuple};
 use sled::IVec;
-#[cfg(feature = "active")]
-use sp_core::H256;
+use sp_core::{blake2_256, H256};
 #[cfg(feature = "active")]
 use sp_io::SubstrateHostFunctions;
 use sp_version::RuntimeVersion;
 #[cfg(feature = "active")]
 use sp_wasm_interface::HostFunctions;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[cfg(feature = "active")]
 use crate::{crypto::Encryption, error_active::Wasm, helpers::unhex, keyring::AddressBookKey};
@@ -91,6 +100,9 @@ pub struct MetaValues {
 
     /// raw metadata
     pub meta: Vec<u8>,
+
+    /// Hash of the full metadata, if `meta` is the trimmed version of it
+    pub full_hash: Option<H256>,
 }
 
 impl MetaValues {
@@ -105,7 +117,7 @@ impl MetaValues {
         version: u32,
         meta_encoded: IVec,
     ) -> Result<Self> {
-        let meta_values = Self::from_slice_metadata(&meta_encoded)?;
+        let meta_values = Self::from_stored(&meta_encoded)?;
         if (meta_values.name != name) || (meta_values.version != version) {
             return Err(Error::MetadataMismatch {
                 this_name: name.to_string(),
@@ -139,9 +151,87 @@ impl MetaValues {
             optional_base58prefix: meta_info.optional_base58prefix,
             warn_incomplete_extensions: meta_info.warn_incomplete_extensions,
             meta: meta_slice.to_vec(),
+            full_hash: None,
+        })
+    }
+
+    /// Gets [`MetaValues`] from the value stored in database tree `METATREE`,
+    /// with either full or trimmed metadata
+    pub fn from_stored(stored: &[u8]) -> Result<Self> {
+        let (full_hash, meta) = split_stored(stored)?;
+        Ok(Self {
+            full_hash,
+            ..Self::from_slice_metadata(meta)?
         })
     }
 
+    /// Value to be stored in database tree `METATREE`
+    ///
+    /// Trimmed metadata is stored with [`TRIMMED_META_PREFIX`] and the hash of
+    /// the full metadata.
+    pub fn to_stored(&self) -> Vec<u8> {
+        match self.full_hash {
+            Some(full_hash) => [TRIMMED_META_PREFIX, full_hash.as_bytes(), &self.meta].concat(),
+            None => self.meta.to_vec(),
+        }
+    }
+
+    /// Hash of the full metadata
+    ///
+    /// For trimmed metadata this is the hash of the metadata it was made
+    /// from, so that the metadata is identified by the same hash regardless of
+    /// the form it is stored in.
+    pub fn hash(&self) -> H256 {
+        self.full_hash
+            .unwrap_or_else(|| blake2_256(&self.meta).into())
+    }
+
+    /// Gets `RuntimeMetadata` version, from the byte following metadata
+    /// prelude
+    pub fn metadata_version(&self) -> Result<u32> {
+        match self.meta.get(META_PREFIX.len()) {
+            Some(version) => Ok(*version as u32),
+            None => Err(MetadataError::UnableToDecode.into()),
+        }
+    }
+
+    /// Makes trimmed [`MetaValues`], with all documentation, storage entries,
+    /// events and errors removed from the metadata
+    ///
+    /// None of these is needed for transaction decoding. Types registry is
+    /// reduced to the types reachable from the extrinsic, the calls, the
+    /// signed extensions and the constants. Types that are not reachable are
+    /// replaced with empty tuples, so that the type ids remain unchanged.
+    /// Metadata digest covers only the types reachable from the extrinsic,
+    /// therefore the trimmed metadata has the same metadata digest as the
+    /// full one. Call documentation is not displayed for transactions decoded
+    /// with trimmed metadata.
+    ///
+    /// Only `V14` metadata could be trimmed.
+    pub fn trim(&self) -> Result<Self> {
+        if self.full_hash.is_some() {
+            return Ok(self.clone());
+        }
+        let mut meta_v14 = match runtime_metadata_from_slice(&self.meta)? {
+            RuntimeMetadata::V14(meta_v14) => meta_v14,
+            _ => return Err(MetadataError::TrimUnsupported.into()),
+        };
+        strip_v14(&mut meta_v14);
+        let meta = [&META_PREFIX[..], &RuntimeMetadata::V14(meta_v14).encode()].concat();
+        let trimmed = Self {
+            full_hash: Some(self.hash()),
+            ..Self::from_slice_metadata(&meta)?
+        };
+        if (trimmed.name != self.name)
+            || (trimmed.version != self.version)
+            || (trimmed.optional_base58prefix != self.optional_base58prefix)
+            || (trimmed.warn_incomplete_extensions != self.warn_incomplete_extensions)
+        {
+            return Err(MetadataError::TrimmedMismatch.into());
+        }
+        Ok(trimmed)
+    }
+
     /// Gets [`MetaValues`] from `wasm` file
     ///
     /// Could be used to generate metadata updates before metadata release.
@@ -163,6 +253,150 @@ impl MetaValues {
     }
 }
 
+/// Prelude of raw metadata
+const META_PREFIX: [u8; 4] = *b"meta";
+
+/// Prelude of trimmed metadata entries in database tree `METATREE`
+pub const TRIMMED_META_PREFIX: &[u8] = b"trim";
+
+/// `RuntimeMetadata` versions supported by the Vault, in increasing order
+pub const SUPPORTED_METADATA_VERSIONS: &[u32] = &[12, 13, 14, 15];
+
+/// Form in which the Vault stores the network metadata from `load_metadata`
+/// updates
+///
+/// Stored SCALE-encoded in cold database tree `SETTREE`. Applies to the
+/// metadata loaded after the setting is changed, already stored metadata
+/// remains as is.
+#[derive(Clone, Copy, Debug, Decode, Default, Encode, PartialEq, Eq)]
+pub enum MetaStorage {
+    /// Full metadata, as received
+    #[default]
+    Full,
+
+    /// Metadata trimmed with [`MetaValues::trim`], checked to have the same
+    /// metadata digest as the received one
+    Trimmed,
+
+    /// No metadata is stored, transactions are decoded only with metadata
+    /// proofs
+    ProofOnly,
+}
+
+/// Split value from database tree `METATREE` into the hash of the full
+/// metadata, for trimmed metadata, and raw metadata itself
+fn split_stored(stored: &[u8]) -> Result<(Option<H256>, &[u8])> {
+    match stored.strip_prefix(TRIMMED_META_PREFIX) {
+        Some(trimmed) => {
+            if trimmed.len() < H256::len_bytes() {
+                return Err(MetadataError::NotMeta.into());
+            }
+            let (full_hash, meta) = trimmed.split_at(H256::len_bytes());
+            Ok((Some(H256::from_slice(full_hash)), meta))
+        }
+        None => Ok((None, stored)),
+    }
+}
+
+/// Remove documentation, storage entries, events, errors and unreachable
+/// types from
+/// [`RuntimeMetadataV14`](https://docs.rs/frame-metadata/15.0.0/frame_metadata/v14/struct.RuntimeMetadataV14.html)
+fn strip_v14(meta_v14: &mut RuntimeMetadataV14) {
+    fn strip_fields(fields: &mut [Field<PortableForm>]) {
+        for field in fields.iter_mut() {
+            field.docs.clear();
+        }
+    }
+    for portable_type in meta_v14.types.types.iter_mut() {
+        portable_type.ty.docs.clear();
+        match &mut portable_type.ty.type_def {
+            TypeDef::Composite(composite) => strip_fields(&mut composite.fields),
+            TypeDef::Variant(variant) => {
+                for x in variant.variants.iter_mut() {
+                    x.docs.clear();
+                    strip_fields(&mut x.fields);
+                }
+            }
+            _ => (),
+        }
+    }
+    for pallet in meta_v14.pallets.iter_mut() {
+        pallet.storage = None;
+        pallet.event = None;
+        pallet.error = None;
+        for constant in pallet.constants.iter_mut() {
+            constant.docs.clear();
+        }
+    }
+
+    let mut roots = vec![meta_v14.ty.id, meta_v14.extrinsic.ty.id];
+    for signed_extension in meta_v14.extrinsic.signed_extensions.iter() {
+        roots.push(signed_extension.ty.id);
+        roots.push(signed_extension.additional_signed.id);
+    }
+    for pallet in meta_v14.pallets.iter() {
+        if let Some(calls) = &pallet.calls {
+            roots.push(calls.ty.id);
+        }
+        roots.extend(pallet.constants.iter().map(|constant| constant.ty.id));
+    }
+    let reachable = reachable_types(&meta_v14.types, roots);
+    for portable_type in meta_v14.types.types.iter_mut() {
+        if !reachable.contains(&portable_type.id) {
+            portable_type.ty = Type {
+                path: Path {
+                    segments: Vec::new(),
+                },
+                type_params: Vec::new(),
+                type_def: TypeDef::Tuple(TypeDefTuple { fields: Vec::new() }),
+                docs: Vec::new(),
+            };
+        }
+    }
+}
+
+/// Ids of the types in the registry reachable from the `roots`, through the
+/// type definitions and the type parameters.
+fn reachable_types(registry: &PortableRegistry, roots: Vec<u32>) -> HashSet<u32> {
+    let mut reachable = HashSet::new();
+    let mut queue = roots;
+    while let Some(id) = queue.pop() {
+        if !reachable.insert(id) {
+            continue;
+        }
+        let ty = match registry.resolve(id) {
+            Some(a) => a,
+            None => continue,
+        };
+        queue.extend(
+            ty.type_params
+                .iter()
+                .filter_map(|x| x.ty.as_ref().map(|ty| ty.id)),
+        );
+        match &ty.type_def {
+            TypeDef::Composite(composite) => {
+                queue.extend(composite.fields.iter().map(|field| field.ty.id))
+            }
+            TypeDef::Variant(variant) => queue.extend(
+                variant
+                    .variants
+                    .iter()
+                    .flat_map(|x| x.fields.iter().map(|field| field.ty.id)),
+            ),
+            TypeDef::Sequence(sequence) => queue.push(sequence.type_param.id),
+            TypeDef::Array(array) => queue.push(array.type_param.id),
+            TypeDef::Tuple(tuple) => queue.extend(tuple.fields.iter().map(|field| field.id)),
+            TypeDef::Primitive(_) => (),
+            TypeDef::Compact(compact) => queue.push(compact.type_param.id),
+            TypeDef::BitSequence(bit_sequence) => {
+                queue.push(bit_sequence.bit_store_type.id);
+                queue.push(bit_sequence.bit_order_type.id);
+            }
+        }
+    }
+    reachable
+}
+
 /// Extracts raw metadata in `Vec<u8>` format from `wasm` file.
 ///
 /// Is used only on Active side, to generate metadata updates before metadata
@@ -170,7 +404,32 @@ impl MetaValues {
 #[cfg(feature = "active")]
 pub fn convert_wasm_into_metadata(filename: &str) -> Result<Vec<u8>> {
     let buffer = std::fs::read(filename).map_err(Wasm::File)?;
-    let runtime_blob = RuntimeBlob::uncompress_if_needed(&buffer).map_err(Wasm::WasmError)?;
+    metadata_from_wasm_code(&buffer)
+}
+
+/// Extracts raw metadata in `Vec<u8>` format from runtime `wasm` code, for
+/// example, from `:code` genesis storage entry of a chain spec.
+#[cfg(feature = "active")]
+pub fn metadata_from_wasm_code(code: &[u8]) -> Result<Vec<u8>> {
+    let data = call_wasm_code(code, "Metadata_metadata")?;
+    Ok(<Vec<u8>>::decode(&mut &data[..]).map_err(|_| Wasm::DecodingMetadata)?)
+}
+
+/// Gets [`RuntimeVersion`] from runtime `wasm` code through `Core_version`
+/// call.
+///
+/// Runtime version determines the state version used for the network storage.
+#[cfg(feature = "active")]
+pub fn runtime_version_from_wasm_code(code: &[u8]) -> Result<RuntimeVersion> {
+    let data = call_wasm_code(code, "Core_version")?;
+    Ok(RuntimeVersion::decode(&mut &data[..]).map_err(|_| Wasm::DecodingRuntimeVersion)?)
+}
+
+/// Calls exported runtime function with no arguments on the runtime `wasm`
+/// code, compressed or not.
+#[cfg(feature = "active")]
+fn call_wasm_code(code: &[u8], method: &str) -> Result<Vec<u8>> {
+    let runtime_blob = RuntimeBlob::uncompress_if_needed(code).map_err(Wasm::WasmError)?;
     let wasmi_runtime = create_runtime(
         runtime_blob,
         64,
@@ -179,10 +438,9 @@ pub fn convert_wasm_into_metadata(filename: &str) -> Result<Vec<u8>> {
     )
     .map_err(Wasm::WasmError)?;
     let mut wasmi_instance = wasmi_runtime.new_instance().map_err(Wasm::Executor)?;
-    let data = wasmi_instance
-        .call(InvokeMethod::Export("Metadata_metadata"), &[])
-        .map_err(Wasm::Executor)?;
-    Ok(<Vec<u8>>::decode(&mut &data[..]).map_err(|_| Wasm::DecodingMetadata)?)
+    Ok(wasmi_instance
+        .call(InvokeMethod::Export(method), &[])
+        .map_err(Wasm::Executor)?)
 }
 
 /// Get [`MetaInfo`] from
@@ -196,7 +454,7 @@ pub fn convert_wasm_into_metadata(filename: &str) -> Result<Vec<u8>> {
 ///
 /// `RuntimeMetadata` suitable for use in Vault:
 ///
-/// - must be of runtime version `V12` or above
+/// - must be of runtime version `V12` to `V15`
 /// - must have 'System' pallet  
 /// - must have `Version` constant in `System` pallet, SCALE-decodeable  
 /// - can have `SS58Prefix` constant in `System` pallet, and if it does, the
@@ -273,7 +531,36 @@ pub fn info_from_metadata(runtime_metadata: &RuntimeMetadata) -> Result<MetaInfo
                     break;
                 }
             }
-            warn_incomplete_extensions = need_v14_warning(metadata_v14);
+            warn_incomplete_extensions = need_extensions_warning(
+                metadata_v14
+                    .extrinsic
+                    .signed_extensions
+                    .iter()
+                    .map(|x| x.identifier.as_str()),
+            );
+        }
+        RuntimeMetadata::V15(metadata_v15) => {
+            for x in metadata_v15.pallets.iter() {
+                if x.name == "System" {
+                    system_block = true;
+                    for y in x.constants.iter() {
+                        if y.name == "Version" {
+                            runtime_version_encoded = Some(&y.value)
+                        }
+                        if y.name == "SS58Prefix" {
+                            base58_prefix_encoded = Some(&y.value)
+                        }
+                    }
+                    break;
+                }
+            }
+            warn_incomplete_extensions = need_extensions_warning(
+                metadata_v15
+                    .extrinsic
+                    .signed_extensions
+                    .iter()
+                    .map(|x| x.identifier.as_str()),
+            );
         }
         _ => return Err(MetadataError::VersionIncompatible.into()),
     }
@@ -314,17 +601,19 @@ pub fn info_from_metadata(runtime_metadata: &RuntimeMetadata) -> Result<MetaInfo
 /// - must begin with b"meta"  
 /// - after that must be SCALE-encoded `RuntimeMetadata` with runtime version `V12` or above
 pub fn runtime_metadata_from_slice(meta: &[u8]) -> Result<RuntimeMetadata> {
-    if !meta.starts_with(&[109, 101, 116, 97]) {
+    if !meta.starts_with(&META_PREFIX) {
         return Err(MetadataError::NotMeta.into());
     }
-    if meta[4] < 12 {
-        return Err(MetadataError::VersionIncompatible.into());
+    match meta.get(META_PREFIX.len()) {
+        Some(version) if *version >= 12 => (),
+        Some(_) => return Err(MetadataError::VersionIncompatible.into()),
+        None => return Err(MetadataError::UnableToDecode.into()),
     }
     Ok(RuntimeMetadata::decode(&mut &meta[4..]).map_err(|_| MetadataError::UnableToDecode)?)
 }
 
-/// Checks if the `v14` metadata has all signed extensions required for transaction decoding.
-/// True if extensions are incomplete.
+/// Checks if the `v14` or `v15` metadata has all signed extensions required
+/// for transaction decoding. True if extensions are incomplete.
 ///
 /// Currently, the decoding of the transaction demands that metadata version, network genesis hash,
 /// and era are among signed extensions. Otherwise, a `ParserMetadataError` would occur on decoding.
@@ -333,12 +622,10 @@ pub fn runtime_metadata_from_slice(meta: &[u8]) -> Result<RuntimeMetadata> {
 ///
 /// This function should be used for warnings only on `generate_message` side and during metadata
 /// loading into Vault.
-fn need_v14_warning(metadata_v14: &RuntimeMetadataV14) -> bool {
+fn need_extensions_warning<'a>(identifiers: impl Iterator<Item = &'a str>) -> bool {
     let mut signed_extensions = HashMap::new();
-    for x in metadata_v14.extrinsic.signed_extensions.iter() {
-        let count = signed_extensions
-            .entry(x.identifier.to_string())
-            .or_insert(0);
+    for x in identifiers {
+        let count = signed_extensions.entry(x.to_string()).or_insert(0);
         *count += 1;
     }
     !(signed_extensions.get("CheckSpecVersion") == Some(&1)
@@ -346,6 +633,50 @@ fn need_v14_warning(metadata_v14: &RuntimeMetadataV14) -> bool {
         && signed_extensions.get("CheckMortality") == Some(&1)) // no warning needed if each one encountered, and only once
 }
 
+/// Transform `V15` metadata into `V14` metadata, for transaction decoding.
+///
+/// Types registry, pallets and signed extensions are the same in both versions.
+/// Runtime APIs, outer enums and custom metadata introduced in `V15` are not
+/// used in decoding and are dropped, as well as the pallet documentation.
+///
+/// `V15` has no separate extrinsic type, runtime call type is recorded
+/// instead. Extrinsic type is not used in transaction decoding.
+pub fn v14_from_v15(metadata_v15: RuntimeMetadataV15) -> RuntimeMetadataV14 {
+    let pallets = metadata_v15
+        .pallets
+        .into_iter()
+        .map(|pallet| PalletMetadata {
+            name: pallet.name,
+            storage: pallet.storage,
+            calls: pallet.calls,
+            event: pallet.event,
+            constants: pallet.constants,
+            error: pallet.error,
+            index: pallet.index,
+        })
+        .collect();
+    let signed_extensions = metadata_v15
+        .extrinsic
+        .signed_extensions
+        .into_iter()
+        .map(|signed_extension| SignedExtensionMetadata {
+            identifier: signed_extension.identifier,
+            ty: signed_extension.ty,
+            additional_signed: signed_extension.additional_signed,
+        })
+        .collect();
+    RuntimeMetadataV14 {
+        types: metadata_v15.types,
+        pallets,
+        extrinsic: ExtrinsicMetadata {
+            ty: metadata_v15.extrinsic.call_ty,
+            version: metadata_v15.extrinsic.version,
+            signed_extensions,
+        },
+        ty: metadata_v15.ty,
+    }
+}
+
 /// Metadata as checked [`RuntimeMetadata`](https://docs.rs/frame-metadata/15.0.0/frame_metadata/enum.RuntimeMetadata.html)
 /// with network info extracted from it, for transaction decoding
 pub struct MetaSetElement {
@@ -380,7 +711,7 @@ impl MetaSetElement {
     /// are very unlikely to happen and would indicate the database corruption
     pub fn from_entry((meta_key_vec, meta_encoded): (IVec, IVec)) -> Result<Self> {
         let (network_name, network_version) = MetaKey::from_ivec(&meta_key_vec).name_version()?;
-        let runtime_metadata = runtime_metadata_from_slice(&meta_encoded)?;
+        let runtime_metadata = runtime_metadata_from_slice(split_stored(&meta_encoded)?.1)?;
         let (name, version, optional_base58prefix) = {
             let a = info_from_metadata(&runtime_metadata)?;
             if (a.version != network_version) || (a.name != network_name) {
@@ -393,6 +724,11 @@ impl MetaSetElement {
             }
             (a.name, a.version, a.optional_base58prefix)
         };
+        // `V15` is decoded with `V14` tools
+        let runtime_metadata = match runtime_metadata {
+            RuntimeMetadata::V15(metadata_v15) => RuntimeMetadata::V14(v14_from_v15(metadata_v15)),
+            a => a,
+        };
         Ok(Self {
             name,
             version,
@@ -508,6 +844,12 @@ pub struct MetaHistoryEntry {
 
     /// block hash at which the metadata was fetched, from value
     pub block_hash: H256,
+
+    /// `RuntimeMetadata` version selected during the fetch, from value
+    ///
+    /// Entries recorded before the version selection was introduced have only
+    /// the block hash.
+    pub metadata_version: Option<u32>,
 }
 
 #[cfg(feature = "active")]
@@ -524,15 +866,105 @@ impl MetaHistoryEntry {
         version: u32,
         hash_encoded: &IVec,
     ) -> Result<Self> {
-        let block_hash = H256::decode(&mut &hash_encoded[..])?;
+        let mut value = &hash_encoded[..];
+        let block_hash = H256::decode(&mut value)?;
+        let metadata_version = if value.is_empty() {
+            None
+        } else {
+            Some(u32::decode(&mut value)?)
+        };
         Ok(MetaHistoryEntry {
             name: name.to_string(),
             version,
             block_hash,
+            metadata_version,
         })
     }
 }
 
+/// Policy for keeping network metadata in the hot database
+///
+/// Stored SCALE-encoded in hot database tree `SETTREE` under key
+/// `META_RETENTION`. Metadata entries not covered by the policy are removed
+/// when the new metadata is added to the hot database, or with `prune`
+/// command.
+///
+/// Without stored policy, two latest metadata versions are kept for each
+/// network.
+#[derive(Clone, Debug, Decode, Encode, PartialEq, Eq)]
+#[cfg(feature = "active")]
+pub struct MetaRetention {
+    /// Number of latest metadata versions kept for each network, at least `1`
+    pub keep: u32,
+
+    /// Metadata entries kept regardless of their version
+    pub pinned: Vec<MetaPin>,
+
+    /// Block ranges with metadata versions kept regardless of the number of
+    /// newer versions
+    pub block_ranges: Vec<MetaBlockRange>,
+}
+
+#[cfg(feature = "active")]
+impl Default for MetaRetention {
+    fn default() -> Self {
+        Self {
+            keep: 2,
+            pinned: Vec::new(),
+            block_ranges: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "active")]
+impl MetaRetention {
+    /// Check if the metadata entry is pinned or is within one of the block
+    /// ranges, and therefore is kept regardless of the number of newer
+    /// versions.
+    pub fn is_kept_always(&self, name: &str, version: u32) -> bool {
+        self.pinned
+            .iter()
+            .any(|pin| pin.name == name && pin.version == version)
+            || self.block_ranges.iter().any(|range| {
+                range.name == name && (range.first_version..=range.last_version).contains(&version)
+            })
+    }
+}
+
+/// Metadata entry pinned in the hot database
+#[derive(Clone, Debug, Decode, Encode, PartialEq, Eq)]
+#[cfg(feature = "active")]
+pub struct MetaPin {
+    /// network name
+    pub name: String,
+
+    /// network metadata version
+    pub version: u32,
+}
+
+/// Network block range, with metadata versions that were used within it
+///
+/// Metadata versions are determined through RPC calls when the range is added
+/// to the [`MetaRetention`].
+#[derive(Clone, Debug, Decode, Encode, PartialEq, Eq)]
+#[cfg(feature = "active")]
+pub struct MetaBlockRange {
+    /// network name
+    pub name: String,
+
+    /// first block number of the range
+    pub from: u32,
+
+    /// last block number of the range
+    pub to: u32,
+
+    /// metadata version at the first block of the range
+    pub first_version: u32,
+
+    /// metadata version at the last block of the range
+    pub last_version: u32,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -801,4 +1233,73 @@ mod tests {
             "Expected complete extensions in westend9150."
         )
     }
+
+    #[test]
+    fn trim_westend9150() {
+        let meta = read_to_string("for_tests/westend9150").unwrap();
+        let meta_values = MetaValues::from_str_metadata(meta.trim()).unwrap();
+        let trimmed = meta_values.trim().unwrap();
+        assert!(trimmed.meta.len() < meta_values.meta.len());
+        assert_eq!(trimmed.name, meta_values.name);
+        assert_eq!(trimmed.version, meta_values.version);
+        assert_eq!(trimmed.full_hash, Some(meta_values.hash()));
+        assert_eq!(trimmed.hash(), meta_values.hash());
+        assert_eq!(trimmed.metadata_version().unwrap(), 14);
+
+        let meta_v14 = match runtime_metadata_from_slice(&trimmed.meta).unwrap() {
+            RuntimeMetadata::V14(a) => a,
+            _ => panic!("Expected V14 metadata"),
+        };
+        assert!(meta_v14
+            .pallets
+            .iter()
+            .all(|pallet| pallet.storage.is_none() && pallet.event.is_none()));
+        let calls = meta_v14
+            .pallets
+            .iter()
+            .find_map(|pallet| pallet.calls.as_ref())
+            .unwrap();
+        assert!(!matches!(
+            meta_v14.types.resolve(calls.ty.id).unwrap().type_def,
+            TypeDef::Tuple(_)
+        ));
+
+        let stored = trimmed.to_stored();
+        assert!(stored.starts_with(TRIMMED_META_PREFIX));
+        assert_eq!(MetaValues::from_stored(&stored).unwrap(), trimmed);
+        assert_eq!(
+            MetaValues::from_stored(&meta_values.to_stored()).unwrap(),
+            meta_values
+        );
+    }
+
+    #[test]
+    fn trim_v13_unsupported() {
+        let meta = read_to_string("for_tests/westend9070").unwrap();
+        let meta_values = MetaValues::from_str_metadata(meta.trim()).unwrap();
+        assert!(matches!(
+            meta_values.trim(),
+            Err(Error::MetadataError(MetadataError::TrimUnsupported))
+        ));
+    }
+
+    #[test]
+    fn short_metadata() {
+        assert!(matches!(
+            runtime_metadata_from_slice(b"meta"),
+            Err(Error::MetadataError(MetadataError::UnableToDecode))
+        ));
+        let meta_values = MetaValues {
+            name: "westend".to_string(),
+            version: 9150,
+            optional_base58prefix: None,
+            warn_incomplete_extensions: false,
+            meta: META_PREFIX.to_vec(),
+            full_hash: None,
+        };
+        assert!(matches!(
+            meta_values.metadata_version(),
+            Err(Error::MetadataError(MetadataError::UnableToDecode))
+        ));
+    }
 }
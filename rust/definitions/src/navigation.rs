@@ -267,6 +267,28 @@ pub enum DecodeSequenceResult {
     Other { s: String },
 }
 
+/// Progress of the stateful QR sequence decoding.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QrDecodingProgress {
+    /// Number of distinct frames accepted so far.
+    pub collected: u32,
+
+    /// Estimated number of frames needed to decode the payload.
+    pub total: u32,
+
+    /// Number of frames that were already collected before.
+    pub duplicates: u32,
+
+    /// Number of frames that do not belong to the sequence being decoded.
+    pub foreign: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QrDecodeStatus {
+    InProgress { p: QrDecodingProgress },
+    Done { r: DecodeSequenceResult },
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct TransactionCardSet {
     pub author: Option<Vec<TransactionCard>>,
@@ -354,6 +376,7 @@ pub struct Address {
     pub identicon: Identicon,
     pub seed_name: String,
     pub secret_exposed: bool,
+    pub label: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -775,6 +798,11 @@ pub struct MKeysInfoExport {
     pub frames: Vec<QrData>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MKeystoreExport {
+    pub frames: Vec<QrData>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Card {
     AuthorCard { f: MAddressCard },
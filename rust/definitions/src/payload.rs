@@ -0,0 +1,486 @@
+//! Prelude and framing of the payloads transferred through the air-gap
+//!
+//! All payloads received by the Vault start with a three-byte prelude
+//! `53xxyy`:
+//!
+//! - `53` is the Substrate prefix
+//! - `xx` is the encryption code, see [`PayloadCrypto`]
+//! - `yy` is the payload type code, see [`PayloadKind`]
+//!
+//! Updates, i.e. `load_metadata`, `load_types` and `add_specs` payloads,
+//! are assembled from the prelude, the verifier public key (if the update is
+//! signed), the content, and the verifier signature (if the update is signed),
+//! see [`Update`].
+//!
+//! Both the hot side, making payloads, and the Vault, parsing them, use the
+//! definitions here, so a new payload type needs to be described only once.
+use sp_core::{ecdsa, ed25519, sr25519, Pair};
+use std::convert::TryInto;
+
+use crate::crypto::SufficientCrypto;
+use crate::error::{Error, Result};
+use crate::qr_transfers::{
+    ContentAddSpecs, ContentLoadMeta, ContentLoadMetaCompressed, ContentLoadTypes,
+};
+
+/// Substrate prefix, the first byte of each payload
+pub const SUBSTRATE_PREFIX: u8 = 0x53;
+
+/// Prelude length
+pub const PRELUDE_LENGTH: usize = 3;
+
+/// Encryption code in the payload prelude.
+///
+/// For transactions and messages it is the encryption of the author, for
+/// updates it is the encryption of the verifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PayloadCrypto {
+    /// `00`
+    Ed25519,
+
+    /// `01`
+    Sr25519,
+
+    /// `02`
+    Ecdsa,
+
+    /// `03`, transactions with Ethereum address of the author. In updates is
+    /// processed same as [`PayloadCrypto::Ecdsa`].
+    Ethereum,
+
+    /// `ff`, payload is not signed
+    Unsigned,
+}
+
+impl PayloadCrypto {
+    /// Encryption code, as it is in the prelude.
+    pub fn code(&self) -> u8 {
+        match self {
+            PayloadCrypto::Ed25519 => 0x00,
+            PayloadCrypto::Sr25519 => 0x01,
+            PayloadCrypto::Ecdsa => 0x02,
+            PayloadCrypto::Ethereum => 0x03,
+            PayloadCrypto::Unsigned => 0xff,
+        }
+    }
+
+    /// Get [`PayloadCrypto`] from the prelude encryption code.
+    pub fn from_code(code: u8) -> Result<Self> {
+        match code {
+            0x00 => Ok(PayloadCrypto::Ed25519),
+            0x01 => Ok(PayloadCrypto::Sr25519),
+            0x02 => Ok(PayloadCrypto::Ecdsa),
+            0x03 => Ok(PayloadCrypto::Ethereum),
+            0xff => Ok(PayloadCrypto::Unsigned),
+            a => Err(Error::PayloadEncryptionNotSupported(a)),
+        }
+    }
+}
+
+/// Payload type code in the payload prelude.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PayloadKind {
+    /// `00`, mortal transaction
+    Transaction,
+
+    /// `02`, immortal transaction
+    TransactionImmortal,
+
+    /// `03`, message for a known network
+    Message,
+
+    /// `04`, bulk of transactions
+    TransactionBulk,
+
+    /// `05`, transaction signed with dynamically derived key
+    DynamicDerivationTransaction,
+
+    /// `06`, transaction with metadata proof
+    TransactionWithProof,
+
+    /// `07`, transaction with metadata proof signed with dynamically derived
+    /// key
+    DynamicDerivationTransactionWithProof,
+
+    /// `08`, message not bound to a network
+    AnyChainMessage,
+
+    /// `80`, `load_metadata` update
+    LoadMetadata,
+
+    /// `81`, `load_types` update
+    LoadTypes,
+
+    /// `82`, compressed `load_metadata` update
+    LoadMetadataCompressed,
+
+    /// `c1`, `add_specs` update
+    AddSpecs,
+
+    /// `de`, derivations import
+    Derivations,
+
+    /// `df`, dynamic derivations request
+    DynamicDerivations,
+}
+
+impl PayloadKind {
+    /// Payload type code, as it is in the prelude.
+    pub fn code(&self) -> u8 {
+        match self {
+            PayloadKind::Transaction => 0x00,
+            PayloadKind::TransactionImmortal => 0x02,
+            PayloadKind::Message => 0x03,
+            PayloadKind::TransactionBulk => 0x04,
+            PayloadKind::DynamicDerivationTransaction => 0x05,
+            PayloadKind::TransactionWithProof => 0x06,
+            PayloadKind::DynamicDerivationTransactionWithProof => 0x07,
+            PayloadKind::AnyChainMessage => 0x08,
+            PayloadKind::LoadMetadata => 0x80,
+            PayloadKind::LoadTypes => 0x81,
+            PayloadKind::LoadMetadataCompressed => 0x82,
+            PayloadKind::AddSpecs => 0xc1,
+            PayloadKind::Derivations => 0xde,
+            PayloadKind::DynamicDerivations => 0xdf,
+        }
+    }
+
+    /// Get [`PayloadKind`] from the prelude payload type code.
+    pub fn from_code(code: u8) -> Result<Self> {
+        match code {
+            0x00 => Ok(PayloadKind::Transaction),
+            0x02 => Ok(PayloadKind::TransactionImmortal),
+            0x03 => Ok(PayloadKind::Message),
+            0x04 => Ok(PayloadKind::TransactionBulk),
+            0x05 => Ok(PayloadKind::DynamicDerivationTransaction),
+            0x06 => Ok(PayloadKind::TransactionWithProof),
+            0x07 => Ok(PayloadKind::DynamicDerivationTransactionWithProof),
+            0x08 => Ok(PayloadKind::AnyChainMessage),
+            0x80 => Ok(PayloadKind::LoadMetadata),
+            0x81 => Ok(PayloadKind::LoadTypes),
+            0x82 => Ok(PayloadKind::LoadMetadataCompressed),
+            0xc1 => Ok(PayloadKind::AddSpecs),
+            0xde => Ok(PayloadKind::Derivations),
+            0xdf => Ok(PayloadKind::DynamicDerivations),
+            a => Err(Error::PayloadKindNotSupported(a)),
+        }
+    }
+}
+
+/// Payload prelude `53xxyy`.
+///
+/// Encryption code is kept as is and is checked with [`Prelude::crypto`] only
+/// when needed, as some payloads do not use it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Prelude {
+    crypto_code: u8,
+    kind: PayloadKind,
+}
+
+impl Prelude {
+    /// Make new [`Prelude`].
+    pub fn new(crypto: PayloadCrypto, kind: PayloadKind) -> Self {
+        Self {
+            crypto_code: crypto.code(),
+            kind,
+        }
+    }
+
+    /// Get [`PayloadCrypto`] of the prelude.
+    pub fn crypto(&self) -> Result<PayloadCrypto> {
+        PayloadCrypto::from_code(self.crypto_code)
+    }
+
+    /// Get [`PayloadKind`] of the prelude.
+    pub fn kind(&self) -> PayloadKind {
+        self.kind
+    }
+
+    /// Prelude bytes.
+    pub fn to_bytes(&self) -> [u8; PRELUDE_LENGTH] {
+        [SUBSTRATE_PREFIX, self.crypto_code, self.kind.code()]
+    }
+
+    /// Cut [`Prelude`] from the start of the payload.
+    ///
+    /// Returns the prelude along with the remaining part of the payload.
+    pub fn from_slice_with_tail(slice: &[u8]) -> Result<(Self, &[u8])> {
+        if slice.len() < PRELUDE_LENGTH {
+            return Err(Error::PayloadTooShort);
+        }
+        if slice[0] != SUBSTRATE_PREFIX {
+            return Err(Error::PayloadNotSubstrate(slice[0]));
+        }
+        let kind = PayloadKind::from_code(slice[2])?;
+        Ok((
+            Self {
+                crypto_code: slice[1],
+                kind,
+            },
+            &slice[PRELUDE_LENGTH..],
+        ))
+    }
+}
+
+/// Update content, as it is transferred in the QR code.
+pub enum UpdateContent {
+    LoadMetadata(ContentLoadMeta),
+    LoadMetadataCompressed(ContentLoadMetaCompressed),
+    LoadTypes(ContentLoadTypes),
+    AddSpecs(ContentAddSpecs),
+}
+
+impl UpdateContent {
+    /// [`PayloadKind`] of the update.
+    pub fn kind(&self) -> PayloadKind {
+        match self {
+            UpdateContent::LoadMetadata(_) => PayloadKind::LoadMetadata,
+            UpdateContent::LoadMetadataCompressed(_) => PayloadKind::LoadMetadataCompressed,
+            UpdateContent::LoadTypes(_) => PayloadKind::LoadTypes,
+            UpdateContent::AddSpecs(_) => PayloadKind::AddSpecs,
+        }
+    }
+
+    /// Bytes signed by the verifier.
+    ///
+    /// Compressed `load_metadata` content is decompressed, as the signature
+    /// is made for uncompressed content.
+    pub fn to_sign(&self) -> Result<Vec<u8>> {
+        match self {
+            UpdateContent::LoadMetadata(a) => Ok(a.to_sign()),
+            UpdateContent::LoadMetadataCompressed(a) => Ok(a.decompress()?.to_sign()),
+            UpdateContent::LoadTypes(a) => Ok(a.to_sign()),
+            UpdateContent::AddSpecs(a) => Ok(a.to_sign()),
+        }
+    }
+
+    /// Bytes concatenated with other parts of the update.
+    pub fn to_transfer(&self) -> Vec<u8> {
+        match self {
+            UpdateContent::LoadMetadata(a) => a.to_transfer(),
+            UpdateContent::LoadMetadataCompressed(a) => a.to_transfer(),
+            UpdateContent::LoadTypes(a) => a.to_transfer(),
+            UpdateContent::AddSpecs(a) => a.to_transfer(),
+        }
+    }
+
+    /// Cut content of given [`PayloadKind`] from the start of `&[u8]` slice.
+    ///
+    /// Returns the content along with the remaining part of the slice.
+    pub fn from_slice_with_tail(kind: PayloadKind, slice: &[u8]) -> Result<(Self, &[u8])> {
+        match kind {
+            PayloadKind::LoadMetadata => ContentLoadMeta::from_slice_with_tail(slice)
+                .map(|(a, tail)| (UpdateContent::LoadMetadata(a), tail)),
+            PayloadKind::LoadMetadataCompressed => {
+                ContentLoadMetaCompressed::from_slice_with_tail(slice)
+                    .map(|(a, tail)| (UpdateContent::LoadMetadataCompressed(a), tail))
+            }
+            PayloadKind::LoadTypes => ContentLoadTypes::from_slice_with_tail(slice)
+                .map(|(a, tail)| (UpdateContent::LoadTypes(a), tail)),
+            PayloadKind::AddSpecs => ContentAddSpecs::from_slice_with_tail(slice)
+                .map(|(a, tail)| (UpdateContent::AddSpecs(a), tail)),
+            a => Err(Error::PayloadNotUpdate(a.code())),
+        }
+    }
+}
+
+/// Complete update, signed or unsigned.
+pub struct Update {
+    pub content: UpdateContent,
+
+    /// Verifier public key and signature, `None` for unsigned update
+    pub signature: Option<SufficientCrypto>,
+}
+
+impl Update {
+    /// [`Prelude`] of the update.
+    pub fn prelude(&self) -> Prelude {
+        let crypto = match self.signature {
+            Some(SufficientCrypto::Ed25519 { .. }) => PayloadCrypto::Ed25519,
+            Some(SufficientCrypto::Sr25519 { .. }) => PayloadCrypto::Sr25519,
+            Some(SufficientCrypto::Ecdsa { .. }) => PayloadCrypto::Ecdsa,
+            None => PayloadCrypto::Unsigned,
+        };
+        Prelude::new(crypto, self.content.kind())
+    }
+
+    /// Update bytes: prelude, verifier public key, content, and signature.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let (public, signature) = match &self.signature {
+            Some(SufficientCrypto::Ed25519 { public, signature }) => {
+                (public.0.to_vec(), signature.0.to_vec())
+            }
+            Some(SufficientCrypto::Sr25519 { public, signature }) => {
+                (public.0.to_vec(), signature.0.to_vec())
+            }
+            Some(SufficientCrypto::Ecdsa { public, signature }) => {
+                (public.0.to_vec(), signature.0.to_vec())
+            }
+            None => (Vec::new(), Vec::new()),
+        };
+        [
+            self.prelude().to_bytes().to_vec(),
+            public,
+            self.content.to_transfer(),
+            signature,
+        ]
+        .concat()
+    }
+
+    /// Cut [`Update`] from the start of the payload.
+    ///
+    /// Returns the update along with the remaining part of the payload.
+    /// Signature is not checked here, see [`Update::verify`].
+    pub fn from_slice_with_tail(slice: &[u8]) -> Result<(Self, &[u8])> {
+        let (prelude, data) = Prelude::from_slice_with_tail(slice)?;
+        let crypto = prelude.crypto()?;
+        let public_length = match crypto {
+            PayloadCrypto::Ed25519 | PayloadCrypto::Sr25519 => 32,
+            PayloadCrypto::Ecdsa | PayloadCrypto::Ethereum => 33,
+            PayloadCrypto::Unsigned => 0,
+        };
+        let public = data.get(..public_length).ok_or(Error::PayloadTooShort)?;
+        let (content, data) =
+            UpdateContent::from_slice_with_tail(prelude.kind(), &data[public_length..])?;
+        let (signature, tail) = match crypto {
+            PayloadCrypto::Ed25519 => {
+                let signature = data.get(..64).ok_or(Error::PayloadTooShort)?;
+                (
+                    Some(SufficientCrypto::Ed25519 {
+                        public: ed25519::Public::from_raw(
+                            public.try_into().expect("fixed size should fit in array"),
+                        ),
+                        signature: ed25519::Signature::from_raw(
+                            signature
+                                .try_into()
+                                .expect("fixed size should fit in array"),
+                        ),
+                    }),
+                    &data[64..],
+                )
+            }
+            PayloadCrypto::Sr25519 => {
+                let signature = data.get(..64).ok_or(Error::PayloadTooShort)?;
+                (
+                    Some(SufficientCrypto::Sr25519 {
+                        public: sr25519::Public::from_raw(
+                            public.try_into().expect("fixed size should fit in array"),
+                        ),
+                        signature: sr25519::Signature::from_raw(
+                            signature
+                                .try_into()
+                                .expect("fixed size should fit in array"),
+                        ),
+                    }),
+                    &data[64..],
+                )
+            }
+            PayloadCrypto::Ecdsa | PayloadCrypto::Ethereum => {
+                let signature = data.get(..65).ok_or(Error::PayloadTooShort)?;
+                (
+                    Some(SufficientCrypto::Ecdsa {
+                        public: ecdsa::Public::from_raw(
+                            public.try_into().expect("fixed size should fit in array"),
+                        ),
+                        signature: ecdsa::Signature::from_raw(
+                            signature
+                                .try_into()
+                                .expect("fixed size should fit in array"),
+                        ),
+                    }),
+                    &data[65..],
+                )
+            }
+            PayloadCrypto::Unsigned => (None, data),
+        };
+        Ok((Update { content, signature }, tail))
+    }
+
+    /// Check the verifier signature for the update content.
+    ///
+    /// Unsigned update is always valid.
+    pub fn verify(&self) -> Result<bool> {
+        Ok(self.verify_message(&self.content.to_sign()?))
+    }
+
+    /// Check the verifier signature for already prepared bytes to sign, see
+    /// [`UpdateContent::to_sign`].
+    ///
+    /// Unsigned update is always valid.
+    pub fn verify_message(&self, message: &[u8]) -> bool {
+        match &self.signature {
+            Some(SufficientCrypto::Ed25519 { public, signature }) => {
+                ed25519::Pair::verify(signature, message, public)
+            }
+            Some(SufficientCrypto::Sr25519 { public, signature }) => {
+                sr25519::Pair::verify(signature, message, public)
+            }
+            Some(SufficientCrypto::Ecdsa { public, signature }) => {
+                ecdsa::Pair::verify(signature, message, public)
+            }
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prelude_round_trip() {
+        let prelude = Prelude::new(PayloadCrypto::Unsigned, PayloadKind::Derivations);
+        assert_eq!(prelude.to_bytes(), [0x53, 0xff, 0xde]);
+        let (decoded, tail) = Prelude::from_slice_with_tail(&[0x53, 0xff, 0xde, 0x01]).unwrap();
+        assert_eq!(decoded, prelude);
+        assert_eq!(tail, [0x01]);
+    }
+
+    #[test]
+    fn prelude_errors() {
+        assert!(matches!(
+            Prelude::from_slice_with_tail(&[0x53, 0x01]),
+            Err(Error::PayloadTooShort)
+        ));
+        assert!(matches!(
+            Prelude::from_slice_with_tail(&[0x54, 0x01, 0x80]),
+            Err(Error::PayloadNotSubstrate(0x54))
+        ));
+        assert!(matches!(
+            Prelude::from_slice_with_tail(&[0x53, 0x01, 0x99]),
+            Err(Error::PayloadKindNotSupported(0x99))
+        ));
+        let (prelude, _) = Prelude::from_slice_with_tail(&[0x53, 0x10, 0x80]).unwrap();
+        assert!(matches!(
+            prelude.crypto(),
+            Err(Error::PayloadEncryptionNotSupported(0x10))
+        ));
+    }
+
+    #[test]
+    fn signed_update_round_trip() {
+        let pair = sr25519::Pair::from_seed(&[7; 32]);
+        let content = ContentLoadTypes::generate(&[]);
+        let signature = pair.sign(&content.to_sign());
+        let update = Update {
+            content: UpdateContent::LoadTypes(content),
+            signature: Some(SufficientCrypto::Sr25519 {
+                public: pair.public(),
+                signature,
+            }),
+        };
+        let bytes = [update.to_bytes(), vec![0x01, 0x02]].concat();
+        assert_eq!(bytes[..3], [0x53, 0x01, 0x81]);
+
+        let (decoded, tail) = Update::from_slice_with_tail(&bytes).unwrap();
+        assert_eq!(tail, [0x01, 0x02]);
+        assert!(decoded.verify().unwrap());
+        assert_eq!(decoded.to_bytes(), update.to_bytes());
+
+        let mut damaged = update.to_bytes();
+        let last = damaged.len() - 1;
+        damaged[last] ^= 0x01;
+        let (decoded, _) = Update::from_slice_with_tail(&damaged).unwrap();
+        assert!(!decoded.verify().unwrap());
+    }
+}
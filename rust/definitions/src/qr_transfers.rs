@@ -15,17 +15,23 @@
 //! - content  
 //! - verifier signature (if the QR code is signed by verifier)  
 //!
+//! `load_metadata` QR code content could be transferred compressed, with
+//! prelude `53xx82`. Signature in this case is still made for uncompressed
+//! content, see [`ContentLoadMetaCompressed`].
+//!
 //! QR codes for importing derivations are never signed and have structure:
 //! - prelude `53ffde`
 //! - content  
 //!
 //! This module deals with content part of QR codes.  
 
+use constants::MAX_DECOMPRESSED_CONTENT_SIZE;
 use parity_scale_codec::{Decode, Encode};
+use std::io::Read;
 #[cfg(feature = "active")]
 use std::path::Path;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::helpers::pic_types;
 use crate::network_specs::NetworkSpecs;
 use crate::types::TypeEntry;
@@ -62,6 +68,16 @@ impl ContentLoadMeta {
         Self(slice.to_vec())
     }
 
+    /// Cut [`ContentLoadMeta`] from the start of `&[u8]` slice.
+    ///
+    /// Returns the content along with the remaining part of the slice.
+    pub fn from_slice_with_tail(slice: &[u8]) -> Result<(Self, &[u8])> {
+        let mut tail = slice;
+        <DecodedContentLoadMeta>::decode(&mut tail)?;
+        let content_length = slice.len() - tail.len();
+        Ok((Self(slice[..content_length].to_vec()), tail))
+    }
+
     /// Get metadata `Vec<u8>` from [`ContentLoadMeta`].
     pub fn meta(&self) -> Result<Vec<u8>> {
         Ok(<DecodedContentLoadMeta>::decode(&mut &self.0[..])?.meta)
@@ -101,6 +117,106 @@ impl ContentLoadMeta {
     }
 }
 
+/// Compression algorithm for [`ContentLoadMetaCompressed`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    /// `zstd` compression
+    Zstd,
+}
+
+impl Compression {
+    /// Get compression code, as it is transferred in the QR code.
+    pub fn code(&self) -> u8 {
+        match self {
+            Compression::Zstd => 0,
+        }
+    }
+
+    /// Get [`Compression`] from the code, as it is transferred in the QR code.
+    pub fn from_code(code: u8) -> Result<Self> {
+        match code {
+            0 => Ok(Compression::Zstd),
+            _ => Err(Error::UnknownCompression(code)),
+        }
+    }
+
+    /// Display [`Compression`] in readable form.
+    pub fn show(&self) -> String {
+        match self {
+            Compression::Zstd => String::from("zstd"),
+        }
+    }
+}
+
+/// Compressed `load_metadata` QR code content
+///
+/// Compressed content consists of the compression code and SCALE-encoded
+/// `Vec<u8>` with compressed [`ContentLoadMeta`]. Verifier signature, if any,
+/// is made for the uncompressed [`ContentLoadMeta`], so that the same
+/// signature is valid for both the compressed and the uncompressed updates.
+///
+/// Size of decompressed content is limited by
+/// [`MAX_DECOMPRESSED_CONTENT_SIZE`].
+#[derive(Decode, Encode)]
+pub struct ContentLoadMetaCompressed {
+    compression: u8,
+    compressed: Vec<u8>,
+}
+
+impl ContentLoadMetaCompressed {
+    /// Compress [`ContentLoadMeta`].
+    pub fn compress(content: &ContentLoadMeta, compression: Compression) -> Result<Self> {
+        let compressed = match compression {
+            Compression::Zstd => zstd::stream::encode_all(&content.0[..], ZSTD_LEVEL)?,
+        };
+        Ok(Self {
+            compression: compression.code(),
+            compressed,
+        })
+    }
+
+    /// Cut [`ContentLoadMetaCompressed`] from the start of `&[u8]` slice.
+    ///
+    /// Returns the content along with the remaining part of the slice.
+    pub fn from_slice_with_tail(slice: &[u8]) -> Result<(Self, &[u8])> {
+        let mut tail = slice;
+        let content = <Self>::decode(&mut tail)?;
+        Ok((content, tail))
+    }
+
+    /// Get [`Compression`] used in [`ContentLoadMetaCompressed`].
+    pub fn compression(&self) -> Result<Compression> {
+        Compression::from_code(self.compression)
+    }
+
+    /// Decompress into [`ContentLoadMeta`].
+    ///
+    /// Decompression stops with an error as soon as the output exceeds
+    /// [`MAX_DECOMPRESSED_CONTENT_SIZE`].
+    pub fn decompress(&self) -> Result<ContentLoadMeta> {
+        let decoder = match self.compression()? {
+            Compression::Zstd => zstd::stream::read::Decoder::new(&self.compressed[..])?,
+        };
+        let mut out = Vec::new();
+        decoder
+            .take(MAX_DECOMPRESSED_CONTENT_SIZE as u64 + 1)
+            .read_to_end(&mut out)?;
+        if out.len() > MAX_DECOMPRESSED_CONTENT_SIZE {
+            return Err(Error::DecompressedTooLarge(MAX_DECOMPRESSED_CONTENT_SIZE));
+        }
+        Ok(ContentLoadMeta(out))
+    }
+
+    /// Transform [`ContentLoadMetaCompressed`] into `Vec<u8>` that is
+    /// concatenated with other parts of the QR code.
+    pub fn to_transfer(&self) -> Vec<u8> {
+        self.encode()
+    }
+}
+
+/// `zstd` compression level used for [`ContentLoadMetaCompressed`]
+const ZSTD_LEVEL: i32 = 19;
+
 /// `add_specs` QR code content  
 ///
 /// Messages `add_specs` are used to add new networks to the Vault.
@@ -128,6 +244,16 @@ impl ContentAddSpecs {
         Self(slice.to_vec())
     }
 
+    /// Cut [`ContentAddSpecs`], as it is transferred in the QR code, from the start
+    /// of `&[u8]` slice.
+    ///
+    /// Returns the content along with the remaining part of the slice.
+    pub fn from_slice_with_tail(slice: &[u8]) -> Result<(Self, &[u8])> {
+        let mut tail = slice;
+        let content = <Self>::decode(&mut tail)?;
+        Ok((content, tail))
+    }
+
     /// Get network specs [`NetworkSpecs`] from [`ContentAddSpecs`].
     pub fn specs(&self) -> Result<NetworkSpecs> {
         Ok(<DecodedContentAddSpecs>::decode(&mut &self.0[..])?.specs)
@@ -192,6 +318,16 @@ impl ContentLoadTypes {
         Self(slice.to_vec())
     }
 
+    /// Cut [`ContentLoadTypes`], as it is transferred in the QR code, from the start
+    /// of `&[u8]` slice.
+    ///
+    /// Returns the content along with the remaining part of the slice.
+    pub fn from_slice_with_tail(slice: &[u8]) -> Result<(Self, &[u8])> {
+        let mut tail = slice;
+        let content = <Self>::decode(&mut tail)?;
+        Ok((content, tail))
+    }
+
     /// Get types information `Vec<TypeEntry>` from [`ContentLoadTypes`].  
     pub fn types(&self) -> Result<Vec<TypeEntry>> {
         Ok(<DecodedContentLoadTypes>::decode(&mut &self.0[..])?.types)
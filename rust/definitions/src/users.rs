@@ -29,7 +29,8 @@ pub struct AddressDetails {
     /// seed name (as it is known to the Vault device)
     pub seed_name: String,
 
-    /// derivation path, only with soft (`/`) and hard (`//`) junctions (i.e. no password)
+    /// derivation path, only with soft (`/`) and hard (`//`) junctions (i.e. no password),
+    /// or BIP32 path starting with `m/`, see [`DerivationScheme`]
     pub path: String,
 
     /// whether the address key has an associated password
@@ -46,6 +47,105 @@ pub struct AddressDetails {
     pub secret_exposed: bool,
 }
 
+/// `bip39` wordlist of the key set seed phrase
+///
+/// Recorded for each key set in the settings tree of the Vault database, under
+/// the key with prefix `SEED_LANGUAGE` followed by the seed name. Key sets
+/// without the record were created before the languages other than English
+/// were supported.
+#[derive(Decode, Encode, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum SeedLanguage {
+    English,
+    ChineseSimplified,
+    ChineseTraditional,
+    French,
+    Italian,
+    Japanese,
+    Korean,
+    Spanish,
+}
+
+/// Key derivation scheme
+///
+/// Derivation scheme is stored in [`AddressDetails`] as a part of the
+/// derivation path `path`, and is read with
+/// [`AddressDetails::derivation_scheme`]: path `m/<index>['][/<index>[']...]`
+/// is a BIP32 path, with derivation determined by the `encryption`, any other
+/// path is a Substrate path. For example, ed25519 key with path
+/// `m/44'/354'/0'/0'/0'` is a BIP32-Ed25519 key, and ed25519 key with
+/// path `//polkadot` is a Substrate key. Substrate paths never start with
+/// `m/`, so no separate field is needed, and the address details encoded
+/// before BIP32 support are decoded unchanged.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum DerivationScheme {
+    /// Substrate junctions, `/soft//hard///password`
+    Substrate,
+
+    /// BIP32 `secp256k1` derivation, as in Ethereum wallets, for
+    /// [`Encryption::Ethereum`] keys
+    Bip32Secp256k1,
+
+    /// BIP32-Ed25519 derivation, with extended secret keys, for
+    /// [`Encryption::Ed25519`] keys
+    Bip32Ed25519,
+}
+
+impl DerivationScheme {
+    /// Derivation scheme for the derivation path and encryption.
+    ///
+    /// Returns `None` for BIP32 path with encryption that has no BIP32
+    /// derivation.
+    pub fn new(path: &str, encryption: Encryption) -> Option<Self> {
+        if !path.starts_with("m/") {
+            return Some(DerivationScheme::Substrate);
+        }
+        match encryption {
+            Encryption::Ethereum => Some(DerivationScheme::Bip32Secp256k1),
+            Encryption::Ed25519 => Some(DerivationScheme::Bip32Ed25519),
+            Encryption::Sr25519 | Encryption::Ecdsa => None,
+        }
+    }
+}
+
+/// Named derivation path template
+///
+/// Recorded in the settings tree of the Vault database, under the key with
+/// prefix `DERIVATION_TEMPLATE` followed by the template name.
+///
+/// Pattern is a derivation path without password, that could contain
+/// placeholders [`DerivationTemplate::NETWORK`] for the network name and
+/// [`DerivationTemplate::INDEX`] for the key index, e.g. `//staking//{index}`
+/// or `//{network}//{index}`.
+#[derive(Decode, Encode, PartialEq, Eq, Debug, Clone)]
+pub struct DerivationTemplate {
+    /// template name, unique in the Vault
+    pub name: String,
+
+    /// derivation path pattern
+    pub pattern: String,
+}
+
+impl DerivationTemplate {
+    /// Placeholder for the network name.
+    pub const NETWORK: &'static str = "{network}";
+
+    /// Placeholder for the key index.
+    pub const INDEX: &'static str = "{index}";
+
+    /// Derivation path for the network name and the key index.
+    pub fn path(&self, network_name: &str, index: u32) -> String {
+        self.pattern
+            .replace(Self::NETWORK, network_name)
+            .replace(Self::INDEX, &index.to_string())
+    }
+
+    /// Check if the pattern has the key index placeholder, i.e. produces
+    /// different paths for different indices.
+    pub fn is_indexed(&self) -> bool {
+        self.pattern.contains(Self::INDEX)
+    }
+}
+
 impl AddressDetails {
     /// Gets ([`MultiSigner`](https://docs.rs/sp-runtime/6.0.0/sp_runtime/enum.MultiSigner.html),
     /// [`AddressDetails`]) tuple from [`AddressKey`] and associated value from
@@ -112,6 +212,14 @@ impl AddressDetails {
         self.path.is_empty() && !self.has_pwd && self.network_id.is_none()
     }
 
+    /// Derivation scheme of the address key, recorded in `path`.
+    ///
+    /// BIP32 path for encryption without BIP32 derivation could not be
+    /// created, such address details are read as having Substrate path.
+    pub fn derivation_scheme(&self) -> DerivationScheme {
+        DerivationScheme::new(&self.path, self.encryption).unwrap_or(DerivationScheme::Substrate)
+    }
+
     /// The style to use for identicons.
     ///
     /// Jdenticon for root key.
@@ -0,0 +1,165 @@
+//! Network specs from a local chain spec file
+//!
+//! Networks without public RPC endpoint, such as private or test chains, could
+//! get `add_specs` update payload made from the raw chain spec JSON file, as
+//! produced by `build-spec --raw` command of the node.
+//!
+//! Network name, and base58 prefix if it is in the metadata, are taken from
+//! the metadata of the runtime `wasm` code in the genesis storage. Genesis hash
+//! is the hash of the genesis block header, that is computed from the raw
+//! genesis storage with the state version of the runtime.
+//!
+//! Network properties are taken from the chain spec `properties`, same as they
+//! would be taken from `system_properties` RPC call. Properties provided in
+//! command line replace the chain spec ones.
+use serde::Deserialize;
+use serde_json::{map::Map, value::Value};
+use sp_core::{
+    storage::well_known_keys::{CODE, DEFAULT_CHILD_STORAGE_KEY_PREFIX},
+    H256,
+};
+use sp_io::trie::{blake2_256_ordered_root, blake2_256_root};
+use sp_runtime::{
+    generic::{Digest, Header},
+    traits::{BlakeTwo256, Header as _},
+    StateVersion,
+};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use constants::{COLOR, SECONDARY_COLOR};
+use definitions::{
+    crypto::Encryption,
+    helpers::unhex,
+    metadata::{metadata_from_wasm_code, runtime_version_from_wasm_code, MetaValues},
+    network_specs::NetworkSpecs,
+};
+
+use crate::error::{Error, Result};
+use crate::interpret_specs::interpret_properties;
+use crate::parser::Token;
+
+/// Chain spec, only the parts needed for network specs.
+#[derive(Deserialize)]
+struct ChainSpec {
+    /// Network properties, same format as in `system_properties` RPC call
+    properties: Option<Map<String, Value>>,
+
+    /// Genesis configuration
+    genesis: Genesis,
+}
+
+/// Genesis configuration, only the raw one is usable.
+#[derive(Deserialize)]
+struct Genesis {
+    raw: Option<RawGenesis>,
+}
+
+/// Raw genesis storage, hexadecimal keys and values.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawGenesis {
+    top: BTreeMap<String, String>,
+
+    #[serde(default)]
+    children_default: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// Make [`NetworkSpecs`] from a raw chain spec file and user-entered values.
+///
+/// Base58 prefix and token from command line are put into network properties
+/// before processing, so that the chain spec does not need to have
+/// `properties` at all.
+pub fn specs_from_chain_spec<P>(
+    path: P,
+    encryption: Encryption,
+    optional_base58prefix: Option<u16>,
+    optional_token: Option<Token>,
+    optional_signer_title_override: Option<String>,
+) -> Result<NetworkSpecs>
+where
+    P: AsRef<Path>,
+{
+    let chain_spec: ChainSpec = serde_json::from_slice(&std::fs::read(&path)?)?;
+    let raw_genesis = chain_spec
+        .genesis
+        .raw
+        .ok_or_else(|| Error::ChainSpecNotRaw(path.as_ref().to_path_buf()))?;
+
+    let code = unhex(
+        raw_genesis
+            .top
+            .get(&format!("0x{}", hex::encode(CODE)))
+            .ok_or_else(|| Error::ChainSpecNoCode(path.as_ref().to_path_buf()))?,
+    )?;
+    let meta_values = MetaValues::from_slice_metadata(&metadata_from_wasm_code(&code)?)?;
+    let state_version = runtime_version_from_wasm_code(&code)?.state_version();
+    let genesis_hash = genesis_hash(&raw_genesis, state_version)?;
+
+    let mut properties = chain_spec.properties.unwrap_or_default();
+    if let Some(base58prefix) = optional_base58prefix {
+        properties.insert("ss58Format".to_string(), base58prefix.into());
+    }
+    if let Some(token) = optional_token {
+        properties.insert("tokenDecimals".to_string(), token.decimals.into());
+        properties.insert("tokenSymbol".to_string(), token.unit.into());
+    }
+    let new_properties =
+        interpret_properties(&properties, meta_values.optional_base58prefix, None)?;
+
+    let title = optional_signer_title_override.unwrap_or(format!(
+        "{}-{}",
+        meta_values.name,
+        encryption.show()
+    ));
+
+    Ok(NetworkSpecs {
+        base58prefix: new_properties.base58prefix,
+        color: COLOR.to_string(),
+        decimals: new_properties.decimals,
+        encryption,
+        genesis_hash,
+        logo: meta_values.name.to_string(),
+        name: meta_values.name.to_string(),
+        path_id: format!("//{}", meta_values.name),
+        secondary_color: SECONDARY_COLOR.to_string(),
+        title,
+        unit: new_properties.unit,
+    })
+}
+
+/// Hash of the genesis block header, built same way as in the node.
+///
+/// Genesis block has zero parent hash, empty body and empty digest, its state
+/// root is the root of the genesis storage, with non-empty default child tries
+/// roots put into the top trie under prefixed child keys.
+fn genesis_hash(raw_genesis: &RawGenesis, state_version: StateVersion) -> Result<H256> {
+    let mut top = storage_entries(&raw_genesis.top)?;
+    for (child_key, child_storage) in raw_genesis.children_default.iter() {
+        let child = storage_entries(child_storage)?;
+        if child.is_empty() {
+            continue;
+        }
+        let child_root = blake2_256_root(child, state_version);
+        let prefixed_key = [DEFAULT_CHILD_STORAGE_KEY_PREFIX, &unhex(child_key)?[..]].concat();
+        top.push((prefixed_key, child_root.as_bytes().to_vec()));
+    }
+    let state_root = blake2_256_root(top, state_version);
+    let extrinsics_root = blake2_256_ordered_root(Vec::new(), state_version);
+    let header = Header::<u32, BlakeTwo256>::new(
+        0,
+        extrinsics_root,
+        state_root,
+        H256::zero(),
+        Digest::default(),
+    );
+    Ok(header.hash())
+}
+
+/// Decode hexadecimal storage keys and values.
+fn storage_entries(storage: &BTreeMap<String, String>) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+    storage
+        .iter()
+        .map(|(key, value)| Ok((unhex(key)?, unhex(value)?)))
+        .collect()
+}
@@ -0,0 +1,225 @@
+//! Cold release database manifest
+//!
+//! Cold release database is generated on the hot side and then copied verbatim
+//! into Vault files during the build. Database directory itself is hard to
+//! audit, so each time the cold release database is generated with
+//! `make-cold-release` or gets metadata with `transfer-meta`, a manifest
+//! [`ColdReleaseManifest`] is written alongside, by default into
+//! [`COLD_RELEASE_MANIFEST`](constants::COLD_RELEASE_MANIFEST).
+//!
+//! Manifest lists network specs, network verifiers, metadata entries with
+//! hashes and the types information hash. It also records the hash of the
+//! complete database content.
+//!
+//! Sled's own files in the database directory are not byte-reproducible, the
+//! same content could be laid out differently. The byte-reproducible artifact
+//! is the canonical dump of the database content, see [`cold_release_dump`],
+//! written alongside the manifest, by default into
+//! [`COLD_RELEASE_DUMP`](constants::COLD_RELEASE_DUMP). Same defaults and same
+//! hot database produce the same dump, byte for byte, and the manifest
+//! content hash is the hash of the dump.
+//!
+//! Existing cold database and its dump could be checked against the manifest:
+//!
+//! `$ cargo run verify-cold-release --manifest <path> --dump <path>`
+use parity_scale_codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use sp_core::blake2_256;
+use std::path::Path;
+
+use constants::{METATREE, SCHEMA_VERSION, SETTREE, SPECSTREE, TYPES, VERIFIERS};
+use db_handling::helpers::open_tree;
+use definitions::{
+    keyring::VerifierKey,
+    metadata::MetaValues,
+    network_specs::{CurrentVerifier, OrderedNetworkSpecs, ValidCurrentVerifier},
+    schema_version::SchemaVersion,
+};
+
+use crate::error::{Error, Result};
+
+/// Content summary of the cold release database.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ColdReleaseManifest {
+    /// Database schema version, if recorded
+    pub schema_version: Option<u32>,
+
+    /// Network specs from [`SPECSTREE`], sorted by network order
+    pub networks: Vec<ManifestNetwork>,
+
+    /// Network verifiers from [`VERIFIERS`], sorted by genesis hash
+    pub verifiers: Vec<ManifestVerifier>,
+
+    /// Metadata entries from [`METATREE`], sorted by name and version
+    pub metadata: Vec<ManifestMetadata>,
+
+    /// Hexadecimal blake2 hash of the types information, if recorded
+    pub types_hash: Option<String>,
+
+    /// Hexadecimal blake2 hash of the complete database content, i.e. of
+    /// the canonical dump, see [`cold_release_dump`]
+    pub content_hash: String,
+}
+
+/// Network specs entry in [`ColdReleaseManifest`].
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ManifestNetwork {
+    pub name: String,
+    pub encryption: String,
+    pub genesis_hash: String,
+    pub base58prefix: u16,
+    pub decimals: u8,
+    pub unit: String,
+    pub title: String,
+    pub order: u8,
+}
+
+/// Network verifier entry in [`ColdReleaseManifest`].
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ManifestVerifier {
+    pub genesis_hash: String,
+
+    /// `general`, or custom verifier public key and encryption
+    pub verifier: String,
+}
+
+/// Metadata entry in [`ColdReleaseManifest`].
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ManifestMetadata {
+    pub name: String,
+    pub version: u32,
+
+    /// Hexadecimal blake2 hash of the full metadata
+    pub hash: String,
+}
+
+/// Make manifest for the cold release database.
+pub fn cold_release_manifest(database: &sled::Db) -> Result<ColdReleaseManifest> {
+    let settings = open_tree(database, SETTREE)?;
+    let schema_version = match settings.get(SCHEMA_VERSION)? {
+        Some(a) => Some(*SchemaVersion::from_ivec(&a)?),
+        None => None,
+    };
+    let types_hash = settings.get(TYPES)?.map(|a| hex::encode(blake2_256(&a)));
+
+    let mut networks = Vec::new();
+    for x in open_tree(database, SPECSTREE)?.iter().flatten() {
+        let ordered_specs = OrderedNetworkSpecs::from_entry_checked(x)?;
+        let specs = ordered_specs.specs;
+        networks.push(ManifestNetwork {
+            name: specs.name,
+            encryption: specs.encryption.show(),
+            genesis_hash: hex::encode(specs.genesis_hash),
+            base58prefix: specs.base58prefix,
+            decimals: specs.decimals,
+            unit: specs.unit,
+            title: specs.title,
+            order: ordered_specs.order,
+        })
+    }
+    networks
+        .sort_by(|a, b| (a.order, &a.name, &a.encryption).cmp(&(b.order, &b.name, &b.encryption)));
+
+    let mut verifiers = Vec::new();
+    for (key, value) in open_tree(database, VERIFIERS)?.iter().flatten() {
+        let genesis_hash = VerifierKey::from_ivec(&key)?.genesis_hash();
+        let verifier = match CurrentVerifier::decode(&mut &value[..])? {
+            CurrentVerifier::Valid(ValidCurrentVerifier::General) => String::from("general"),
+            CurrentVerifier::Valid(ValidCurrentVerifier::Custom { v }) => v.show_error(),
+        };
+        verifiers.push(ManifestVerifier {
+            genesis_hash: hex::encode(genesis_hash),
+            verifier,
+        })
+    }
+    verifiers.sort_by(|a, b| a.genesis_hash.cmp(&b.genesis_hash));
+
+    let mut metadata = Vec::new();
+    for x in open_tree(database, METATREE)?.iter().flatten() {
+        let meta_values = MetaValues::from_entry_checked(x)?;
+        metadata.push(ManifestMetadata {
+            hash: hex::encode(meta_values.hash()),
+            name: meta_values.name,
+            version: meta_values.version,
+        })
+    }
+    metadata.sort_by(|a, b| (&a.name, a.version).cmp(&(&b.name, b.version)));
+
+    Ok(ColdReleaseManifest {
+        schema_version,
+        networks,
+        verifiers,
+        metadata,
+        types_hash,
+        content_hash: hex::encode(blake2_256(&cold_release_dump(database)?)),
+    })
+}
+
+/// Canonical dump of the complete database content.
+///
+/// All non-empty trees, sorted by name, with all entries in key order, are
+/// SCALE-encoded. Empty trees are skipped, so that a dropped tree and an empty
+/// tree give the same dump.
+pub fn cold_release_dump(database: &sled::Db) -> Result<Vec<u8>> {
+    let mut tree_names = database.tree_names();
+    tree_names.sort();
+    let mut content: Vec<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>)> = Vec::new();
+    for tree_name in tree_names.into_iter() {
+        let mut entries = Vec::new();
+        for (key, value) in open_tree(database, &tree_name)?.iter().flatten() {
+            entries.push((key.to_vec(), value.to_vec()))
+        }
+        if !entries.is_empty() {
+            content.push((tree_name.to_vec(), entries))
+        }
+    }
+    Ok(content.encode())
+}
+
+/// Write manifest and canonical dump for the cold release database into the
+/// files.
+pub fn write_cold_release_manifest<P, Q>(database: &sled::Db, path: P, dump_path: Q) -> Result<()>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let manifest = cold_release_manifest(database)?;
+    std::fs::write(path, serde_json::to_string_pretty(&manifest)? + "\n")?;
+    std::fs::write(dump_path, cold_release_dump(database)?)?;
+    Ok(())
+}
+
+/// Check the cold release database and its canonical dump against the
+/// manifest file.
+///
+/// Reports the first manifest section that does not match, the dump is
+/// checked last.
+pub fn verify_cold_release<P, Q>(database: &sled::Db, path: P, dump_path: Q) -> Result<()>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let expected: ColdReleaseManifest = serde_json::from_slice(&std::fs::read(path)?)?;
+    let dump = std::fs::read(dump_path)?;
+    let found = cold_release_manifest(database)?;
+    let part = if found.schema_version != expected.schema_version {
+        "schema version"
+    } else if found.networks != expected.networks {
+        "network specs"
+    } else if found.verifiers != expected.verifiers {
+        "verifiers"
+    } else if found.metadata != expected.metadata {
+        "metadata"
+    } else if found.types_hash != expected.types_hash {
+        "types information"
+    } else if found.content_hash != expected.content_hash {
+        "database content"
+    } else if hex::encode(blake2_256(&dump)) != expected.content_hash {
+        "database dump"
+    } else {
+        return Ok(());
+    };
+    Err(Error::ColdReleaseMismatch {
+        part: part.to_string(),
+    })
+}
@@ -33,6 +33,58 @@ pub enum Error {
     #[error(transparent)]
     Metadata(#[from] MetadataError),
 
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    QrReader(#[from] qr_reader_phone::Error),
+
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+
+    #[error(transparent)]
+    TomlSerialize(#[from] toml::ser::Error),
+
+    #[error(transparent)]
+    TomlDeserialize(#[from] toml::de::Error),
+
+    #[error("Unknown networks file format {}, expected `.toml` or `.json` file.", .0.display())]
+    NetworksFileFormat(std::path::PathBuf),
+
+    #[error("Unexpected encryption {encryption} for network {name} in networks file.")]
+    NetworksFileEncryption { name: String, encryption: String },
+
+    #[error("Network {title} is listed in networks file more than once.")]
+    NetworksFileDuplicate { title: String },
+
+    #[error("No response for {method} with parameters {params} in RPC fixture {}.", .path.display())]
+    RpcFixtureMissing {
+        path: std::path::PathBuf,
+        method: String,
+        params: String,
+    },
+
+    #[error("Cold release database does not match the manifest: {part} differ.")]
+    ColdReleaseMismatch { part: String },
+
+    #[error("Chain spec {} has no raw genesis storage, use `build-spec --raw` output.", .0.display())]
+    ChainSpecNotRaw(std::path::PathBuf),
+
+    #[error("Chain spec {} has no runtime code in genesis storage.", .0.display())]
+    ChainSpecNoCode(std::path::PathBuf),
+
+    #[error("Chain spec {} could not be used without `--encryption` key, encryption is not in the chain spec.", .0.display())]
+    ChainSpecNoEncryption(std::path::PathBuf),
+
+    #[error("QR code {} does not contain a complete Vault update.", .0.display())]
+    QrNotUpdate(std::path::PathBuf),
+
+    #[error("Keystore file not supported: {0}.")]
+    KeystoreUnsupported(String),
+
+    #[error("Keystore secret is damaged or does not match the encryption.")]
+    KeystoreSecret,
+
     #[error("qr error {0}")]
     Qr(Box<dyn std::error::Error>),
 
@@ -151,6 +203,15 @@ pub enum Error {
     #[error("Unexpected metadata format.")]
     UnexpectedMetadataFormat,
 
+    #[error("Unexpected runtime API call result format.")]
+    UnexpectedStateCallFormat,
+
+    #[error("Unexpected runtime version format.")]
+    UnexpectedRuntimeVersionFormat,
+
+    #[error("Subscriptions are not supported for address {url}, WebSocket address is needed.")]
+    SubscriptionNotSupported { url: String },
+
     #[error("Unexpected genesis hash format.")]
     UnexpectedGenesisHashFormat,
 
@@ -199,18 +260,6 @@ pub enum Error {
         url: String,
     },
 
-    /// `METATREE` of the hot database should contain at most two latest
-    /// metadata versions for each network, with the older entries being
-    /// deleted as the new ones appear.
-    ///
-    /// This error appears if during the processing more than two metadata
-    /// entries for the network are found.
-    #[error("More than two entries for network {name} in hot database.")]
-    HotDatabaseMetadataOverTwoEntries {
-        /// network name
-        name: String,
-    },
-
     /// `METATREE` of the hot database has two entries for a network with the
     /// same metadata version.
     ///
@@ -306,6 +355,10 @@ pub enum Error {
         value: String,
     },
 
+    /// User-entered block range has first block after the last one
+    #[error("Block range {from}-{to} is empty.")]
+    BlockRangeEmpty { from: u32, to: u32 },
+
     /// User-entered block hash has invalid length
     #[error("Provided block hash has wrong length.")]
     BlockHashLength,
@@ -11,7 +11,7 @@
 //!         <th>fetched information</th>
 //!     </tr>
 //!     <tr>
-//!         <td><code>state_getMetadata</code>, for current block</td>
+//!         <td>metadata fetch, for current block</td>
 //!         <td>current block network metadata, that will be used to get:<br>
 //!             - network name<br>
 //!             - base58 prefix from metadata
@@ -45,7 +45,7 @@
 //!         <td>current block hash</td>
 //!     </tr>
 //!     <tr>
-//!         <td><code>state_getMetadata</code>, for just fetched block hash</td>
+//!         <td>metadata fetch, for just fetched block hash</td>
 //!         <td>latest network metadata</td>
 //!     </tr>
 //!     <tr>
@@ -57,22 +57,63 @@
 //! Block hash is fetched first to always have network metadata matching the
 //! block hash, even if the two RPC calls were done during block switching.
 //!
+//! For `watch` command, runtime version updates are received through
+//! `state_subscribeRuntimeVersion` subscription, available only for WebSocket
+//! addresses.
+//!
+//! For `load-metadata` with `-a` key, fetches for all networks are made
+//! concurrently, with bounded number of simultaneous fetches, and failed
+//! fetches are retried with increasing delays.
+//!
+//! For metadata retention block ranges, metadata version at a block number is
+//! found with `chain_getBlockHash` call for the block number and
+//! `state_getRuntimeVersion` call for the received block hash.
+//!
+//! Metadata fetch selects the highest `RuntimeMetadata` version supported both
+//! by the node and by the Vault (see
+//! [`SUPPORTED_METADATA_VERSIONS`](definitions::metadata::SUPPORTED_METADATA_VERSIONS)).
+//! Versions available in the node are fetched with `state_call` of
+//! `Metadata_metadata_versions` runtime API, and the metadata itself with
+//! `state_call` of `Metadata_metadata_at_version`. If the runtime API is not
+//! available, or offers no suitable versions, metadata is fetched with
+//! `state_getMetadata`, that always gives `V14` metadata.
+//!
+//! Metadata version for `load_metadata` payloads could be capped with
+//! `--metadata-version` key, e.g. `--metadata-version 14` for payloads
+//! accepted by older Vaults that do not support `V15` metadata.
+//!
 //! Addresses for RPC calls in different networks could be found
 //! [here](https://github.com/polkadot-js/apps/tree/master/packages/apps-config/src/endpoints)
 //!
+//! Addresses starting with `http://` or `https://` are served with HTTP
+//! JSON-RPC client, addresses starting with `file://` are read as
+//! [`FixtureClient`] files with recorded responses, for offline tests. All
+//! other addresses are served with WebSocket client.
+//!
 //! This module deals only with the RPC calls part and does **no processing**
 //! of the fetched data.
-use jsonrpsee::core::client::ClientT;
+use async_trait::async_trait;
+use jsonrpsee::core::client::{ClientT, Subscription, SubscriptionClientT};
+use jsonrpsee::core::params::ArrayParams;
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use jsonrpsee::rpc_params;
-use jsonrpsee::ws_client::WsClientBuilder;
+use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
 use lazy_static::lazy_static;
+use parity_scale_codec::{Decode, Encode};
 use regex::Regex;
+use serde::Deserialize;
 use serde_json::{
     map::Map,
     value::{Number, Value},
 };
 use sp_core::H256;
+use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use std::time::Duration;
+use tokio::sync::Semaphore;
+
+use definitions::metadata::SUPPORTED_METADATA_VERSIONS;
 
 use crate::error::{Error, Result};
 
@@ -120,6 +161,9 @@ lazy_static! {
 const CONNECTION_TIMEOUT: Duration = Duration::from_secs(60);
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
 
+/// Delay before the first retry of a failed fetch, doubled for each next retry.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
 /// Supply address with port if needed.
 ///
 /// Transform address as it is displayed to user in <https://polkadot.js.org/>
@@ -148,6 +192,123 @@ fn address_with_port(str_address: &str) -> String {
     }
 }
 
+/// Client making JSON-RPC calls to a node.
+///
+/// Implemented for WebSocket and HTTP `jsonrpsee` clients, and for
+/// [`FixtureClient`] with recorded responses.
+#[async_trait]
+pub trait RpcClient: Send + Sync {
+    /// Make RPC call `method` with positional `params`.
+    async fn request(&self, method: &str, params: Vec<Value>) -> Result<Value>;
+}
+
+/// Collect positional parameters into `jsonrpsee` parameters.
+fn array_params(params: Vec<Value>) -> Result<ArrayParams> {
+    let mut array_params = ArrayParams::new();
+    for param in params.into_iter() {
+        array_params.insert(param)?;
+    }
+    Ok(array_params)
+}
+
+#[async_trait]
+impl RpcClient for WsClient {
+    async fn request(&self, method: &str, params: Vec<Value>) -> Result<Value> {
+        Ok(ClientT::request(self, method, array_params(params)?).await?)
+    }
+}
+
+#[async_trait]
+impl RpcClient for HttpClient {
+    async fn request(&self, method: &str, params: Vec<Value>) -> Result<Value> {
+        Ok(ClientT::request(self, method, array_params(params)?).await?)
+    }
+}
+
+/// Single recorded RPC call in [`FixtureClient`] file.
+#[derive(Debug, Deserialize)]
+pub struct FixtureEntry {
+    /// RPC method name
+    pub method: String,
+
+    /// Positional call parameters, empty if not specified
+    #[serde(default)]
+    pub params: Vec<Value>,
+
+    /// Response to the call
+    pub result: Value,
+}
+
+/// File-backed client, responding to RPC calls with recorded results.
+///
+/// Fixture file is a JSON array of [`FixtureEntry`] objects:
+///
+/// ```json
+/// [
+///   {"method": "chain_getBlockHash", "params": [0], "result": "0x91b1..."},
+///   {"method": "system_properties", "result": {"tokenDecimals": 10}}
+/// ]
+/// ```
+///
+/// Call is answered with the result of the first entry with the same method
+/// and parameters.
+pub struct FixtureClient {
+    path: PathBuf,
+    entries: Vec<FixtureEntry>,
+}
+
+impl FixtureClient {
+    /// Read fixture file.
+    pub fn from_file<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let entries = serde_json::from_slice(&std::fs::read(path.as_ref())?)?;
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            entries,
+        })
+    }
+}
+
+#[async_trait]
+impl RpcClient for FixtureClient {
+    async fn request(&self, method: &str, params: Vec<Value>) -> Result<Value> {
+        self.entries
+            .iter()
+            .find(|entry| entry.method == method && entry.params == params)
+            .map(|entry| entry.result.to_owned())
+            .ok_or_else(|| Error::RpcFixtureMissing {
+                path: self.path.to_owned(),
+                method: method.to_string(),
+                params: Value::Array(params).to_string(),
+            })
+    }
+}
+
+/// Make [`RpcClient`] for the address.
+///
+/// Client is selected by the address scheme, see module-level docs.
+pub async fn connect(str_address: &str) -> Result<Box<dyn RpcClient>> {
+    if let Some(path) = str_address.strip_prefix("file://") {
+        Ok(Box::new(FixtureClient::from_file(path)?))
+    } else if str_address.starts_with("http://") || str_address.starts_with("https://") {
+        Ok(Box::new(
+            HttpClientBuilder::default()
+                .request_timeout(REQUEST_TIMEOUT)
+                .build(str_address)?,
+        ))
+    } else {
+        Ok(Box::new(
+            WsClientBuilder::default()
+                .connection_timeout(CONNECTION_TIMEOUT)
+                .request_timeout(REQUEST_TIMEOUT)
+                .build(address_with_port(str_address)) // port supplied if needed
+                .await?,
+        ))
+    }
+}
+
 /// Fetch data for `load_metadata` update payload through RPC calls.
 ///
 /// Function inputs address at which RPC calls are made.
@@ -157,35 +318,30 @@ fn address_with_port(str_address: &str) -> String {
 /// 1. current block hash
 /// 2. metadata at this block hash
 /// 3. network genesis hash
+///
+/// Metadata is fetched in the highest `RuntimeMetadata` version not exceeding
+/// `max_metadata_version`, if one is set.
 #[tokio::main]
-pub async fn fetch_info(str_address: &str) -> Result<FetchedInfo> {
-    let client = WsClientBuilder::default()
-        .connection_timeout(CONNECTION_TIMEOUT)
-        .request_timeout(REQUEST_TIMEOUT)
-        .build(address_with_port(str_address)) // port supplied if needed
-        .await?;
-    let response: Value = client.request("chain_getBlockHash", rpc_params![]).await?;
+pub async fn fetch_info(
+    str_address: &str,
+    max_metadata_version: Option<u32>,
+) -> Result<FetchedInfo> {
+    let client = connect(str_address).await?;
+    fetch_info_with_client(client.as_ref(), max_metadata_version).await
+}
+
+/// Fetch data for `load_metadata` update payload with given [`RpcClient`].
+pub async fn fetch_info_with_client(
+    client: &dyn RpcClient,
+    max_metadata_version: Option<u32>,
+) -> Result<FetchedInfo> {
+    let response = client.request("chain_getBlockHash", vec![]).await?;
     let block_hash = match response {
         Value::String(x) => x,
         _ => return Err(Error::UnexpectedBlockHashFormat),
     };
-    let response: Value = client
-        .request("state_getMetadata", rpc_params![&block_hash])
-        .await?;
-    let meta = match response {
-        Value::String(x) => x,
-        _ => return Err(Error::UnexpectedMetadataFormat),
-    };
-    let response: Value = client
-        .request(
-            "chain_getBlockHash",
-            rpc_params![Value::Number(Number::from(0u8))],
-        )
-        .await?;
-    let genesis_hash = match response {
-        Value::String(x) => x,
-        _ => return Err(Error::UnexpectedGenesisHashFormat),
-    };
+    let meta = fetch_meta(client, Some(&block_hash), max_metadata_version).await?;
+    let genesis_hash = fetch_genesis_hash(client).await?;
     Ok(FetchedInfo {
         meta,
         block_hash,
@@ -193,6 +349,74 @@ pub async fn fetch_info(str_address: &str) -> Result<FetchedInfo> {
     })
 }
 
+/// Fetch data for `load_metadata` update payloads at several addresses at
+/// once.
+///
+/// At most `jobs` addresses are processed at the same time. Failed fetch is
+/// retried at most `retries` times, with [`RETRY_DELAY`] before the first
+/// retry, doubled for each next one.
+///
+/// Outputs fetch results in the same order as the input addresses, each with
+/// the number of attempts made.
+pub fn fetch_info_set(
+    addresses: &[String],
+    jobs: usize,
+    retries: u32,
+    max_metadata_version: Option<u32>,
+) -> Result<Vec<(Result<FetchedInfo>, u32)>> {
+    // all fetches run in a single thread, the work here is waiting for the
+    // nodes, and the errors need not be sent between threads
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    let local_set = tokio::task::LocalSet::new();
+    let semaphore = Rc::new(Semaphore::new(jobs));
+    let out = local_set.block_on(&runtime, async {
+        let handles: Vec<_> = addresses
+            .iter()
+            .map(|address| {
+                let semaphore = Rc::clone(&semaphore);
+                let address = address.to_string();
+                tokio::task::spawn_local(async move {
+                    let _permit = semaphore.acquire().await;
+                    fetch_info_with_retries(&address, retries, max_metadata_version).await
+                })
+            })
+            .collect();
+        let mut out = Vec::new();
+        for handle in handles.into_iter() {
+            match handle.await {
+                Ok(a) => out.push(a),
+                Err(e) => std::panic::resume_unwind(e.into_panic()),
+            }
+        }
+        out
+    });
+    Ok(out)
+}
+
+/// Fetch data for `load_metadata` update payload, retrying on errors.
+///
+/// Outputs the last fetch result and the number of attempts made.
+async fn fetch_info_with_retries(
+    str_address: &str,
+    retries: u32,
+    max_metadata_version: Option<u32>,
+) -> (Result<FetchedInfo>, u32) {
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let result = match connect(str_address).await {
+            Ok(client) => fetch_info_with_client(client.as_ref(), max_metadata_version).await,
+            Err(e) => Err(e),
+        };
+        if result.is_ok() || attempts > retries {
+            return (result, attempts);
+        }
+        tokio::time::sleep(RETRY_DELAY * 2u32.saturating_pow(attempts - 1)).await;
+    }
+}
+
 /// Fetch network metadata from given URL address at given block through RPC
 /// call.
 ///
@@ -200,21 +424,63 @@ pub async fn fetch_info(str_address: &str) -> Result<FetchedInfo> {
 /// format. Outputs hexadecimal metadata.
 #[tokio::main]
 pub async fn fetch_meta_at_block(str_address: &str, block_hash: H256) -> Result<String> {
-    let client = WsClientBuilder::default()
-        .connection_timeout(CONNECTION_TIMEOUT)
-        .request_timeout(REQUEST_TIMEOUT)
-        .build(address_with_port(str_address)) // port supplied if needed
-        .await?;
-    let response: Value = client
+    let client = connect(str_address).await?;
+    fetch_meta_at_block_with_client(client.as_ref(), block_hash).await
+}
+
+/// Fetch network metadata at given block with given [`RpcClient`].
+pub async fn fetch_meta_at_block_with_client(
+    client: &dyn RpcClient,
+    block_hash: H256,
+) -> Result<String> {
+    fetch_meta(
+        client,
+        Some(&format!("0x{}", hex::encode(block_hash))),
+        None,
+    )
+    .await
+}
+
+/// Fetch network metadata version at given block number through RPC calls.
+///
+/// Block hash for the block number is fetched first, and then the runtime
+/// version at this block hash.
+#[tokio::main]
+pub async fn fetch_version_at_block_number(str_address: &str, number: u32) -> Result<u32> {
+    let client = connect(str_address).await?;
+    fetch_version_at_block_number_with_client(client.as_ref(), number).await
+}
+
+/// Fetch network metadata version at given block number with given
+/// [`RpcClient`].
+pub async fn fetch_version_at_block_number_with_client(
+    client: &dyn RpcClient,
+    number: u32,
+) -> Result<u32> {
+    let block_hash = match client
         .request(
-            "state_getMetadata",
-            rpc_params![Value::String(format!("0x{}", hex::encode(block_hash)))],
+            "chain_getBlockHash",
+            vec![Value::Number(Number::from(number))],
         )
+        .await?
+    {
+        Value::String(x) => x,
+        _ => return Err(Error::UnexpectedBlockHashFormat),
+    };
+    let runtime_version = client
+        .request("state_getRuntimeVersion", vec![Value::String(block_hash)])
         .await?;
-    match response {
-        Value::String(x) => Ok(x),
-        _ => Err(Error::UnexpectedMetadataFormat),
-    }
+    spec_version(&runtime_version)
+}
+
+/// Get `specVersion` from runtime version, as received through RPC call or
+/// subscription.
+pub fn spec_version(value: &Value) -> Result<u32> {
+    value
+        .get("specVersion")
+        .and_then(Value::as_u64)
+        .and_then(|a| u32::try_from(a).ok())
+        .ok_or(Error::UnexpectedRuntimeVersionFormat)
 }
 
 /// Fetch data for `add_specs` update payload through RPC calls.
@@ -230,27 +496,17 @@ pub async fn fetch_meta_at_block(str_address: &str, block_hash: H256) -> Result<
 pub async fn fetch_info_with_network_specs(
     str_address: &str,
 ) -> Result<FetchedInfoWithNetworkSpecs> {
-    let client = WsClientBuilder::default()
-        .connection_timeout(CONNECTION_TIMEOUT)
-        .request_timeout(REQUEST_TIMEOUT)
-        .build(address_with_port(str_address)) // port supplied if needed
-        .await?;
-    let response: Value = client.request("state_getMetadata", rpc_params![]).await?;
-    let meta = match response {
-        Value::String(x) => x,
-        _ => return Err(Error::UnexpectedMetadataFormat),
-    };
-    let response: Value = client
-        .request(
-            "chain_getBlockHash",
-            rpc_params![Value::Number(Number::from(0u8))],
-        )
-        .await?;
-    let genesis_hash = match response {
-        Value::String(x) => x,
-        _ => return Err(Error::UnexpectedGenesisHashFormat),
-    };
-    let response: Value = client.request("system_properties", rpc_params![]).await?;
+    let client = connect(str_address).await?;
+    fetch_info_with_network_specs_with_client(client.as_ref()).await
+}
+
+/// Fetch data for `add_specs` update payload with given [`RpcClient`].
+pub async fn fetch_info_with_network_specs_with_client(
+    client: &dyn RpcClient,
+) -> Result<FetchedInfoWithNetworkSpecs> {
+    let meta = fetch_meta(client, None, None).await?;
+    let genesis_hash = fetch_genesis_hash(client).await?;
+    let response = client.request("system_properties", vec![]).await?;
     let properties = match response {
         Value::Object(x) => x,
         _ => return Err(Error::UnexpectedSystemPropertiesFormat),
@@ -262,9 +518,207 @@ pub async fn fetch_info_with_network_specs(
     })
 }
 
+/// Subscription to network runtime version updates.
+///
+/// Holds the client, subscription is active as long as the client is alive.
+pub struct RuntimeVersionSubscription {
+    subscription: Subscription<Value>,
+    _client: WsClient,
+}
+
+impl RuntimeVersionSubscription {
+    /// Wait for the next runtime version notification.
+    ///
+    /// First notification is the current runtime version. `None` means the
+    /// subscription is closed.
+    pub async fn next(&mut self) -> Option<Result<Value>> {
+        self.subscription
+            .next()
+            .await
+            .map(|response| response.map_err(Error::from))
+    }
+}
+
+/// Subscribe to network runtime version updates through RPC call
+/// `state_subscribeRuntimeVersion`.
+///
+/// Subscriptions are available only with WebSocket client.
+pub async fn subscribe_runtime_version(str_address: &str) -> Result<RuntimeVersionSubscription> {
+    if str_address.starts_with("file://")
+        || str_address.starts_with("http://")
+        || str_address.starts_with("https://")
+    {
+        return Err(Error::SubscriptionNotSupported {
+            url: str_address.to_string(),
+        });
+    }
+    let client = WsClientBuilder::default()
+        .connection_timeout(CONNECTION_TIMEOUT)
+        .request_timeout(REQUEST_TIMEOUT)
+        .build(address_with_port(str_address)) // port supplied if needed
+        .await?;
+    let subscription = client
+        .subscribe(
+            "state_subscribeRuntimeVersion",
+            rpc_params![],
+            "state_unsubscribeRuntimeVersion",
+        )
+        .await?;
+    Ok(RuntimeVersionSubscription {
+        subscription,
+        _client: client,
+    })
+}
+
+/// Fetch hexadecimal network metadata, at given block hash or at current
+/// block, in the highest `RuntimeMetadata` version supported both by the node
+/// and by the Vault, and not exceeding `max_metadata_version`, if it is set.
+async fn fetch_meta(
+    client: &dyn RpcClient,
+    block_hash: Option<&str>,
+    max_metadata_version: Option<u32>,
+) -> Result<String> {
+    let versions = state_call(client, "Metadata_metadata_versions", &[], block_hash)
+        .await
+        .ok()
+        .and_then(|data| <Vec<u32>>::decode(&mut &data[..]).ok())
+        .unwrap_or_default();
+    let selected_version = versions
+        .into_iter()
+        .filter(|version| SUPPORTED_METADATA_VERSIONS.contains(version))
+        .filter(|version| max_metadata_version.map_or(true, |max| *version <= max))
+        .max();
+    match selected_version {
+        Some(version) => {
+            let data = state_call(
+                client,
+                "Metadata_metadata_at_version",
+                &version.encode(),
+                block_hash,
+            )
+            .await?;
+            match <Option<Vec<u8>>>::decode(&mut &data[..])? {
+                Some(meta) => Ok(format!("0x{}", hex::encode(meta))),
+                None => Err(Error::UnexpectedMetadataFormat),
+            }
+        }
+        None => {
+            let mut params = Vec::new();
+            if let Some(block_hash) = block_hash {
+                params.push(Value::String(block_hash.to_string()))
+            }
+            match client.request("state_getMetadata", params).await? {
+                Value::String(x) => Ok(x),
+                _ => Err(Error::UnexpectedMetadataFormat),
+            }
+        }
+    }
+}
+
+/// Call runtime API `method` with SCALE-encoded `data` through `state_call`,
+/// at given block hash or at current block.
+///
+/// Outputs SCALE-encoded result.
+async fn state_call(
+    client: &dyn RpcClient,
+    method: &str,
+    data: &[u8],
+    block_hash: Option<&str>,
+) -> Result<Vec<u8>> {
+    let mut params = vec![
+        Value::String(method.to_string()),
+        Value::String(format!("0x{}", hex::encode(data))),
+    ];
+    if let Some(block_hash) = block_hash {
+        params.push(Value::String(block_hash.to_string()))
+    }
+    match client.request("state_call", params).await? {
+        Value::String(x) => {
+            hex::decode(x.trim_start_matches("0x")).map_err(|_| Error::UnexpectedStateCallFormat)
+        }
+        _ => Err(Error::UnexpectedStateCallFormat),
+    }
+}
+
+/// Fetch network genesis hash, i.e. hash of the 0th block.
+async fn fetch_genesis_hash(client: &dyn RpcClient) -> Result<String> {
+    let response = client
+        .request("chain_getBlockHash", vec![Value::Number(Number::from(0u8))])
+        .await?;
+    match response {
+        Value::String(x) => Ok(x),
+        _ => Err(Error::UnexpectedGenesisHashFormat),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    /// Local HTTP JSON-RPC node, answering calls with results of the
+    /// [`FixtureEntry`] list, and with `Method not found` error for calls not
+    /// in the list.
+    ///
+    /// Responses are sent with HTTP `status`. Outputs node address.
+    fn mock_http_node(entries: &str, status: u16) -> String {
+        let entries: Vec<FixtureEntry> = serde_json::from_str(entries).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                let request: Value = serde_json::from_slice(&body).unwrap();
+                assert_eq!(request["jsonrpc"], "2.0");
+                let method = request["method"].as_str().unwrap();
+                let params = match request.get("params") {
+                    Some(Value::Array(params)) => params.to_owned(),
+                    _ => Vec::new(),
+                };
+                let response = match entries
+                    .iter()
+                    .find(|entry| entry.method == method && entry.params == params)
+                {
+                    Some(entry) => serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": request["id"],
+                        "result": entry.result,
+                    }),
+                    None => serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": request["id"],
+                        "error": {"code": -32601, "message": "Method not found"},
+                    }),
+                }
+                .to_string();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                    response.len()
+                )
+                .unwrap();
+            }
+        });
+        address
+    }
     #[test]
     fn address_1() {
         let address = "wss://rpc.polkadot.io";
@@ -336,4 +790,158 @@ mod tests {
             "Fetch address calc: \n{address_calc}"
         );
     }
+
+    #[test]
+    fn fixture_client() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixture.json");
+        std::fs::write(
+            &path,
+            r#"[
+                {"method": "state_getMetadata", "result": "0x6d657461"},
+                {"method": "chain_getBlockHash", "params": [0], "result": "0x91b1"},
+                {"method": "system_properties", "result": {"tokenDecimals": 10}}
+            ]"#,
+        )
+        .unwrap();
+        let address = format!("file://{}", path.to_string_lossy());
+
+        let fetched = fetch_info_with_network_specs(&address).unwrap();
+        assert_eq!(fetched.meta, "0x6d657461");
+        assert_eq!(fetched.genesis_hash, "0x91b1");
+        assert_eq!(fetched.properties["tokenDecimals"], 10);
+
+        // current block hash is not recorded
+        assert!(matches!(
+            fetch_info(&address, None),
+            Err(Error::RpcFixtureMissing { method, .. }) if method == "chain_getBlockHash"
+        ));
+    }
+
+    #[test]
+    fn fixture_client_metadata_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixture.json");
+        // node offers versions 14, 15 and 16, version 15 is selected
+        std::fs::write(
+            &path,
+            r#"[
+                {"method": "state_call", "params": ["Metadata_metadata_versions", "0x"], "result": "0x0c0e0000000f00000010000000"},
+                {"method": "state_call", "params": ["Metadata_metadata_at_version", "0x0f000000"], "result": "0x01146d6574610f"},
+                {"method": "state_getMetadata", "result": "0x6d6574610e"},
+                {"method": "chain_getBlockHash", "params": [0], "result": "0x91b1"},
+                {"method": "system_properties", "result": {"tokenDecimals": 10}}
+            ]"#,
+        )
+        .unwrap();
+        let address = format!("file://{}", path.to_string_lossy());
+
+        let fetched = fetch_info_with_network_specs(&address).unwrap();
+        assert_eq!(fetched.meta, "0x6d6574610f");
+    }
+
+    #[test]
+    fn fixture_client_metadata_version_capped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixture.json");
+        // node offers versions 14, 15 and 16, version 14 is selected with cap
+        std::fs::write(
+            &path,
+            r#"[
+                {"method": "chain_getBlockHash", "result": "0x5a5a"},
+                {"method": "state_call", "params": ["Metadata_metadata_versions", "0x", "0x5a5a"], "result": "0x0c0e0000000f00000010000000"},
+                {"method": "state_call", "params": ["Metadata_metadata_at_version", "0x0e000000", "0x5a5a"], "result": "0x01146d6574610e"},
+                {"method": "state_call", "params": ["Metadata_metadata_at_version", "0x0f000000", "0x5a5a"], "result": "0x01146d6574610f"},
+                {"method": "chain_getBlockHash", "params": [0], "result": "0x91b1"}
+            ]"#,
+        )
+        .unwrap();
+        let address = format!("file://{}", path.to_string_lossy());
+
+        let fetched = fetch_info(&address, None).unwrap();
+        assert_eq!(fetched.meta, "0x6d6574610f");
+
+        let fetched = fetch_info(&address, Some(14)).unwrap();
+        assert_eq!(fetched.meta, "0x6d6574610e");
+        assert_eq!(fetched.block_hash, "0x5a5a");
+    }
+
+    #[test]
+    fn fixture_client_fetch_set() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixture.json");
+        std::fs::write(
+            &path,
+            r#"[
+                {"method": "chain_getBlockHash", "result": "0x5a5a"},
+                {"method": "state_getMetadata", "params": ["0x5a5a"], "result": "0x6d6574610e"},
+                {"method": "chain_getBlockHash", "params": [0], "result": "0x91b1"}
+            ]"#,
+        )
+        .unwrap();
+        let addresses = vec![
+            format!(
+                "file://{}",
+                dir.path().join("missing.json").to_string_lossy()
+            ),
+            format!("file://{}", path.to_string_lossy()),
+        ];
+
+        let fetched = fetch_info_set(&addresses, 1, 1, None).unwrap();
+        assert_eq!(fetched.len(), 2);
+
+        // missing fixture is retried once
+        assert!(matches!(fetched[0], (Err(Error::Io(_)), 2)));
+
+        let (result, attempts) = &fetched[1];
+        let info = result.as_ref().unwrap();
+        assert_eq!(info.block_hash, "0x5a5a");
+        assert_eq!(info.meta, "0x6d6574610e");
+        assert_eq!(info.genesis_hash, "0x91b1");
+        assert_eq!(*attempts, 1);
+    }
+
+    #[test]
+    fn http_client() {
+        // no `state_call` runtime API, metadata is fetched with
+        // `state_getMetadata`
+        let address = mock_http_node(
+            r#"[
+                {"method": "state_getMetadata", "result": "0x6d657461"},
+                {"method": "chain_getBlockHash", "params": [0], "result": "0x91b1"},
+                {"method": "system_properties", "result": {"tokenDecimals": 10}}
+            ]"#,
+            200,
+        );
+
+        let fetched = fetch_info_with_network_specs(&address).unwrap();
+        assert_eq!(fetched.meta, "0x6d657461");
+        assert_eq!(fetched.genesis_hash, "0x91b1");
+        assert_eq!(fetched.properties["tokenDecimals"], 10);
+
+        // call error from the node
+        assert!(matches!(
+            fetch_info(&address, None),
+            Err(Error::JsonRPC(jsonrpsee::core::error::Error::Call(e))) if e.code() == -32601
+        ));
+    }
+
+    #[test]
+    fn http_client_errors() {
+        // node rejects the request
+        let address = mock_http_node("[]", 500);
+        assert!(matches!(
+            fetch_info_with_network_specs(&address),
+            Err(Error::JsonRPC(jsonrpsee::core::error::Error::Transport(_)))
+        ));
+
+        // no node at the address
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        assert!(matches!(
+            fetch_info_with_network_specs(&address),
+            Err(Error::JsonRPC(jsonrpsee::core::error::Error::Transport(_)))
+        ));
+    }
 }
@@ -25,9 +25,12 @@ use definitions::{
 };
 
 use crate::error::{Changed, Error, NotHexActive, Result, SpecsError};
-use crate::fetch_metadata::{fetch_info, fetch_info_with_network_specs, fetch_meta_at_block};
+use crate::fetch_metadata::{
+    fetch_info, fetch_info_with_network_specs, fetch_meta_at_block, FetchedInfo,
+};
 use crate::interpret_specs::{check_specs, interpret_properties, TokenFetch};
 use crate::parser::{Goal, Token};
+use crate::retention::{get_meta_retention, split_by_retention};
 
 /// Get [`AddressBookEntry`] from the database for given address book title.
 pub fn get_address_book_entry(database: &sled::Db, title: &str) -> Result<AddressBookEntry> {
@@ -284,16 +287,16 @@ pub struct SortedMetaValues {
     /// Set of the metadata entries with latest version known to the database.
     pub newer: Vec<MetaValuesStamped>,
 
-    /// Other metadata entries. Since there are maximum two entries allowed,
-    /// this set contains at most one entry for each network.
+    /// Other metadata entries. Number of entries for each network depends on
+    /// the metadata retention policy, see [`crate::retention`].
     pub older: Vec<MetaValuesStamped>,
 }
 
 /// Sort [`MetaValuesStamped`] into sets of newer and older, by metadata
 /// version.
 ///
-/// Database contains maximum two metadata entries for each network name, both
-/// newer and older sets can contain at most one metadata [`MetaValuesStamped`].
+/// Newer set contains single latest metadata [`MetaValuesStamped`] for each
+/// network, older set contains all other entries.
 fn sort_metavalues(meta_values: Vec<MetaValuesStamped>) -> Result<SortedMetaValues> {
     // newer metadata set, i.e. with higher version for given network
     let mut newer: Vec<MetaValuesStamped> = Vec::new();
@@ -315,16 +318,6 @@ fn sort_metavalues(meta_values: Vec<MetaValuesStamped>) -> Result<SortedMetaValu
         // set
         for (i, y) in newer.iter().enumerate() {
             if x.meta_values.name == y.meta_values.name {
-                // search for the network name in already collected elements of
-                // `older` set; should not find any;
-                for z in older.iter() {
-                    if x.meta_values.name == z.meta_values.name {
-                        return Err(Error::HotDatabaseMetadataOverTwoEntries {
-                            name: x.meta_values.name.to_string(),
-                        });
-                    }
-                }
-
                 found_in_new = true;
 
                 // where the entry goes, based on the version
@@ -372,22 +365,25 @@ fn sort_metavalues(meta_values: Vec<MetaValuesStamped>) -> Result<SortedMetaValu
 ///
 /// If the fetched metadata is good and has later version than the ones in
 /// [`SortedMetaValues`], it is added to `newer` set, any previous value from
-/// `newer` is moved to `older`. If there was any value in `older`, it gets
-/// kicked out.
+/// `newer` is moved to `older`. Entries in `older` not covered by the metadata
+/// retention policy get removed when the database is updated, see
+/// [`db_upd_metadata`].
 ///
 /// If there was no block hash in hot database and the metadata did not change,
 /// a new block hash could be added if it is known.
+///
+/// If the fetched metadata has the same version as the one in `newer` set, but
+/// different `RuntimeMetadata` version (e.g. `V15` fetched for network with
+/// `V14` on record), the stored entry is replaced with the fetched one. Which
+/// `RuntimeMetadata` version is fetched is set by the user, with
+/// `--metadata-version` key.
 pub fn add_new_metadata(new: &MetaValuesStamped, sorted: &mut SortedMetaValues) -> Result<bool> {
     // action to perform after sorting on found entry
     enum Found {
         DoNothing,
-        Replace {
-            move_from_newer: usize,
-            remove_from_older: Option<usize>,
-        },
-        UpdateBlock {
-            in_newer: usize,
-        },
+        Replace { move_from_newer: usize },
+        ReplaceFormat { in_newer: usize },
+        UpdateBlock { in_newer: usize },
     }
 
     let mut similar_entries: Option<Found> = None;
@@ -413,56 +409,31 @@ pub fn add_new_metadata(new: &MetaValuesStamped, sorted: &mut SortedMetaValues)
 
                 // same version, no updates;
                 //
-                // check that metadata is exactly the same, different metadata
-                // under same version is an error;
+                // same version in different `RuntimeMetadata` version is
+                // the same metadata in another format, replace the stored one;
+                //
+                // otherwise check that metadata is exactly the same, different
+                // metadata under same version is an error;
                 Ordering::Equal => {
-                    if new.meta_values.meta != x.meta_values.meta {
-                        // metadata comparing, hopefully never to be needed
-                        // again
-                        //
-                        // prints the difference for user to check
-                        let mut sus1: Vec<u8> = Vec::new();
-                        let mut sus2: Vec<u8> = Vec::new();
-                        for a in 0..x.meta_values.meta.len() {
-                            if new.meta_values.meta[a] != x.meta_values.meta[a] {
-                                println!("Suspicious number {a}");
-                                sus1.push(new.meta_values.meta[a]);
-                                sus2.push(x.meta_values.meta[a]);
-                            }
-                        }
-                        println!("new: {sus1:?}, in db: {sus2:?}");
-
+                    if new.meta_values.metadata_version()? != x.meta_values.metadata_version()? {
+                        Some(Found::ReplaceFormat { in_newer: i })
+                    } else if new.meta_values.meta != x.meta_values.meta {
                         return Err(Error::SameVersionDifferentMetadata {
                             name: new.meta_values.name.to_string(),
                             version: new.meta_values.version,
                             block_hash_in_db: x.at_block_hash,
                             block_hash_in_fetch: new.at_block_hash,
                         });
-                    }
-                    match x.at_block_hash {
-                        Some(_) => Some(Found::DoNothing),
-                        None => Some(Found::UpdateBlock { in_newer: i }),
+                    } else {
+                        match x.at_block_hash {
+                            Some(_) => Some(Found::DoNothing),
+                            None => Some(Found::UpdateBlock { in_newer: i }),
+                        }
                     }
                 }
 
                 // fetched newer metadata
-                Ordering::Greater => {
-                    let mut remove_from_older = None;
-
-                    // check if there is entry in `older` to be kicked
-                    // altogether
-                    for (j, y) in sorted.older.iter().enumerate() {
-                        if x.meta_values.name == y.meta_values.name {
-                            // found entry in `older` to be removed
-                            remove_from_older = Some(j);
-                            break;
-                        }
-                    }
-                    Some(Found::Replace {
-                        move_from_newer: i,
-                        remove_from_older,
-                    })
-                }
+                Ordering::Greater => Some(Found::Replace { move_from_newer: i }),
             };
             break;
         }
@@ -470,17 +441,15 @@ pub fn add_new_metadata(new: &MetaValuesStamped, sorted: &mut SortedMetaValues)
 
     match similar_entries {
         Some(Found::DoNothing) => Ok(false),
-        Some(Found::Replace {
-            move_from_newer,
-            remove_from_older,
-        }) => {
-            if let Some(j) = remove_from_older {
-                sorted.older.remove(j);
-            }
+        Some(Found::Replace { move_from_newer }) => {
             sorted.older.push(sorted.newer.remove(move_from_newer));
             sorted.newer.push(new.to_owned());
             Ok(true)
         }
+        Some(Found::ReplaceFormat { in_newer }) => {
+            sorted.newer[in_newer] = new.to_owned();
+            Ok(true)
+        }
         Some(Found::UpdateBlock { in_newer }) => {
             sorted.newer[in_newer].at_block_hash = new.at_block_hash;
             Ok(false)
@@ -501,7 +470,7 @@ pub fn prepare_metadata(database: &sled::Db) -> Result<SortedMetaValues> {
 /// Update the database after `load-metadata` run.
 ///
 /// Clear [`METATREE`] tree of the hot database and write new metadata set in
-/// it.
+/// it. Only the entries kept by the metadata retention policy are written.
 ///
 /// Update [`META_HISTORY`] tree.
 pub fn db_upd_metadata(database: &sled::Db, sorted_meta_values: SortedMetaValues) -> Result<()> {
@@ -509,11 +478,15 @@ pub fn db_upd_metadata(database: &sled::Db, sorted_meta_values: SortedMetaValues
     let mut meta_history_batch = Batch::default();
     let mut all_meta = sorted_meta_values.newer;
     all_meta.extend_from_slice(&sorted_meta_values.older);
-    for x in all_meta.iter() {
+    let (kept_meta, _) = split_by_retention(&get_meta_retention(database)?, all_meta);
+    for x in kept_meta.iter() {
         let meta_key = MetaKey::from_parts(&x.meta_values.name, x.meta_values.version);
         metadata_batch.insert(meta_key.key(), &x.meta_values.meta[..]);
         if let Some(hash) = x.at_block_hash {
-            meta_history_batch.insert(meta_key.key(), hash.encode());
+            meta_history_batch.insert(
+                meta_key.key(),
+                (hash, x.meta_values.metadata_version()?).encode(),
+            );
         }
     }
     TrDbHot::new()
@@ -555,9 +528,16 @@ impl MetaFetched {
 
 /// Get network information through RPC calls at `address` and interpret it into
 /// [`MetaFetched`].
-pub fn meta_fetch(address: &str) -> Result<MetaFetched> {
-    let new_info = fetch_info(address)?;
+///
+/// Metadata is fetched in the highest `RuntimeMetadata` version not exceeding
+/// `max_metadata_version`, if one is set.
+pub fn meta_fetch(address: &str, max_metadata_version: Option<u32>) -> Result<MetaFetched> {
+    interpret_fetched_info(address, fetch_info(address, max_metadata_version)?)
+}
 
+/// Interpret [`FetchedInfo`] received through RPC calls at `address` into
+/// [`MetaFetched`].
+pub fn interpret_fetched_info(address: &str, new_info: FetchedInfo) -> Result<MetaFetched> {
     let genesis_hash = get_hash(
         &new_info.genesis_hash,
         Hash::Genesis {
@@ -1012,16 +992,19 @@ pub fn load_metadata_print<P>(shortcut: &MetaShortCut, files_dir: P) -> Result<(
 where
     P: AsRef<Path>,
 {
-    let file_name = format!(
-        "sign_me_load_metadata_{}V{}",
-        shortcut.meta_values.name, shortcut.meta_values.version
-    );
+    let file_name =
+        load_metadata_file_name(&shortcut.meta_values.name, shortcut.meta_values.version);
     let file_path = files_dir.as_ref().join(file_name);
     let content = ContentLoadMeta::generate(&shortcut.meta_values.meta, &shortcut.genesis_hash);
     content.write(file_path)?;
     Ok(())
 }
 
+/// Name of `load_metadata` update payload file for network metadata.
+pub fn load_metadata_file_name(name: &str, version: u32) -> String {
+    format!("sign_me_load_metadata_{name}V{version}")
+}
+
 /// Write to file `add_specs` update payload as raw bytes.
 ///
 /// Resulting file, located in dedicated directory (by default, [`FOLDER`](constants::FOLDER)), could be
@@ -1040,3 +1023,58 @@ where
     content.write(file_path)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stamped(metadata_version: u8, meta_tail: &[u8]) -> MetaValuesStamped {
+        MetaValuesStamped {
+            meta_values: MetaValues {
+                name: String::from("westend"),
+                version: 9430,
+                optional_base58prefix: Some(42),
+                warn_incomplete_extensions: false,
+                meta: [b"meta", &[metadata_version][..], meta_tail].concat(),
+                full_hash: None,
+            },
+            at_block_hash: Some(H256::zero()),
+        }
+    }
+
+    #[test]
+    fn same_version_other_metadata_version_replaces_stored() {
+        let mut sorted = SortedMetaValues {
+            newer: vec![stamped(14, &[0; 8])],
+            older: Vec::new(),
+        };
+
+        // `V15` fetched for the same network version, shorter than stored
+        let v15 = stamped(15, &[1; 4]);
+        assert!(add_new_metadata(&v15, &mut sorted).unwrap());
+        assert_eq!(sorted.newer.len(), 1);
+        assert_eq!(sorted.newer[0].meta_values, v15.meta_values);
+        assert!(sorted.older.is_empty());
+
+        // same `V15` fetched again, nothing changes
+        assert!(!add_new_metadata(&stamped(15, &[1; 4]), &mut sorted).unwrap());
+
+        // capped at `V14` again, stored one is replaced back
+        let v14 = stamped(14, &[0; 8]);
+        assert!(add_new_metadata(&v14, &mut sorted).unwrap());
+        assert_eq!(sorted.newer.len(), 1);
+        assert_eq!(sorted.newer[0].meta_values, v14.meta_values);
+    }
+
+    #[test]
+    fn same_version_different_metadata_is_error() {
+        let mut sorted = SortedMetaValues {
+            newer: vec![stamped(14, &[0; 8])],
+            older: Vec::new(),
+        };
+        assert!(matches!(
+            add_new_metadata(&stamped(14, &[1; 4]), &mut sorted),
+            Err(Error::SameVersionDifferentMetadata { version: 9430, .. })
+        ));
+    }
+}
@@ -0,0 +1,190 @@
+//! Verifier keys from encrypted keystore files
+//!
+//! Update payloads could be signed with a real verifier key directly by
+//! `make` and `sign` commands, with `--keystore <FILE>` key. The file is
+//! decrypted in memory with the password read from the standard input, and
+//! decrypted secrets are zeroed as soon as the signing pair is made. The secret
+//! is never written to disk in plaintext.
+//!
+//! Supported keystore files are JSON files with scrypt-derived key and
+//! xsalsa20-poly1305 encryption (version `3`):
+//!
+//! - Polkadot-JS account export, with PKCS8 content, for `sr25519`, `ed25519`
+//!   and `ecdsa` accounts
+//! - password-protected seed file, with secret URI (seed phrase and
+//!   derivation) content, made with `make-keystore` command
+//!
+//! ## Make password-protected seed file
+//!
+//! `$ cargo run make-keystore --encryption <ENCRYPTION> --output <FILE>`
+//!
+//! Secret URI and password are read from the standard input, each on its own
+//! line. Public key of the verifier is printed.
+use base64::{engine::general_purpose::STANDARD, Engine};
+use sp_core::{ecdsa, ed25519, sr25519, Pair};
+use std::io::BufRead;
+use std::path::Path;
+use zeroize::Zeroizing;
+
+use db_handling::keystore::{
+    decrypt, encrypt, Keystore, KeystoreEncoding, KeystoreMeta, KeystorePair, ENCRYPTION_TYPE,
+    VERSION,
+};
+use definitions::crypto::{Encryption, SufficientCrypto};
+
+use crate::error::{Error, Result};
+use crate::parser::encryption_from_args;
+
+/// Key pair for signing update payloads.
+pub enum SigningPair {
+    Ed25519(ed25519::Pair),
+    Sr25519(sr25519::Pair),
+    Ecdsa(ecdsa::Pair),
+}
+
+impl SigningPair {
+    /// Make pair from secret URI, i.e. seed phrase or hexadecimal seed with
+    /// optional derivation.
+    ///
+    /// `Ethereum` encryption uses `ecdsa` pair.
+    pub fn from_suri(suri: &str, encryption: Encryption) -> Result<Self> {
+        match encryption {
+            Encryption::Ed25519 => ed25519::Pair::from_string(suri, None).map(Self::Ed25519),
+            Encryption::Sr25519 => sr25519::Pair::from_string(suri, None).map(Self::Sr25519),
+            Encryption::Ecdsa | Encryption::Ethereum => {
+                ecdsa::Pair::from_string(suri, None).map(Self::Ecdsa)
+            }
+        }
+        .map_err(|_| Error::KeystoreSecret)
+    }
+
+    /// Make pair from keystore file, decrypted with given password.
+    ///
+    /// Fields not needed for signing, such as Polkadot-JS account `meta`, are
+    /// ignored.
+    pub fn from_keystore<P>(path: P, password: &str) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let keystore: Keystore = serde_json::from_slice(&std::fs::read(path)?)?;
+        let (content, encryption) = keystore.content_description()?;
+        let encryption = encryption_from_args(encryption)
+            .map_err(|e| Error::KeystoreUnsupported(e.to_string()))?;
+        let encoded = STANDARD
+            .decode(&keystore.encoded)
+            .map_err(|_| Error::KeystoreUnsupported(String::from("content is not base64")))?;
+        let decrypted = decrypt(&encoded, password)?;
+        match content {
+            "pkcs8" => Ok(KeystorePair::from_pkcs8(&decrypted, encryption)?.into()),
+            "seed" => {
+                let suri = std::str::from_utf8(&decrypted).map_err(|_| Error::KeystoreSecret)?;
+                Self::from_suri(suri, encryption)
+            }
+            a => Err(Error::KeystoreUnsupported(format!("content type {a}"))),
+        }
+    }
+
+    /// Hexadecimal public key.
+    pub fn public_hex(&self) -> String {
+        match self {
+            SigningPair::Ed25519(pair) => hex::encode(pair.public()),
+            SigningPair::Sr25519(pair) => hex::encode(pair.public()),
+            SigningPair::Ecdsa(pair) => hex::encode(pair.public()),
+        }
+    }
+
+    /// Sign the message, output [`SufficientCrypto`] with public key and
+    /// signature.
+    pub fn sign(&self, message: &[u8]) -> SufficientCrypto {
+        match self {
+            SigningPair::Ed25519(pair) => SufficientCrypto::Ed25519 {
+                public: pair.public(),
+                signature: pair.sign(message),
+            },
+            SigningPair::Sr25519(pair) => SufficientCrypto::Sr25519 {
+                public: pair.public(),
+                signature: pair.sign(message),
+            },
+            SigningPair::Ecdsa(pair) => SufficientCrypto::Ecdsa {
+                public: pair.public(),
+                signature: pair.sign(message),
+            },
+        }
+    }
+}
+
+impl From<KeystorePair> for SigningPair {
+    fn from(pair: KeystorePair) -> Self {
+        match pair {
+            KeystorePair::Ed25519(pair) => SigningPair::Ed25519(pair),
+            KeystorePair::Sr25519(pair) => SigningPair::Sr25519(pair),
+            KeystorePair::Ecdsa(pair) => SigningPair::Ecdsa(pair),
+        }
+    }
+}
+
+/// Read a single line from the standard input, without the line ending.
+pub fn read_secret_line() -> Result<Zeroizing<String>> {
+    let mut line = Zeroizing::new(String::new());
+    std::io::stdin().lock().read_line(&mut line)?;
+    let len = line.trim_end_matches(['\r', '\n']).len();
+    line.truncate(len);
+    Ok(line)
+}
+
+/// Make password-protected seed file for secret URI.
+///
+/// Outputs [`SigningPair`] made from the secret URI.
+pub fn make_keystore<P>(
+    suri: &str,
+    password: &str,
+    encryption: Encryption,
+    path: P,
+) -> Result<SigningPair>
+where
+    P: AsRef<Path>,
+{
+    let pair = SigningPair::from_suri(suri, encryption)?;
+    let keystore = Keystore {
+        encoded: STANDARD.encode(encrypt(suri.as_bytes(), password)?),
+        encoding: KeystoreEncoding {
+            content: vec![String::from("seed"), encryption.show()],
+            encryption_type: ENCRYPTION_TYPE.iter().map(|a| a.to_string()).collect(),
+            version: VERSION.to_string(),
+        },
+        address: pair.public_hex(),
+        meta: KeystoreMeta::default(),
+    };
+    std::fs::write(path, serde_json::to_string_pretty(&keystore)? + "\n")?;
+    Ok(pair)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Polkadot-JS account export for `//Alice` `ed25519` key, address
+    /// `5FA9nQDVg267DEd8m1ZypXLBnvN7SFxYwV7ndqSYGiN9TTpu`.
+    const POLKADOT_JS_ALICE_ED25519: &str = "./tests/for_tests/keystore_alice_ed25519.json";
+
+    #[test]
+    fn polkadot_js_keystore() {
+        let pair =
+            SigningPair::from_keystore(POLKADOT_JS_ALICE_ED25519, "polkadot-js fixture").unwrap();
+        assert_eq!(
+            pair.public_hex(),
+            "88dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee"
+        );
+        let sufficient_crypto = pair.sign(b"update");
+        assert!(matches!(
+            sufficient_crypto,
+            SufficientCrypto::Ed25519 { public, signature }
+                if ed25519::Pair::verify(&signature, b"update", &public)
+        ));
+
+        assert!(matches!(
+            SigningPair::from_keystore(POLKADOT_JS_ALICE_ED25519, "password"),
+            Err(Error::Database(db_handling::Error::KeystorePassword))
+        ));
+    }
+}
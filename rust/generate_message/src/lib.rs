@@ -113,7 +113,8 @@
 //!    Vault
 //!
 //! Steps (1) and (3) are done in `generate_message`, the signature is produced
-//! in other tools, except the test "signed" updates with Alice as a verifier,
+//! in other tools, except the test "signed" updates with Alice as a verifier
+//! and the updates signed with a verifier key from encrypted keystore file,
 //! when the signature is produced while making QR code during step (3).
 //!
 //! Signature could be produced with Subkey or with Vault. For update signing
@@ -203,9 +204,14 @@
 //! Block hashes could be useful should silent metadata updates (metadata change
 //! with no version bump) happen again.
 //!
+//! Along with the block hash, `RuntimeMetadata` version selected during the
+//! fetch is recorded. Nodes are asked for the highest metadata version
+//! supported both by the node and by the Vault, through `Metadata` runtime API,
+//! see [`fetch_metadata`] for details.
+//!
 //! ## Prepare `add_specs` update payload
 //!
-//! `$ cargo run add-specs [OPTIONS] <-d|-f|-k|-p|-t> <--all|--name <NAME>|--url <ADDRESS>>`
+//! `$ cargo run add-specs [OPTIONS] <-d|-f|-k|-p|-t> <--all|--name <NAME>|--url <ADDRESS>|--chain-spec <FILE>>`
 //!
 //! A file is generated in dedicated [`FOLDER`](constants::FOLDER) to
 //! (optionally) be signed and later be transformed into `add_specs` update
@@ -263,6 +269,12 @@
 //!   existing record in the [`ADDRESS_BOOK`](constants::ADDRESS_BOOK)
 //! - `--url` followed by single URL address: reserved for networks with no
 //!   record yet in the [`ADDRESS_BOOK`](constants::ADDRESS_BOOK)
+//! - `--chain-spec` followed by path to the raw chain spec JSON file: for
+//!   networks with no RPC endpoint, see [`chain_spec`] for details
+//!
+//! URL addresses could be WebSocket (`wss://`) or HTTP (`https://`) node
+//! addresses, or `file://` paths to recorded RPC responses, see
+//! [`fetch_metadata`] for details.
 //!
 //! `--all` key could be used with `--pass-errors` key, to stop processing after first
 //! error.
@@ -283,6 +295,10 @@
 //! By default, if no token override in provided, such networks have `0u8` decimals
 //! and `UNIT` unit set up.
 //!
+//! With `--chain-spec` reference key, base58 prefix and token could be set with
+//! `--base58-prefix` and `--token-decimals` with `--token-unit` keys, these
+//! replace the values from the chain spec `properties`.
+//!
 //! Title override could be used when processing an individual network, to set
 //! the title under which the network will be displayed in Vault, should the
 //! `add-specs` payload be accepted. Non-default networks, if the title override
@@ -316,6 +332,20 @@
 //!         </td>
 //!     </tr>
 //!     <tr>
+//!         <td><code>-d</code></td>
+//!         <td><code>--chain-spec</code></td>
+//!         <td><code>chain_spec_file</code></td>
+//!         <td>mandatory</td>
+//!         <td>possible</td>
+//!         <td>possible</td>
+//!         <td>- read raw chain spec file<br>
+//!             - compute genesis hash from the genesis storage<br>
+//!             - apply overrides<br>
+//!             - make payload file<br>
+//!             Note: neither RPC calls nor database are used
+//!         </td>
+//!     </tr>
+//!     <tr>
 //!         <td><code>-f</code></td>
 //!         <td><code>-a</code></td>
 //!         <td></td>
@@ -411,6 +441,10 @@
 //!
 //! `$ cargo run add-specs -d -u wss://acala.polkawallet.io --encryption sr25519 --token-decimals 12 --token-unit ACA --title Acala`
 //!
+//! Make `add-specs` update payload for a new network with no RPC endpoint:
+//!
+//! `$ cargo run add-specs -d --chain-spec chain_spec_raw.json --encryption sr25519 --base58-prefix 42 --token-decimals 12 --token-unit UNIT`
+//!
 //! ## Prepare `load_metadata` update payload
 //!
 //! `$ cargo run load-metadata [OPTIONS] <-d|-f|-k|-p|-t>`
@@ -488,6 +522,27 @@
 //! `-a` key could be used with `--pass-errors` key, to stop processing after first
 //! error.
 //!
+//! With `-a` key and setting keys making RPC calls, networks are fetched
+//! concurrently, and the fetched data is then processed one network at a time,
+//! in the address book order. Additional keys:
+//!
+//! - `--jobs` followed by the maximum number of networks fetched at the same
+//!   time, 4 by default
+//! - `--retries` followed by the number of retries for a failed fetch, 2 by
+//!   default, with the delay between the retries doubled each time
+//! - `--summary` followed by a file path, to write the JSON summary of the
+//!   processed networks, updated, unchanged, or failed with the reason
+//!
+//! The summary is also printed after processing. Without `--pass-errors` the
+//! summary includes only the networks processed before the first error.
+//!
+//! Metadata is fetched in the highest `RuntimeMetadata` version supported both
+//! by the node and by the Vault. Key `--metadata-version` followed by the
+//! version number caps it, e.g. `--metadata-version 14` makes payloads for
+//! Vaults that do not support `V15` metadata. If the network metadata of the
+//! same network version is already in the hot database in other
+//! `RuntimeMetadata` version, it is replaced with the fetched one.
+//!
 //! `load-metadata` has no overrides available. Not all setting and reference
 //! key combinations are compatible, and not all overrides are supported. Users
 //! are encouraged to comment if they need some other than current key
@@ -620,10 +675,29 @@
 //!
 //! `$ cargo run load-metadata -a`
 //!
+//! Check metadata updates for all known networks, 8 networks at a time,
+//! skipping failed ones and writing the summary for monitoring:
+//!
+//! `$ cargo run load-metadata -p -a --pass-errors --jobs 8 --summary summary.json`
+//!
 //! Make `load_metadata` update payload for a network not in the database:
 //!
 //! `$ cargo run load-metadata -d -u wss://rococo-rpc.polkadot.io`
 //!
+//! ## Watch networks for runtime upgrades
+//!
+//! `$ cargo run watch [--notify-dir <NOTIFY_DIR>]`
+//!
+//! Long-running mode: subscribes to runtime version updates for all networks
+//! in the [`ADDRESS_BOOK`](constants::ADDRESS_BOOK). When a network reports a
+//! runtime version not yet in the hot database, runs the equivalent of
+//! `$ cargo run load-metadata -k -n <network_name>`, producing
+//! `load_metadata` update payload file in dedicated
+//! [`FOLDER`](constants::FOLDER).
+//!
+//! With `--notify-dir`, a JSON notification file is additionally written into
+//! the given directory for each produced payload. See [`watch`] for details.
+//!
 //! ## Prepare `load_types` update payload
 //!
 //! `$ cargo run load-types`
@@ -733,6 +807,11 @@
 //!    - `-file` followed by the path in dedicated [`FOLDER`](constants::FOLDER)
 //!       for file with signature as raw bytes
 //!
+//! - Key `--keystore` (instead of `--verifier` and `--signature`) followed by
+//!   path to encrypted keystore file with verifier key, see [`keystore`]. The
+//!   password is read from the standard input, the signature is made with the
+//!   decrypted key.
+//!
 //! - Optional key `-name` followed by path override for export file in
 //!   dedicated [`EXPORT_FOLDER`](constants::EXPORT_FOLDER)
 //!
@@ -817,6 +896,18 @@
 //! `$ cargo run make --goal qr --crypto sr25519 --msg load-metadata --verifier Alice
 //! --payload sign_me_load_metadata_westendV9200`
 //!
+//! #### `make` with keystore verifier
+//!
+//! `$ cargo run make --goal qr --msg load-metadata --keystore <keystore_file>
+//! --payload sign_me_load_metadata_westendV9200`
+//!
+//! Password for `<keystore_file>` is typed into the standard input. Keystore
+//! file could be Polkadot-JS account export or a seed file made with
+//! `make-keystore` command.
+//!
+//! Output file is `load_metadata_westendV9200` in
+//! [`EXPORT_FOLDER`](constants::EXPORT_FOLDER).
+//!
 //! #### `make` with no signature
 //!
 //! `$ cargo run make --goal qr --crypto none --msg load-metadata --payload
@@ -848,6 +939,29 @@
 //! 0146ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47aceef7c58b5f952b6233b8aba5beb6f0000c8ca7f7cc16b7ada7cd45026fc3f3ec2289dd90dab0dfac38dfe3be843231443ddd30a3f3bbabb5cefcd2bbcef908c
 //! --msg load-metadata --payload sign_me_load_metadata_westendV9200`
 //!
+//! ## Verify complete update
+//!
+//! `$ cargo run verify <FILE> [--json]`
+//!
+//! Update in `<FILE>` could be a text file with hexadecimal update (as made
+//! with `--goal text`), a raw bytes file, or a QR code `.png` file, static or
+//! animated. Supported are `load_metadata` (including compressed),
+//! `load_types` and `add_specs` updates.
+//!
+//! Prelude is parsed, and the signature, if any, is checked same way as in
+//! the Vault. Report with update type, verifier and decoded content is printed,
+//! as text or, with `--json` key, as JSON. Error is produced if the signature
+//! is invalid or the content could not be decoded.
+//!
+//! ## Make password-protected seed file for update signing
+//!
+//! `$ cargo run make-keystore --encryption <ENCRYPTION> --output <FILE>`
+//!
+//! Secret URI (seed phrase with optional derivation) and password are read from
+//! the standard input, each on its own line. Seed file is encrypted same way as
+//! Polkadot-JS keystore files, and could be used in `make` and `sign` commands
+//! with `--keystore` key.
+//!
 //! ## Remove a single metadata entry from the `METATREE`
 //!
 //! `$ cargo run remove --name <network_name> --version <metadata_version>`
@@ -878,6 +992,47 @@
 //!   [`META_HISTORY`](constants::META_HISTORY) if there are no other address book
 //!   entries this block history entries are associated with
 //!
+//! ## Show or change metadata retention policy
+//!
+//! `$ cargo run retention <show|keep|pin|unpin|keep-blocks|clear-blocks>`
+//!
+//! Metadata retention policy determines which metadata entries are kept in
+//! [`METATREE`](constants::METATREE) when new metadata is added, see
+//! [`retention`] for details. By default, two latest metadata versions are
+//! kept for each network.
+//!
+//! - `keep <NUMBER>` sets the number of latest versions kept for each network
+//! - `pin --name <NAME> --version <VERSION>` keeps the metadata entry
+//!   regardless of its version, `unpin` reverts this
+//! - `keep-blocks --name <NAME> --from <BLOCK> --to <BLOCK>` keeps all
+//!   metadata versions used by the network within the block range, the
+//!   versions are found through RPC calls; `clear-blocks --name <NAME>`
+//!   removes all block ranges of the network
+//!
+//! ## Prune hot database
+//!
+//! `$ cargo run prune [--apply]`
+//!
+//! Reports metadata entries not covered by the metadata retention policy, and
+//! [`META_HISTORY`](constants::META_HISTORY) entries with no kept metadata
+//! entry. The entries are removed only if `--apply` key is used.
+//!
+//! ## Export hot database networks into a file
+//!
+//! `$ cargo run export --file <path.toml or path.json>`
+//!
+//! Writes all address book entries with associated network specs into a TOML
+//! or JSON file, sorted by the address book title. The file could be kept
+//! under version control and reviewed.
+//!
+//! ## Import hot database networks from a file
+//!
+//! `$ cargo run import --file <path.toml or path.json>`
+//!
+//! Replaces all entries in [`ADDRESS_BOOK`](constants::ADDRESS_BOOK) and
+//! [`SPECSTREEPREP`](constants::SPECSTREEPREP) trees with the ones from the
+//! file. Metadata and meta block history are not affected.
+//!
 //! ## Restore hot database to default state
 //!
 //! `$ cargo run restore-defaults`
@@ -901,7 +1056,7 @@
 //! `$ cargo run make-cold-release <optional path>`
 //!
 //! Removes old cold release database and generates new one with default values
-//! (unitiniated) at user-provided path or, if no valid path is given, at
+//! (unitiniated) at user-provided path or, if no path is given, at
 //! default path [`COLD_DB_NAME_RELEASE`](constants::COLD_DB_NAME_RELEASE).
 //!
 //! By default, the uninitiated cold release database contains:
@@ -918,6 +1073,14 @@
 //! started. This will be done only in Vault itself. Before initialization,
 //! the cold release database could not be used by Vault.
 //!
+//! Manifest of the generated database is written into file at path set with
+//! `--manifest` key, by default
+//! [`COLD_RELEASE_MANIFEST`](constants::COLD_RELEASE_MANIFEST). Canonical
+//! database content dump, byte-reproducible unlike the sled directory, is
+//! written into file at path set with `--dump` key, by default
+//! [`COLD_RELEASE_DUMP`](constants::COLD_RELEASE_DUMP). See [`cold_release`]
+//! for the manifest and dump content.
+//!
 //! ## Transfer metadata from hot database to cold release database
 //!
 //! `$ cargo run transfer_meta_to_cold_release <optional path>`
@@ -930,6 +1093,17 @@
 //! database, i.e. the ones having
 //! [`OrderedNetworkSpecs`](definitions::network_specs::OrderedNetworkSpecs) entry in
 //! [`SPECSTREE`](constants::SPECSTREE).
+//!
+//! Manifest and dump of the resulting database are written, same as for
+//! `make-cold-release`.
+//!
+//! ## Check cold release database against the manifest
+//!
+//! `$ cargo run verify-cold-release [--cold-db <COLD_DB_PATH>] [--manifest <MANIFEST_PATH>] [--dump <DUMP_PATH>]`
+//!
+//! Manifest is made for the database and compared with the one in the file,
+//! and the dump is checked to have the manifest content hash. Error names the
+//! first manifest section that does not match.
 
 //! ## Make derivations import QR and/or hexadecimal string file
 //!
@@ -1014,12 +1188,15 @@
 #![deny(unused)]
 #![deny(rustdoc::broken_intra_doc_links)]
 
-use constants::FPS_DEN;
+use constants::{COLD_DB_NAME_RELEASE, FPS_DEN};
 use db_handling::{
     default_cold_release, default_hot,
     helpers::{prep_types, transfer_metadata_to_cold},
 };
 
+pub mod chain_spec;
+pub mod cold_release;
+use cold_release::{verify_cold_release, write_cold_release_manifest};
 pub mod fetch_metadata;
 pub mod helpers;
 use helpers::{
@@ -1027,18 +1204,28 @@ use helpers::{
     generate_qr_code,
 };
 pub mod interpret_specs;
+pub mod keystore;
+use keystore::{make_keystore, read_secret_line};
 mod load;
+pub mod networks_file;
 use load::{gen_load_meta, meta_default_file, unwasm};
+use networks_file::{export_networks, import_networks};
 mod make_message;
 use make_message::make_message;
 pub mod parser;
 use parser::{Command, Show};
 mod remove;
 use remove::remove_info;
+pub mod retention;
+use retention::{prune, retention};
 mod show;
 use show::{check_file, show_block_history, show_metadata, show_networks, show_specs};
 mod specs;
 use specs::gen_add_specs;
+mod summary;
+pub mod verify;
+use verify::verify_update;
+pub mod watch;
 
 mod error;
 pub use error::{Error, Result};
@@ -1063,24 +1250,104 @@ pub fn full_run(command: Command) -> Result<()> {
             Ok(prep_types(&database)?.write(files_dir.join("sign_me_load_types"))?)
         }
         Command::Sign(make) | Command::Make(make) => make_message(make),
+        Command::Verify { file, json } => {
+            let report = verify_update(file)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                println!("{report}");
+            }
+            Ok(())
+        }
+        Command::MakeKeystore { encryption, output } => {
+            let suri = read_secret_line()?;
+            let password = read_secret_line()?;
+            let pair = make_keystore(&suri, &password, encryption, &output)?;
+            println!(
+                "Keystore {} made for verifier {}",
+                output.display(),
+                pair.public_hex()
+            );
+            Ok(())
+        }
         Command::Remove { r: info, db_path } => {
             let database = sled::open(db_path)?;
             remove_info(&database, info)
         }
+        Command::Retention { r, db_path } => {
+            let database = sled::open(db_path)?;
+            retention(&database, r)
+        }
+        Command::Prune { apply, db_path } => {
+            let database = sled::open(db_path)?;
+            prune(&database, apply)
+        }
+        Command::Watch {
+            db_path,
+            files_dir,
+            notify_dir,
+            metadata_version,
+        } => {
+            let database = sled::open(db_path)?;
+            watch::watch(&database, files_dir, notify_dir, metadata_version)
+        }
+        Command::Export {
+            file,
+            format,
+            db_path,
+        } => {
+            let database = sled::open(db_path)?;
+            export_networks(&database, file, format)
+        }
+        Command::Import {
+            file,
+            format,
+            db_path,
+        } => {
+            let database = sled::open(db_path)?;
+            import_networks(&database, file, format)
+        }
         Command::RestoreDefaults { db_path } => {
             let db = sled::open(db_path)?;
 
             Ok(default_hot(Some(&db))?)
         }
-        Command::MakeColdRelease { path } => {
-            let db = path.and_then(|path| sled::open(path).ok());
+        Command::MakeColdRelease {
+            path,
+            manifest,
+            dump,
+        } => {
+            let db = match path {
+                Some(path) => sled::open(path)?,
+                None => sled::open(COLD_DB_NAME_RELEASE)?,
+            };
 
-            Ok(default_cold_release(db.as_ref())?)
+            default_cold_release(Some(&db))?;
+            write_cold_release_manifest(&db, manifest, dump)
         }
-        Command::TransferMetaToColdRelease { cold_db, hot_db } => {
+        Command::TransferMetaToColdRelease {
+            cold_db,
+            hot_db,
+            manifest,
+            dump,
+        } => {
             let hot_db = sled::open(hot_db)?;
             let cold_db = sled::open(cold_db)?;
-            Ok(transfer_metadata_to_cold(&hot_db, &cold_db)?)
+            transfer_metadata_to_cold(&hot_db, &cold_db)?;
+            write_cold_release_manifest(&cold_db, manifest, dump)
+        }
+        Command::VerifyColdRelease {
+            cold_db,
+            manifest,
+            dump,
+        } => {
+            let cold_db = sled::open(cold_db)?;
+            verify_cold_release(&cold_db, &manifest, dump)?;
+            println!(
+                "Cold release database matches manifest {}",
+                manifest.display()
+            );
+            Ok(())
         }
         Command::Unwasm {
             filename,
@@ -21,12 +21,14 @@ use db_handling::helpers::{get_meta_values_by_name_version, open_tree};
 use definitions::{error::MetadataError, keyring::MetaKeyPrefix, metadata::MetaValues};
 
 use crate::error::{Changed, Error, Result};
+use crate::fetch_metadata::fetch_info_set;
 use crate::helpers::{
-    add_new_metadata, address_book_content, db_upd_metadata, error_occured, load_metadata_print,
-    meta_fetch, network_specs_from_entry, prepare_metadata, MetaFetched, MetaShortCut,
-    MetaValuesStamped, SortedMetaValues, Write,
+    add_new_metadata, address_book_content, db_upd_metadata, error_occured, interpret_fetched_info,
+    load_metadata_print, meta_fetch, network_specs_from_entry, prepare_metadata, MetaFetched,
+    MetaShortCut, MetaValuesStamped, SortedMetaValues, Write,
 };
-use crate::parser::{Content, InstructionMeta, Set};
+use crate::parser::{Content, FetchArgs, InstructionMeta, Set};
+use crate::summary::{FetchSummary, NetworkReport, Status};
 
 /// Process `load-metadata` command according to the [`InstructionMeta`]
 /// received from the command line.
@@ -76,18 +78,16 @@ pub fn gen_load_meta(instruction: InstructionMeta) -> Result<()> {
             // Make RPC calls for all networks in `ADDRESS_BOOK`, produce
             // `load_metadata` payload files.
             Content::All { pass_errors } => {
-                // Collect `AddressSpecs` for each network in `ADDRESS_BOOK`
                 let database = sled::open(&instruction.db)?;
-                let set = address_specs_set(&database)?;
-
-                // Process each entry
-                for x in set.iter() {
-                    match meta_d_a_element(x, &instruction.files_dir) {
-                        Ok(()) => (),
-                        Err(e) => error_occured(e, pass_errors)?,
-                    }
-                }
-                Ok(())
+                meta_a(
+                    &database,
+                    &Write::All,
+                    false,
+                    pass_errors,
+                    &instruction.fetch,
+                    instruction.metadata_version,
+                    &instruction.files_dir,
+                )
             }
 
             // `$ cargo run load-metadata -d -n <network_name>`
@@ -100,7 +100,12 @@ pub fn gen_load_meta(instruction: InstructionMeta) -> Result<()> {
             // be found.
             Content::Name { s: name } => {
                 let database = sled::open(&instruction.db)?;
-                meta_d_n(&database, &name, &instruction.files_dir)
+                meta_d_n(
+                    &database,
+                    &name,
+                    &instruction.files_dir,
+                    instruction.metadata_version,
+                )
             }
 
             // `$ cargo run load-metadata -d -u <url_address>`
@@ -114,7 +119,11 @@ pub fn gen_load_meta(instruction: InstructionMeta) -> Result<()> {
             // This key combination is completely agnostic and will not address
             // the database at all. If there are changes in the base58 prefix or
             // genesis hash, this will not be found here.
-            Content::Address { s: address } => meta_d_u(&address, &instruction.files_dir),
+            Content::Address { s: address } => meta_d_u(
+                &address,
+                &instruction.files_dir,
+                instruction.metadata_version,
+            ),
         },
 
         // `-k` setting key: get network data using RPC calls, update the
@@ -134,7 +143,15 @@ pub fn gen_load_meta(instruction: InstructionMeta) -> Result<()> {
                 // once: `load_metadata` payloads do not specify encryption.
                 Content::All { pass_errors } => {
                     let database = sled::open(instruction.db)?;
-                    meta_kpt_a(&database, &write, pass_errors, &instruction.files_dir)
+                    meta_a(
+                        &database,
+                        &write,
+                        true,
+                        pass_errors,
+                        &instruction.fetch,
+                        instruction.metadata_version,
+                        &instruction.files_dir,
+                    )
                 }
 
                 // `$ cargo run load-metadata -k -n <network_name>`
@@ -151,7 +168,14 @@ pub fn gen_load_meta(instruction: InstructionMeta) -> Result<()> {
                 // there, fetch and (possibly) payload export is done only once.
                 Content::Name { s: name } => {
                     let database = sled::open(instruction.db)?;
-                    meta_kpt_n(&database, &name, &write, &instruction.files_dir)
+                    meta_kpt_n(
+                        &database,
+                        &name,
+                        &write,
+                        &instruction.files_dir,
+                        instruction.metadata_version,
+                    )
+                    .map(|_| ())
                 }
 
                 // Key `-u` is for URL addresses. If network has no entry in the
@@ -175,7 +199,15 @@ pub fn gen_load_meta(instruction: InstructionMeta) -> Result<()> {
                 // One fetch for each address.
                 Content::All { pass_errors } => {
                     let database = sled::open(instruction.db)?;
-                    meta_kpt_a(&database, &write, pass_errors, &instruction.files_dir)
+                    meta_a(
+                        &database,
+                        &write,
+                        true,
+                        pass_errors,
+                        &instruction.fetch,
+                        instruction.metadata_version,
+                        &instruction.files_dir,
+                    )
                 }
 
                 // `$ cargo run load-metadata -p -n <network_name>`
@@ -190,7 +222,14 @@ pub fn gen_load_meta(instruction: InstructionMeta) -> Result<()> {
                 // One fetch only.
                 Content::Name { s: name } => {
                     let database = sled::open(instruction.db)?;
-                    meta_kpt_n(&database, &name, &write, &instruction.files_dir)
+                    meta_kpt_n(
+                        &database,
+                        &name,
+                        &write,
+                        &instruction.files_dir,
+                        instruction.metadata_version,
+                    )
+                    .map(|_| ())
                 }
 
                 // Key `-u` is for URL addresses. If network has no entry in the
@@ -214,7 +253,15 @@ pub fn gen_load_meta(instruction: InstructionMeta) -> Result<()> {
                 // One fetch and one payload print for each address.
                 Content::All { pass_errors } => {
                     let database = sled::open(instruction.db)?;
-                    meta_kpt_a(&database, &write, pass_errors, &instruction.files_dir)
+                    meta_a(
+                        &database,
+                        &write,
+                        true,
+                        pass_errors,
+                        &instruction.fetch,
+                        instruction.metadata_version,
+                        &instruction.files_dir,
+                    )
                 }
 
                 // `$ cargo run load-metadata -n <network_name>`
@@ -229,7 +276,14 @@ pub fn gen_load_meta(instruction: InstructionMeta) -> Result<()> {
                 // One fetch and one payload print only.
                 Content::Name { s: name } => {
                     let database = sled::open(instruction.db)?;
-                    meta_kpt_n(&database, &name, &write, &instruction.files_dir)
+                    meta_kpt_n(
+                        &database,
+                        &name,
+                        &write,
+                        &instruction.files_dir,
+                        instruction.metadata_version,
+                    )
+                    .map(|_| ())
                 }
 
                 // Key `-u` is for URL addresses. If network has no entry in the
@@ -291,17 +345,21 @@ where
     meta_f_a_element(database, &search_name(database, name)?, &files_dir)
 }
 
-/// `load-metadata-d -a` for individual [`AddressSpecs`] value.
+/// `load-metadata -d` for individual [`AddressSpecs`] value.
 ///
 /// - Fetch network information using RPC calls at `address` in [`AddressSpecs`]
 ///   and interpret it
 /// - Check the metadata integrity with the data on record in the database
 /// - Output raw bytes payload file
-fn meta_d_a_element<P>(set_element: &AddressSpecs, files_dir: P) -> Result<()>
+fn meta_d_a_element<P>(
+    set_element: &AddressSpecs,
+    files_dir: P,
+    max_metadata_version: Option<u32>,
+) -> Result<()>
 where
     P: AsRef<Path>,
 {
-    let meta_fetch = fetch_set_element(set_element)?;
+    let meta_fetch = fetch_set_element(set_element, max_metadata_version)?;
     load_metadata_print(&meta_fetch.cut(), files_dir)
 }
 
@@ -313,11 +371,20 @@ where
 ///   and interpret it
 /// - Check the metadata integrity with the data on record in the database
 /// - Output raw bytes payload file
-fn meta_d_n<P>(database: &sled::Db, name: &str, files_dir: P) -> Result<()>
+fn meta_d_n<P>(
+    database: &sled::Db,
+    name: &str,
+    files_dir: P,
+    max_metadata_version: Option<u32>,
+) -> Result<()>
 where
     P: AsRef<Path>,
 {
-    meta_d_a_element(&search_name(database, name)?, files_dir)
+    meta_d_a_element(
+        &search_name(database, name)?,
+        files_dir,
+        max_metadata_version,
+    )
 }
 
 /// `load-metadata-d -u <url_address>`
@@ -335,11 +402,11 @@ where
 /// error here. The Vault, if such contradicting metadata update is scanned,
 /// will produce an error, since the Vault must have matching network specs to
 /// accept the metadata.
-fn meta_d_u<P>(address: &str, files_dir: P) -> Result<()>
+fn meta_d_u<P>(address: &str, files_dir: P, max_metadata_version: Option<u32>) -> Result<()>
 where
     P: AsRef<Path>,
 {
-    let meta_fetched = meta_fetch(address)?;
+    let meta_fetched = meta_fetch(address, max_metadata_version)?;
     if meta_fetched.meta_values.warn_incomplete_extensions {
         warn(
             &meta_fetched.meta_values.name,
@@ -349,31 +416,109 @@ where
     load_metadata_print(&meta_fetched.cut(), files_dir)
 }
 
-/// `load-metadata<-k/-p/-t> -a`
+/// `load-metadata<-d/-k/-p/-t> -a`
 ///
 /// - Get all available [`AddressSpecs`] from the database
-/// - Get and sort existing metadata entries from [`METATREE`], with block
-///   data from [`META_HISTORY`](constants::META_HISTORY) if available
-/// - Process each [`AddressSpecs`] and update sorted metadata entries in the
-///   process. Input [`Write`] indicates if the payload file should be created.
-/// - Rewrite the database [`METATREE`] with updated metadata set and update
-///   [`META_HISTORY`](constants::META_HISTORY)
-fn meta_kpt_a<P>(database: &sled::Db, write: &Write, pass_errors: bool, files_dir: P) -> Result<()>
+/// - If `update_db` is set, get and sort existing metadata entries from
+///   [`METATREE`], with block data from
+///   [`META_HISTORY`](constants::META_HISTORY) if available; `-d` key does not
+///   consult the metadata in the database at all
+/// - Fetch network information for all [`AddressSpecs`] concurrently, as set
+///   by [`FetchArgs`]
+/// - Process fetched information for each [`AddressSpecs`], in order, and
+///   update sorted metadata entries in the process. Input [`Write`] indicates
+///   if the payload file should be created.
+/// - Print [`FetchSummary`] and write it into a file, if requested
+/// - If `update_db` is set, rewrite the database [`METATREE`] with updated
+///   metadata set and update [`META_HISTORY`](constants::META_HISTORY)
+///
+/// Without `pass_errors` processing stops at the first error, and the
+/// database is not updated. The summary is still written, for the networks
+/// processed before the error.
+fn meta_a<P>(
+    database: &sled::Db,
+    write: &Write,
+    update_db: bool,
+    pass_errors: bool,
+    fetch_args: &FetchArgs,
+    max_metadata_version: Option<u32>,
+    files_dir: P,
+) -> Result<()>
 where
     P: AsRef<Path>,
 {
     let set = address_specs_set(database)?;
-    let mut sorted_meta_values = prepare_metadata(database)?;
-    for x in set.iter() {
-        match meta_kpt_a_element(x, write, &mut sorted_meta_values, &files_dir) {
-            Ok(_) => (),
-            Err(e) => error_occured(e, pass_errors)?,
+    let mut sorted_meta_values = if update_db {
+        prepare_metadata(database)?
+    } else {
+        SortedMetaValues {
+            newer: Vec::new(),
+            older: Vec::new(),
+        }
+    };
+    let addresses: Vec<String> = set.iter().map(|x| x.address.to_string()).collect();
+    let fetched_set = fetch_info_set(
+        &addresses,
+        fetch_args.jobs as usize,
+        fetch_args.retries,
+        max_metadata_version,
+    )?;
+
+    let mut summary = FetchSummary::default();
+    let mut stopped = None;
+    for (set_element, (fetched, attempts)) in set.iter().zip(fetched_set.into_iter()) {
+        let processed = fetched
+            .and_then(|new_info| interpret_fetched_info(&set_element.address, new_info))
+            .and_then(|meta_fetched| check_set_element(set_element, meta_fetched))
+            .and_then(|meta_fetched| {
+                let new_version =
+                    add_fetched(&meta_fetched, write, &mut sorted_meta_values, &files_dir)?;
+                Ok((meta_fetched.meta_values.version, new_version.is_some()))
+            });
+        let mut report = NetworkReport {
+            name: set_element.name.to_string(),
+            address: set_element.address.to_string(),
+            status: Status::Failed,
+            version: None,
+            reason: None,
+            attempts,
         };
+        match processed {
+            Ok((version, got_meta_update)) => {
+                report.status = if got_meta_update {
+                    Status::Updated
+                } else {
+                    Status::Unchanged
+                };
+                report.version = Some(version);
+                summary.push(report);
+            }
+            Err(e) => {
+                report.reason = Some(e.to_string());
+                summary.push(report);
+                if let Err(e) = error_occured(e, pass_errors) {
+                    stopped = Some(e);
+                    break;
+                }
+            }
+        }
     }
-    db_upd_metadata(database, sorted_meta_values)
+
+    print!("{summary}");
+    if let Some(path) = &fetch_args.summary {
+        summary.write(path)?;
+    }
+    if let Some(e) = stopped {
+        return Err(e);
+    }
+    if update_db {
+        db_upd_metadata(database, sorted_meta_values)?;
+    }
+    Ok(())
 }
 
-/// `load-metadata<-k/-p/-t> -a` for individual [`AddressSpecs`] value.
+/// `load-metadata<-k/-p/-t> -n <network_name>` for individual
+/// [`AddressSpecs`] value.
 ///
 /// - Fetch network information using RPC calls at `address` in [`AddressSpecs`]
 ///   and interpret it
@@ -384,16 +529,35 @@ where
 /// Inputs [`AddressSpecs`] for the network currently processed, [`Write`]
 /// indicating if the `load_metadata` payload should be created, and
 /// [`SortedMetaValues`] to be updated.
+///
+/// Outputs the network version, if new metadata was fetched.
 fn meta_kpt_a_element<P>(
     set_element: &AddressSpecs,
     write: &Write,
     sorted_meta_values: &mut SortedMetaValues,
     files_dir: P,
-) -> Result<()>
+    max_metadata_version: Option<u32>,
+) -> Result<Option<u32>>
+where
+    P: AsRef<Path>,
+{
+    let meta_fetched = fetch_set_element(set_element, max_metadata_version)?;
+    add_fetched(&meta_fetched, write, sorted_meta_values, files_dir)
+}
+
+/// Insert checked [`MetaFetched`] into [`SortedMetaValues`] and output raw
+/// bytes payload file, if requested by input [`Write`].
+///
+/// Outputs the network version, if new metadata was fetched.
+fn add_fetched<P>(
+    meta_fetched: &MetaFetched,
+    write: &Write,
+    sorted_meta_values: &mut SortedMetaValues,
+    files_dir: P,
+) -> Result<Option<u32>>
 where
     P: AsRef<Path>,
 {
-    let meta_fetched = fetch_set_element(set_element)?;
     let got_meta_update = add_new_metadata(&meta_fetched.stamped(), sorted_meta_values)?;
     match write {
         Write::All => load_metadata_print(&meta_fetched.cut(), files_dir)?,
@@ -410,14 +574,15 @@ where
             meta_fetched.meta_values.name,
             meta_fetched.meta_values.version,
             hex::encode(meta_fetched.block_hash)
-        )
+        );
+        Ok(Some(meta_fetched.meta_values.version))
     } else {
         println!(
             "Fetched previously known metadata {}{}",
             meta_fetched.meta_values.name, meta_fetched.meta_values.version,
-        )
+        );
+        Ok(None)
     }
-    Ok(())
 }
 
 /// `load-metadata<-k/-p/-t> -n <network_name>`
@@ -436,18 +601,28 @@ where
 ///
 /// Inputs user-entered network name and [`Write`] indicating if the
 /// `load_metadata` payload should be created.
-fn meta_kpt_n<P>(database: &sled::Db, name: &str, write: &Write, files_dir: P) -> Result<()>
+///
+/// Outputs the network version, if new metadata was fetched.
+pub(crate) fn meta_kpt_n<P>(
+    database: &sled::Db,
+    name: &str,
+    write: &Write,
+    files_dir: P,
+    max_metadata_version: Option<u32>,
+) -> Result<Option<u32>>
 where
     P: AsRef<Path>,
 {
     let mut sorted_meta_values = prepare_metadata(database)?;
-    meta_kpt_a_element(
+    let new_version = meta_kpt_a_element(
         &search_name(database, name)?,
         write,
         &mut sorted_meta_values,
         files_dir,
+        max_metadata_version,
     )?;
-    db_upd_metadata(database, sorted_meta_values)
+    db_upd_metadata(database, sorted_meta_values)?;
+    Ok(new_version)
 }
 
 /// Network information from [`ADDRESS_BOOK`](constants::ADDRESS_BOOK) and
@@ -531,8 +706,19 @@ fn search_name(database: &sled::Db, name: &str) -> Result<AddressSpecs> {
 ///
 /// Outputs [`MetaFetched`], the data sufficient to produce `load_metadata`
 /// payload and update the database.
-fn fetch_set_element(set_element: &AddressSpecs) -> Result<MetaFetched> {
-    let meta_fetched = meta_fetch(&set_element.address)?;
+fn fetch_set_element(
+    set_element: &AddressSpecs,
+    max_metadata_version: Option<u32>,
+) -> Result<MetaFetched> {
+    check_set_element(
+        set_element,
+        meta_fetch(&set_element.address, max_metadata_version)?,
+    )
+}
+
+/// Check the information received through RPC calls for given
+/// [`AddressSpecs`], see [`fetch_set_element`].
+fn check_set_element(set_element: &AddressSpecs, meta_fetched: MetaFetched) -> Result<MetaFetched> {
     if meta_fetched.meta_values.name != set_element.name {
         return Err(Error::ValuesChanged {
             url: set_element.address.to_string(),
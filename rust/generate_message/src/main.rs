@@ -5,5 +5,6 @@ fn main() {
     let args = Cli::parse();
     if let Err(e) = full_run(args.command) {
         eprintln!("An error occurred: {e}");
+        std::process::exit(1);
     }
 }
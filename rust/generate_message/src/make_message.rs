@@ -1,14 +1,15 @@
 //! Complete generating update QR code or test string, signed or unsigned
 use constants::ALICE_SEED_PHRASE;
 use definitions::{
-    crypto::{Encryption, SufficientCrypto},
+    crypto::Encryption,
     metadata::MetaValues,
-    qr_transfers::{ContentAddSpecs, ContentLoadMeta, ContentLoadTypes},
+    payload::{Update, UpdateContent},
+    qr_transfers::{ContentAddSpecs, ContentLoadMeta, ContentLoadMetaCompressed, ContentLoadTypes},
 };
 use qrcode_rtx::make_pretty_qr;
-use sp_core::{ecdsa, ed25519, sr25519, Pair};
 
 use crate::error::{Error, Result};
+use crate::keystore::SigningPair;
 use crate::parser::{Crypto, Goal, Make, Msg};
 
 /// Alice seed phrase and derivation `//Alice`, for making updates signed with
@@ -25,164 +26,107 @@ fn alice_secret() -> String {
 /// hex-encoded bytes.
 pub fn make_message(make: Make) -> Result<()> {
     let vec = make.payload()?;
+    if make.compress.is_some() && !matches!(make.msg, Msg::LoadMetadata) {
+        return Err(Error::NotSupported);
+    }
     // check message content for consistency
-    //
-    // note that bytes signed and bytes added into concatenated update are not
-    // necessarily the same
-    let (message_to_verify, message_to_transfer, name_stub, msg_type_code) = match make.msg {
+    let (content, name_stub) = match make.msg {
         Msg::LoadTypes => {
             let content = ContentLoadTypes::from_slice(&vec);
             content.types()?;
             (
-                content.to_sign(),
-                content.to_transfer(),
+                UpdateContent::LoadTypes(content),
                 String::from("load_types"),
-                "81",
             )
         }
         Msg::LoadMetadata => {
             let content = ContentLoadMeta::from_slice(&vec);
             let meta = content.meta()?;
             let meta_values = MetaValues::from_slice_metadata(&meta)?;
-            (
-                content.to_sign(),
-                content.to_transfer(),
-                format!("load_metadata_{}V{}", meta_values.name, meta_values.version),
-                "80",
-            )
+            let name_stub = format!("load_metadata_{}V{}", meta_values.name, meta_values.version);
+            match make.compress {
+                // signature is made for uncompressed content
+                Some(compression) => (
+                    UpdateContent::LoadMetadataCompressed(ContentLoadMetaCompressed::compress(
+                        &content,
+                        compression,
+                    )?),
+                    format!("{name_stub}_{}", compression.show()),
+                ),
+                None => (UpdateContent::LoadMetadata(content), name_stub),
+            }
         }
         Msg::AddSpecs => {
             let content = ContentAddSpecs::from_slice(&vec);
             let network_specs = content.specs()?;
             (
-                content.to_sign(),
-                content.to_transfer(),
+                UpdateContent::AddSpecs(content),
                 format!(
                     "add_specs_{}-{}",
                     network_specs.name,
                     network_specs.encryption.show()
                 ),
-                "c1",
             )
         }
     };
 
+    // note that bytes signed and bytes added into concatenated update are not
+    // necessarily the same
+    let message_to_verify = content.to_sign()?;
+
     // adding signature (if any) and finalize the message and filename
-    let (complete_message, complete_name) = match make.crypto()? {
+    let (update, complete_name) = match make.crypto()? {
         // verifier is Alice, make signature here
-        Crypto::Alice { e: encryption } => match encryption {
-            Encryption::Ed25519 => {
-                let crypto_type_code = "00";
-                let prelude = format!("53{crypto_type_code}{msg_type_code}");
-                let ed25519_pair =
-                    ed25519::Pair::from_string(&alice_secret(), None).expect("known Alice secret");
-                let signature = ed25519_pair.sign(&message_to_verify[..]).0.to_vec();
-                let complete_message = [
-                    hex::decode(prelude).expect("known value"),
-                    ed25519_pair.public().to_vec(),
-                    message_to_transfer,
-                    signature,
-                ]
-                .concat();
-                (complete_message, format!("{name_stub}_Alice-ed25519"))
-            }
-            Encryption::Sr25519 => {
-                let crypto_type_code = "01";
-                let prelude = format!("53{crypto_type_code}{msg_type_code}");
-                let sr25519_pair =
-                    sr25519::Pair::from_string(&alice_secret(), None).expect("known Alice secret");
-                let signature = sr25519_pair.sign(&message_to_verify[..]).0.to_vec();
-                let complete_message = [
-                    hex::decode(prelude).expect("known value"),
-                    sr25519_pair.public().to_vec(),
-                    message_to_transfer,
-                    signature,
-                ]
-                .concat();
-                (complete_message, format!("{name_stub}_Alice-sr25519"))
-            }
-            Encryption::Ecdsa | Encryption::Ethereum => {
-                let crypto_type_code = "02";
-                let prelude = format!("53{crypto_type_code}{msg_type_code}");
-                let ecdsa_pair =
-                    ecdsa::Pair::from_string(&alice_secret(), None).expect("known Alice secret");
-                let signature = ecdsa_pair.sign(&message_to_verify[..]).0.to_vec();
-                let complete_message = [
-                    hex::decode(prelude).expect("known value"),
-                    ecdsa_pair.public().0.to_vec(),
-                    message_to_transfer,
-                    signature,
-                ]
-                .concat();
-                (complete_message, format!("{name_stub}_Alice-ecdsa"))
-            }
-        },
+        Crypto::Alice { e: encryption } => {
+            let signature = SigningPair::from_suri(&alice_secret(), encryption)
+                .expect("known Alice secret")
+                .sign(&message_to_verify);
+            let name_tail = match encryption {
+                Encryption::Ed25519 => "Alice-ed25519",
+                Encryption::Sr25519 => "Alice-sr25519",
+                Encryption::Ecdsa | Encryption::Ethereum => "Alice-ecdsa",
+            };
+            (
+                Update {
+                    content,
+                    signature: Some(signature),
+                },
+                format!("{name_stub}_{name_tail}"),
+            )
+        }
 
         // no verifier
-        Crypto::None => {
-            let crypto_type_code = "ff";
-            let prelude = format!("53{crypto_type_code}{msg_type_code}");
-            let complete_message = [
-                hex::decode(prelude).expect("known value"),
-                message_to_transfer,
-            ]
-            .concat();
-            (complete_message, format!("{name_stub}_unverified"))
-        }
+        Crypto::None => (
+            Update {
+                content,
+                signature: None,
+            },
+            format!("{name_stub}_unverified"),
+        ),
+
+        // verifier key from keystore, make signature here
+        Crypto::Keystore { pair } => (
+            Update {
+                content,
+                signature: Some(pair.sign(&message_to_verify)),
+            },
+            name_stub,
+        ),
 
         // real verifier with real signature: check that signature is valid
         // first
-        Crypto::Sufficient { s } => match s {
-            SufficientCrypto::Ed25519 { public, signature } => {
-                let crypto_type_code = "00";
-                let prelude = format!("53{crypto_type_code}{msg_type_code}");
-                if ed25519::Pair::verify(&signature, &message_to_verify, &public) {
-                    let complete_message = [
-                        hex::decode(prelude).expect("known value"),
-                        public.to_vec(),
-                        message_to_transfer,
-                        signature.0.to_vec(),
-                    ]
-                    .concat();
-                    (complete_message, name_stub)
-                } else {
-                    return Err(Error::BadSignature);
-                }
-            }
-            SufficientCrypto::Sr25519 { public, signature } => {
-                let crypto_type_code = "01";
-                let prelude = format!("53{crypto_type_code}{msg_type_code}");
-                if sr25519::Pair::verify(&signature, &message_to_verify, &public) {
-                    let complete_message = [
-                        hex::decode(prelude).expect("known value"),
-                        public.to_vec(),
-                        message_to_transfer,
-                        signature.0.to_vec(),
-                    ]
-                    .concat();
-                    (complete_message, name_stub)
-                } else {
-                    return Err(Error::BadSignature);
-                }
+        Crypto::Sufficient { s } => {
+            let update = Update {
+                content,
+                signature: Some(s),
+            };
+            if !update.verify_message(&message_to_verify) {
+                return Err(Error::BadSignature);
             }
-            SufficientCrypto::Ecdsa { public, signature } => {
-                let crypto_type_code = "02";
-                let prelude = format!("53{crypto_type_code}{msg_type_code}");
-                if ecdsa::Pair::verify(&signature, &message_to_verify, &public) {
-                    let complete_message = [
-                        hex::decode(prelude).expect("known value"),
-                        public.0.to_vec(),
-                        message_to_transfer,
-                        signature.0.to_vec(),
-                    ]
-                    .concat();
-                    (complete_message, name_stub)
-                } else {
-                    return Err(Error::BadSignature);
-                }
-            }
-        },
+            (update, name_stub)
+        }
     };
+    let complete_message = update.to_bytes();
 
     let mut output_name = make
         .name
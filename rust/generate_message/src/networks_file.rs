@@ -0,0 +1,226 @@
+//! Export and import of the hot database networks as TOML or JSON file
+//!
+//! Networks in the hot database are defined by matching entries in
+//! [`ADDRESS_BOOK`] and [`SPECSTREEPREP`] trees. These could be exported into
+//! a human-readable file, kept under version control and reviewed, and then
+//! imported to rebuild the hot database networks deterministically.
+//!
+//! ## Export networks
+//!
+//! `$ cargo run export --file networks.toml`
+//!
+//! All address book entries with associated network specs are written into
+//! the file, sorted by the address book title.
+//!
+//! ## Import networks
+//!
+//! `$ cargo run import --file networks.toml`
+//!
+//! All address book entries and network specs in the hot database are
+//! replaced with the ones from the file. Metadata entries in
+//! [`METATREE`](constants::METATREE) and block history entries in
+//! [`META_HISTORY`](constants::META_HISTORY) are not affected.
+//!
+//! File format is selected by the file extension, `.toml` or `.json`, unless
+//! set explicitly with `--format` key.
+use serde::{Deserialize, Serialize};
+use sp_core::H256;
+use std::path::Path;
+
+use constants::{ADDRESS_BOOK, SPECSTREEPREP};
+use db_handling::{db_transactions::TrDbHot, helpers::make_batch_clear_tree};
+use definitions::{
+    keyring::{AddressBookKey, NetworkSpecsKey},
+    metadata::AddressBookEntry,
+    network_specs::NetworkSpecs,
+};
+use parity_scale_codec::Encode;
+
+use crate::error::{Error, Result};
+use crate::helpers::{address_book_content, network_specs_from_entry};
+use crate::parser::{encryption_from_args, FileFormat};
+
+/// Content of the networks file.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct NetworksFile {
+    /// Networks, sorted by address book title
+    #[serde(default)]
+    pub network: Vec<NetworkRecord>,
+}
+
+/// Address book entry and network specs for a single network and encryption.
+///
+/// Address book title is not recorded, it is always
+/// `<network_name>-<network_encryption>`.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct NetworkRecord {
+    /// Network name, as it appears in the metadata
+    pub name: String,
+
+    /// Encryption, `ed25519`, `sr25519`, `ecdsa` or `ethereum`
+    pub encryption: String,
+
+    /// Hexadecimal network genesis hash
+    pub genesis_hash: String,
+
+    /// URL address for RPC calls
+    pub address: String,
+
+    /// Address book entry is the default one
+    #[serde(default)]
+    pub def: bool,
+
+    /// Network-specific prefix for address representation
+    pub base58prefix: u16,
+
+    /// Order of magnitude, by which the token unit exceeds the balance integer
+    /// unit
+    pub decimals: u8,
+
+    /// Unit of the network balance
+    pub unit: String,
+
+    /// Network title, as it appears in Vault menus
+    pub title: String,
+
+    /// Network logo
+    pub logo: String,
+
+    /// Default derivation path for addresses in this network
+    pub path_id: String,
+
+    /// Network-associated color
+    pub color: String,
+
+    /// Network-associated secondary color
+    pub secondary_color: String,
+}
+
+impl NetworkRecord {
+    /// Make [`NetworkRecord`] from the hot database entries.
+    fn new(address_book_entry: &AddressBookEntry, network_specs: &NetworkSpecs) -> Self {
+        Self {
+            name: network_specs.name.to_string(),
+            encryption: network_specs.encryption.show(),
+            genesis_hash: hex::encode(network_specs.genesis_hash),
+            address: address_book_entry.address.to_string(),
+            def: address_book_entry.def,
+            base58prefix: network_specs.base58prefix,
+            decimals: network_specs.decimals,
+            unit: network_specs.unit.to_string(),
+            title: network_specs.title.to_string(),
+            logo: network_specs.logo.to_string(),
+            path_id: network_specs.path_id.to_string(),
+            color: network_specs.color.to_string(),
+            secondary_color: network_specs.secondary_color.to_string(),
+        }
+    }
+
+    /// Address book title and hot database entries for the record.
+    fn entries(&self) -> Result<(String, AddressBookEntry, NetworkSpecs)> {
+        let encryption =
+            encryption_from_args(&self.encryption).map_err(|_| Error::NetworksFileEncryption {
+                name: self.name.to_string(),
+                encryption: self.encryption.to_string(),
+            })?;
+        let genesis_hash = hex::decode(self.genesis_hash.trim_start_matches("0x"))
+            .ok()
+            .filter(|a| a.len() == H256::len_bytes())
+            .map(|a| H256::from_slice(&a))
+            .ok_or(Error::UnexpectedGenesisHashFormat)?;
+        let title = format!("{}-{}", self.name, encryption.show());
+        let address_book_entry = AddressBookEntry {
+            name: self.name.to_string(),
+            genesis_hash,
+            address: self.address.to_string(),
+            encryption,
+            def: self.def,
+        };
+        let network_specs = NetworkSpecs {
+            base58prefix: self.base58prefix,
+            color: self.color.to_string(),
+            decimals: self.decimals,
+            encryption,
+            genesis_hash,
+            logo: self.logo.to_string(),
+            name: self.name.to_string(),
+            path_id: self.path_id.to_string(),
+            secondary_color: self.secondary_color.to_string(),
+            title: self.title.to_string(),
+            unit: self.unit.to_string(),
+        };
+        Ok((title, address_book_entry, network_specs))
+    }
+}
+
+/// Get file format from the command line or from the file extension.
+fn file_format(path: &Path, format: Option<FileFormat>) -> Result<FileFormat> {
+    if let Some(format) = format {
+        return Ok(format);
+    }
+    match path.extension().and_then(|a| a.to_str()) {
+        Some("toml") => Ok(FileFormat::Toml),
+        Some("json") => Ok(FileFormat::Json),
+        _ => Err(Error::NetworksFileFormat(path.to_path_buf())),
+    }
+}
+
+/// Collect all networks from the hot database.
+pub fn networks_file_content(database: &sled::Db) -> Result<NetworksFile> {
+    let mut address_book = address_book_content(database)?;
+    address_book.sort_by(|(title1, _), (title2, _)| title1.cmp(title2));
+    let mut network = Vec::new();
+    for (_, address_book_entry) in address_book.iter() {
+        let network_specs = network_specs_from_entry(database, address_book_entry)?;
+        network.push(NetworkRecord::new(address_book_entry, &network_specs));
+    }
+    Ok(NetworksFile { network })
+}
+
+/// Write all hot database networks into the file.
+pub fn export_networks<P>(database: &sled::Db, path: P, format: Option<FileFormat>) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    let content = networks_file_content(database)?;
+    let text = match file_format(path.as_ref(), format)? {
+        FileFormat::Toml => toml::to_string(&content)?,
+        FileFormat::Json => serde_json::to_string_pretty(&content)? + "\n",
+    };
+    std::fs::write(path, text)?;
+    Ok(())
+}
+
+/// Replace all hot database networks with the ones from the file.
+pub fn import_networks<P>(database: &sled::Db, path: P, format: Option<FileFormat>) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    let text = std::fs::read_to_string(path.as_ref())?;
+    let content: NetworksFile = match file_format(path.as_ref(), format)? {
+        FileFormat::Toml => toml::from_str(&text)?,
+        FileFormat::Json => serde_json::from_str(&text)?,
+    };
+    let mut address_book_batch = make_batch_clear_tree(database, ADDRESS_BOOK)?;
+    let mut network_specs_prep_batch = make_batch_clear_tree(database, SPECSTREEPREP)?;
+    let mut known_keys = Vec::new();
+    for record in content.network.iter() {
+        let (title, address_book_entry, network_specs) = record.entries()?;
+        let address_book_key = AddressBookKey::from_title(&title).key();
+        let network_specs_key =
+            NetworkSpecsKey::from_parts(&network_specs.genesis_hash, &network_specs.encryption)
+                .key();
+        if known_keys.contains(&address_book_key) || known_keys.contains(&network_specs_key) {
+            return Err(Error::NetworksFileDuplicate { title });
+        }
+        address_book_batch.insert(address_book_key.to_vec(), address_book_entry.encode());
+        network_specs_prep_batch.insert(network_specs_key.to_vec(), network_specs.encode());
+        known_keys.push(address_book_key);
+        known_keys.push(network_specs_key);
+    }
+    TrDbHot::new()
+        .set_address_book(address_book_batch)
+        .set_network_specs_prep(network_specs_prep_batch)
+        .apply(database)?;
+    Ok(())
+}
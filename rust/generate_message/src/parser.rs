@@ -1,12 +1,17 @@
 //! Command line parser for the client
-use constants::{COLD_DB_NAME_RELEASE, EXPORT_FOLDER, FOLDER, HOT_DB_NAME};
+use constants::{
+    COLD_DB_NAME_RELEASE, COLD_RELEASE_DUMP, COLD_RELEASE_MANIFEST, EXPORT_FOLDER, FOLDER,
+    HOT_DB_NAME,
+};
 use definitions::{
     crypto::{Encryption, SufficientCrypto},
     helpers::unhex,
+    qr_transfers::Compression,
 };
 use sp_core::{ecdsa, ed25519, sr25519};
 use std::{convert::TryInto, path::PathBuf};
 
+use crate::keystore::{read_secret_line, SigningPair};
 use crate::{error::Result, Error};
 use parity_scale_codec::Decode;
 
@@ -68,12 +73,64 @@ pub enum Command {
         files_dir: PathBuf,
     },
 
+    /// Watch networks for runtime upgrades and prepare load-metadata updates
+    ///
+    /// Runs until interrupted. For each network in
+    /// [`ADDRESS_BOOK`](constants::ADDRESS_BOOK) runtime version updates are
+    /// subscribed to, and new metadata is fetched as with `load-metadata -k`.
+    Watch {
+        /// Path to the hot database
+        #[arg(long = "hot-db-path", value_name = "HOT_DB_PATH", default_value = HOT_DB_NAME)]
+        db_path: PathBuf,
+
+        /// Folder to save payloads ready for signing
+        #[arg(long, value_name = "FOLDER_PATH", default_value = FOLDER)]
+        files_dir: PathBuf,
+
+        /// Folder to write JSON notifications about new payloads into
+        #[arg(long, value_name = "NOTIFY_DIR")]
+        notify_dir: Option<PathBuf>,
+
+        /// Highest `RuntimeMetadata` version to fetch, e.g. `14` for Vaults
+        /// that do not support `V15` metadata
+        #[arg(long, value_name = "VERSION", value_parser = clap::value_parser!(u32).range(14..))]
+        metadata_version: Option<u32>,
+    },
+
     /// Complete update generation according
     Make(Make),
 
     /// Sign
     Sign(Make),
 
+    /// Check complete update and display its verifier and content
+    ///
+    /// Update could be a text file with hexadecimal update, as produced with
+    /// `--goal text`, a raw bytes file, or a `.png` QR code, static or
+    /// animated. Signature is checked same way as in the Vault.
+    Verify {
+        /// Path to the update file
+        file: PathBuf,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Make password-protected seed file for signing with `--keystore`
+    ///
+    /// Secret URI and password are read from the standard input, each on its
+    /// own line. The secret URI is stored only encrypted.
+    MakeKeystore {
+        /// Encryption of the verifier key
+        #[arg(long, value_parser = encryption_from_args)]
+        encryption: Encryption,
+
+        /// Path to the keystore file to write
+        #[arg(long, value_name = "FILE")]
+        output: PathBuf,
+    },
+
     /// Remove all data associated with a network
     ///
     /// This will remove:
@@ -98,6 +155,79 @@ pub enum Command {
         db_path: PathBuf,
     },
 
+    /// Show or change metadata retention policy of the hot database
+    ///
+    /// Policy determines which metadata entries in
+    /// [`METATREE`](constants::METATREE) are kept when new metadata is added,
+    /// see [`retention`](crate::retention).
+    Retention {
+        #[clap(subcommand)]
+        r: Retention,
+
+        /// Path to the hot database
+        #[arg(long="hot-db-path", global=true, value_name="HOT_DB_PATH", default_value = HOT_DB_NAME)]
+        db_path: PathBuf,
+    },
+
+    /// Remove hot database entries not covered by the metadata retention
+    /// policy
+    ///
+    /// Entries to be removed from [`METATREE`](constants::METATREE) and
+    /// [`META_HISTORY`](constants::META_HISTORY) are always printed, and are
+    /// removed only with `--apply` key.
+    Prune {
+        /// Remove the entries, not only report them
+        #[arg(long)]
+        apply: bool,
+
+        /// Path to the hot database
+        #[arg(long = "hot-db-path", value_name = "HOT_DB_PATH", default_value = HOT_DB_NAME)]
+        db_path: PathBuf,
+    },
+
+    /// Export hot database networks into TOML or JSON file
+    ///
+    /// All address book entries
+    /// [`AddressBookEntry`](definitions::metadata::AddressBookEntry) from
+    /// [`ADDRESS_BOOK`](constants::ADDRESS_BOOK) tree are written together with
+    /// associated network specs
+    /// [`NetworkSpecs`](definitions::network_specs::NetworkSpecs) from
+    /// [`SPECSTREEPREP`](constants::SPECSTREEPREP) tree, sorted by the address
+    /// book title.
+    Export {
+        /// File to write the networks into, `.toml` or `.json`
+        #[arg(long, value_name = "FILE")]
+        file: PathBuf,
+
+        /// File format, if it should not be determined from the file extension
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<FileFormat>,
+
+        /// Path to the hot database
+        #[arg(long = "hot-db-path", value_name = "HOT_DB_PATH", default_value = HOT_DB_NAME)]
+        db_path: PathBuf,
+    },
+
+    /// Import hot database networks from TOML or JSON file
+    ///
+    /// All entries in [`ADDRESS_BOOK`](constants::ADDRESS_BOOK) and
+    /// [`SPECSTREEPREP`](constants::SPECSTREEPREP) trees are replaced with
+    /// the ones from the file. Metadata and meta block history entries are not
+    /// affected.
+    Import {
+        /// File to read the networks from, `.toml` or `.json`
+        #[arg(long, value_name = "FILE")]
+        file: PathBuf,
+
+        /// File format, if it should not be determined from the file extension
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<FileFormat>,
+
+        /// Path to the hot database
+        #[arg(long = "hot-db-path", value_name = "HOT_DB_PATH", default_value = HOT_DB_NAME)]
+        db_path: PathBuf,
+    },
+
     /// Restore hot database to default state
     ///
     /// Removes old hot database and generates new one with default values at
@@ -122,7 +252,7 @@ pub enum Command {
     /// Generate release cold database at optionally provided path
     ///
     /// Removes old cold release database and generates new one with default values
-    /// (uninitiated) at user-provided path or, if no valid path is given, at
+    /// (uninitiated) at user-provided path or, if no path is given, at
     /// default path [`COLD_DB_NAME_RELEASE`](constants::COLD_DB_NAME_RELEASE).
     ///
     /// By default, the uninitiated cold release database contains:
@@ -138,9 +268,21 @@ pub enum Command {
     /// Note that the general verifier is not specified and history is not
     /// started. This will be done only in Vault itself. Before initialization,
     /// the cold release database could not be used by Vault.
+    ///
+    /// Manifest and canonical content dump of the generated database are
+    /// written, see
+    /// [`ColdReleaseManifest`](crate::cold_release::ColdReleaseManifest).
     MakeColdRelease {
         /// Path to release db
         path: Option<PathBuf>,
+
+        /// Path to the manifest file
+        #[arg(long, value_name = "MANIFEST_PATH", default_value = COLD_RELEASE_MANIFEST)]
+        manifest: PathBuf,
+
+        /// Path to the canonical database content dump file
+        #[arg(long, value_name = "DUMP_PATH", default_value = COLD_RELEASE_DUMP)]
+        dump: PathBuf,
     },
 
     /// Transfer metadata from hot database to release cold database
@@ -153,6 +295,10 @@ pub enum Command {
     /// database, i.e. the ones having
     /// [`OrderedNetworkSpecs`](definitions::network_specs::OrderedNetworkSpecs) entry in
     /// [`SPECSTREE`](constants::SPECSTREE).
+    ///
+    /// Manifest and canonical content dump of the resulting database are
+    /// written, see
+    /// [`ColdReleaseManifest`](crate::cold_release::ColdReleaseManifest).
     #[command(name = "transfer-meta")]
     TransferMetaToColdRelease {
         /// Path to release db
@@ -162,6 +308,34 @@ pub enum Command {
         /// Path to hot db
         #[arg(long, value_name = "HOT_DB_PATH", default_value = HOT_DB_NAME)]
         hot_db: PathBuf,
+
+        /// Path to the manifest file
+        #[arg(long, value_name = "MANIFEST_PATH", default_value = COLD_RELEASE_MANIFEST)]
+        manifest: PathBuf,
+
+        /// Path to the canonical database content dump file
+        #[arg(long, value_name = "DUMP_PATH", default_value = COLD_RELEASE_DUMP)]
+        dump: PathBuf,
+    },
+
+    /// Check release cold database and its content dump against the manifest
+    ///
+    /// Manifest and dump are written by `make-cold-release` and
+    /// `transfer-meta` commands. Reports the first manifest section that does
+    /// not match.
+    #[command(name = "verify-cold-release")]
+    VerifyColdRelease {
+        /// Path to release db
+        #[arg(long, value_name = "COLD_DB_PATH", default_value = COLD_DB_NAME_RELEASE)]
+        cold_db: PathBuf,
+
+        /// Path to the manifest file
+        #[arg(long, value_name = "MANIFEST_PATH", default_value = COLD_RELEASE_MANIFEST)]
+        manifest: PathBuf,
+
+        /// Path to the canonical database content dump file
+        #[arg(long, value_name = "DUMP_PATH", default_value = COLD_RELEASE_DUMP)]
+        dump: PathBuf,
     },
 
     /// Prepare payload for `load_metadata` update from `.wasm` file
@@ -344,6 +518,67 @@ pub enum Show {
     BlockHistory,
 }
 
+/// Metadata retention policy commands.
+#[derive(Clone, Debug, Subcommand)]
+pub enum Retention {
+    /// Show current metadata retention policy
+    Show,
+
+    /// Set number of latest metadata versions kept for each network
+    Keep {
+        /// Number of versions, at least 1
+        #[arg(value_name = "NUMBER", value_parser = clap::value_parser!(u32).range(1..))]
+        n: u32,
+    },
+
+    /// Keep metadata entry regardless of its version
+    Pin {
+        /// Network name
+        #[arg(long)]
+        name: String,
+
+        /// Network metadata version
+        #[arg(long)]
+        version: u32,
+    },
+
+    /// Stop keeping pinned metadata entry
+    Unpin {
+        /// Network name
+        #[arg(long)]
+        name: String,
+
+        /// Network metadata version
+        #[arg(long)]
+        version: u32,
+    },
+
+    /// Keep metadata versions used by the network within block range
+    ///
+    /// Metadata versions at the first and the last blocks are fetched through
+    /// RPC calls at the network address from the address book.
+    KeepBlocks {
+        /// Network name
+        #[arg(long)]
+        name: String,
+
+        /// First block number of the range
+        #[arg(long)]
+        from: u32,
+
+        /// Last block number of the range
+        #[arg(long)]
+        to: u32,
+    },
+
+    /// Stop keeping metadata versions for all block ranges of the network
+    ClearBlocks {
+        /// Network name
+        #[arg(long)]
+        name: String,
+    },
+}
+
 /// Command details for `load-metadata`.
 #[derive(clap::Args, Debug)]
 pub struct InstructionMeta {
@@ -362,6 +597,31 @@ pub struct InstructionMeta {
     /// Folder to save payloads ready for signing
     #[arg(long, default_value = FOLDER)]
     pub files_dir: PathBuf,
+
+    /// Fetch settings, relevant only for `-a` reference key
+    #[clap(flatten)]
+    pub fetch: FetchArgs,
+
+    /// Highest `RuntimeMetadata` version to fetch, e.g. `14` for Vaults that
+    /// do not support `V15` metadata
+    #[arg(long, value_name = "VERSION", value_parser = clap::value_parser!(u32).range(14..))]
+    pub metadata_version: Option<u32>,
+}
+
+/// Fetch settings for `load-metadata` with `-a` reference key.
+#[derive(clap::Args, Debug)]
+pub struct FetchArgs {
+    /// Maximum number of networks fetched at the same time
+    #[arg(long, value_name = "NUMBER", default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+    pub jobs: u32,
+
+    /// Number of retries for a failed fetch
+    #[arg(long, value_name = "NUMBER", default_value_t = 2)]
+    pub retries: u32,
+
+    /// Write JSON summary of processed networks into a file
+    #[arg(long, value_name = "FILE", requires = "all")]
+    pub summary: Option<PathBuf>,
 }
 
 impl From<SetFlags> for Set {
@@ -381,7 +641,7 @@ impl From<SetFlags> for Set {
 #[derive(clap::Args, Debug)]
 #[command(group(clap::ArgGroup::new("referencekey")
                 .required(true)
-                .args(&["all", "name", "address"])
+                .args(&["all", "name", "address", "chain_spec"])
 ))]
 pub struct InstructionSpecs {
     #[clap(flatten)]
@@ -394,6 +654,15 @@ pub struct InstructionSpecs {
     #[clap(flatten)]
     pub content: ContentArgs,
 
+    /// Make network specs from a raw chain spec file instead of RPC calls
+    #[arg(long, value_name = "FILE")]
+    pub chain_spec: Option<PathBuf>,
+
+    /// Base58 prefix of the network, replaces the one in chain spec
+    /// properties
+    #[arg(long, requires = "chain_spec")]
+    pub base58_prefix: Option<u16>,
+
     /// Path to the hot database
     #[arg(long = "hot-db-path", value_name = "HOT_DB_PATH", default_value = HOT_DB_NAME)]
     pub db: PathBuf,
@@ -551,6 +820,10 @@ pub struct Make {
     #[arg(long, value_name = "crypto", value_parser = encryption_from_args)]
     pub crypto: Option<Encryption>,
 
+    /// compress the payload content, only for `load-metadata` payloads
+    #[arg(long, value_name = "compression", value_parser = compression_from_args)]
+    pub compress: Option<Compression>,
+
     /// output name override
     #[arg(long, value_name = "name")]
     pub name: Option<PathBuf>,
@@ -584,6 +857,11 @@ impl Make {
             let s = <SufficientCrypto>::decode(&mut &s[..])?;
             return Ok(Crypto::Sufficient { s });
         }
+        if let Some(path) = &self.verifier.keystore {
+            let password = read_secret_line()?;
+            let pair = SigningPair::from_keystore(path, &password)?;
+            return Ok(Crypto::Keystore { pair });
+        }
         let verifier_public_key = match (
             self.verifier.verifier_alice,
             &self.verifier.verifier_hex,
@@ -650,7 +928,7 @@ impl std::fmt::Display for Goal {
 /// Verifier-to-be, for `make` and `sign` commands.
 #[derive(clap::Args, Debug, Clone)]
 #[command(group(clap::ArgGroup::new("verifier")
-                .args(&["verifier_alice", "verifier_hex", "verifier_file"])
+                .args(&["verifier_alice", "verifier_hex", "verifier_file", "keystore"])
         ))]
 pub struct Verifier {
     /// Use Alice key with a specified encryption scheme
@@ -664,6 +942,11 @@ pub struct Verifier {
     /// Read Verifier from a file
     #[arg(long, value_name = "FILE")]
     pub verifier_file: Option<PathBuf>,
+
+    /// Sign with verifier key from encrypted keystore file, password is read
+    /// from the standard input
+    #[arg(long, value_name = "FILE")]
+    pub keystore: Option<PathBuf>,
 }
 
 /// Verifier-to-be, for `make` and `sign` commands.
@@ -677,6 +960,9 @@ pub enum Crypto {
     /// No verifier, to make unsigned updates.
     None,
 
+    /// Verifier key from keystore file, signature is made here.
+    Keystore { pair: SigningPair },
+
     /// Real verifier, [`SufficientCrypto`] is either assembled from `make`
     /// command input parts or from `sign` command input directly.
     Sufficient { s: SufficientCrypto },
@@ -709,6 +995,14 @@ pub struct Sufficient {
     #[arg(long, value_name = "FILE")]
     pub sufficient_file: Option<String>,
 }
+
+/// Format of the file with hot database networks.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileFormat {
+    Toml,
+    Json,
+}
+
 /// Payload for `make` and `sign` commands.
 ///
 /// Associated data is `Vec<u8>` blob that becomes part of the update.
@@ -810,7 +1104,7 @@ impl Override {
     }
 }
 
-fn encryption_from_args(s: &str) -> std::result::Result<Encryption, &'static str> {
+pub(crate) fn encryption_from_args(s: &str) -> std::result::Result<Encryption, &'static str> {
     match s {
         "ed25519" => Ok(Encryption::Ed25519),
         "sr25519" => Ok(Encryption::Sr25519),
@@ -822,6 +1116,13 @@ fn encryption_from_args(s: &str) -> std::result::Result<Encryption, &'static str
     }
 }
 
+fn compression_from_args(s: &str) -> std::result::Result<Compression, &'static str> {
+    match s {
+        "zstd" => Ok(Compression::Zstd),
+        _ => Err("unexpected compression type, expected `zstd`"),
+    }
+}
+
 impl Override {
     /// Flag to indicate that no overrides were invoked.
     pub fn all_empty(&self) -> bool {
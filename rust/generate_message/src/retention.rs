@@ -0,0 +1,261 @@
+//! Metadata retention policy and pruning of the hot database
+//!
+//! Hot database keeps metadata entries in [`METATREE`](constants::METATREE)
+//! according to the metadata retention policy [`MetaRetention`], stored in
+//! [`SETTREE`]:
+//!
+//! - a number of latest metadata versions kept for each network, two by
+//!   default
+//! - pinned metadata entries, kept regardless of the version
+//! - network block ranges, all metadata versions used within the range are
+//!   kept regardless of the version
+//!
+//! Metadata entries not covered by the policy are removed from the hot database
+//! when new metadata is added there.
+//!
+//! Block hashes in [`META_HISTORY`](constants::META_HISTORY) are not removed
+//! together with the metadata, so that the block hash is still known if the
+//! same metadata is fetched again. These entries, and metadata entries left
+//! over after the policy change, are removed with `prune` command.
+use constants::{META_RETENTION, SETTREE};
+use db_handling::{db_transactions::TrDbHot, helpers::open_tree};
+use definitions::{
+    keyring::MetaKey,
+    metadata::{MetaBlockRange, MetaPin, MetaRetention},
+};
+use parity_scale_codec::{Decode, Encode};
+use sled::Batch;
+
+use crate::error::{Error, Result};
+use crate::fetch_metadata::fetch_version_at_block_number;
+use crate::helpers::{
+    address_book_content, meta_history_content, read_metadata_database, MetaValuesStamped,
+};
+use crate::parser::Retention;
+
+/// Get metadata retention policy from the hot database.
+///
+/// If no policy is stored, the default one is used.
+pub fn get_meta_retention(database: &sled::Db) -> Result<MetaRetention> {
+    let settings = open_tree(database, SETTREE)?;
+    let res = settings
+        .get(META_RETENTION)?
+        .map(|meta_retention_encoded| <MetaRetention>::decode(&mut &meta_retention_encoded[..]))
+        .transpose()?;
+    Ok(res.unwrap_or_default())
+}
+
+/// Store metadata retention policy in the hot database.
+fn set_meta_retention(database: &sled::Db, meta_retention: &MetaRetention) -> Result<()> {
+    let mut settings_batch = Batch::default();
+    settings_batch.insert(META_RETENTION, meta_retention.encode());
+    TrDbHot::new()
+        .set_settings(settings_batch)
+        .apply(database)?;
+    Ok(())
+}
+
+/// Split metadata entries into the ones kept by the metadata retention
+/// policy, and the ones that should be removed.
+pub fn split_by_retention(
+    meta_retention: &MetaRetention,
+    all_meta: Vec<MetaValuesStamped>,
+) -> (Vec<MetaValuesStamped>, Vec<MetaValuesStamped>) {
+    let name_versions: Vec<(String, u32)> = all_meta
+        .iter()
+        .map(|x| (x.meta_values.name.to_string(), x.meta_values.version))
+        .collect();
+    all_meta.into_iter().partition(|x| {
+        let newer_versions = name_versions
+            .iter()
+            .filter(|(name, version)| {
+                name == &x.meta_values.name && *version > x.meta_values.version
+            })
+            .count();
+        newer_versions < meta_retention.keep as usize
+            || meta_retention.is_kept_always(&x.meta_values.name, x.meta_values.version)
+    })
+}
+
+/// Process `retention` command.
+pub fn retention(database: &sled::Db, command: Retention) -> Result<()> {
+    let mut meta_retention = get_meta_retention(database)?;
+    match command {
+        Retention::Show => {
+            show_retention(&meta_retention);
+            return Ok(());
+        }
+        Retention::Keep { n } => meta_retention.keep = n,
+        Retention::Pin { name, version } => {
+            let pin = MetaPin { name, version };
+            if !meta_retention.pinned.contains(&pin) {
+                meta_retention.pinned.push(pin)
+            }
+        }
+        Retention::Unpin { name, version } => meta_retention
+            .pinned
+            .retain(|pin| pin.name != name || pin.version != version),
+        Retention::KeepBlocks { name, from, to } => {
+            if from > to {
+                return Err(Error::BlockRangeEmpty { from, to });
+            }
+            let address = address_book_content(database)?
+                .into_iter()
+                .find(|(_, address_book_entry)| address_book_entry.name == name)
+                .map(|(_, address_book_entry)| address_book_entry.address)
+                .ok_or_else(|| Error::AddressBookEntryWithName {
+                    name: name.to_string(),
+                })?;
+            let block_range = MetaBlockRange {
+                first_version: fetch_version_at_block_number(&address, from)?,
+                last_version: fetch_version_at_block_number(&address, to)?,
+                name,
+                from,
+                to,
+            };
+            meta_retention.block_ranges.retain(|range| {
+                range.name != block_range.name || range.from != from || range.to != to
+            });
+            meta_retention.block_ranges.push(block_range);
+        }
+        Retention::ClearBlocks { name } => meta_retention
+            .block_ranges
+            .retain(|range| range.name != name),
+    }
+    set_meta_retention(database, &meta_retention)
+}
+
+/// Print metadata retention policy.
+fn show_retention(meta_retention: &MetaRetention) {
+    println!(
+        "Keep {} latest metadata versions for each network.",
+        meta_retention.keep
+    );
+    if meta_retention.pinned.is_empty() {
+        println!("Pinned metadata: none");
+    } else {
+        let pinned: Vec<String> = meta_retention
+            .pinned
+            .iter()
+            .map(|pin| format!("{} {}", pin.name, pin.version))
+            .collect();
+        println!("Pinned metadata: {}", pinned.join(", "));
+    }
+    if meta_retention.block_ranges.is_empty() {
+        println!("Block ranges: none");
+    } else {
+        let block_ranges: Vec<String> = meta_retention
+            .block_ranges
+            .iter()
+            .map(|range| {
+                format!(
+                    "{} blocks {}-{}, versions {}-{}",
+                    range.name, range.from, range.to, range.first_version, range.last_version
+                )
+            })
+            .collect();
+        println!("Block ranges: {}", block_ranges.join("; "));
+    }
+}
+
+/// Hot database entries that are not covered by the metadata retention
+/// policy.
+pub struct PrunePlan {
+    /// Network name and version for [`METATREE`](constants::METATREE) entries
+    pub metadata: Vec<(String, u32)>,
+
+    /// Network name and version for [`META_HISTORY`](constants::META_HISTORY)
+    /// entries
+    pub meta_history: Vec<(String, u32)>,
+}
+
+impl PrunePlan {
+    /// Collect hot database entries not covered by the metadata retention
+    /// policy.
+    ///
+    /// [`META_HISTORY`](constants::META_HISTORY) entries are removed if there
+    /// is no kept metadata entry with the same name and version.
+    pub fn get(database: &sled::Db) -> Result<Self> {
+        let meta_retention = get_meta_retention(database)?;
+        let (kept, removed) =
+            split_by_retention(&meta_retention, read_metadata_database(database)?);
+        let metadata = removed
+            .into_iter()
+            .map(|x| (x.meta_values.name, x.meta_values.version))
+            .collect();
+        let meta_history = meta_history_content(database)?
+            .into_iter()
+            .filter(|entry| {
+                !kept.iter().any(|x| {
+                    x.meta_values.name == entry.name && x.meta_values.version == entry.version
+                })
+            })
+            .map(|entry| (entry.name, entry.version))
+            .collect();
+        Ok(Self {
+            metadata,
+            meta_history,
+        })
+    }
+
+    /// Remove collected entries from the hot database.
+    pub fn apply(&self, database: &sled::Db) -> Result<()> {
+        let mut metadata_batch = Batch::default();
+        for (name, version) in self.metadata.iter() {
+            metadata_batch.remove(MetaKey::from_parts(name, *version).key())
+        }
+        let mut meta_history_batch = Batch::default();
+        for (name, version) in self.meta_history.iter() {
+            meta_history_batch.remove(MetaKey::from_parts(name, *version).key())
+        }
+        TrDbHot::new()
+            .set_metadata(metadata_batch)
+            .set_meta_history(meta_history_batch)
+            .apply(database)?;
+        Ok(())
+    }
+
+    /// Check if there is nothing to remove.
+    pub fn is_empty(&self) -> bool {
+        self.metadata.is_empty() && self.meta_history.is_empty()
+    }
+}
+
+impl std::fmt::Display for PrunePlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (title, set) in [
+            ("Metadata entries", &self.metadata),
+            ("Block history entries", &self.meta_history),
+        ] {
+            if set.is_empty() {
+                writeln!(f, "{title} to remove: none")?;
+            } else {
+                let entries: Vec<String> = set
+                    .iter()
+                    .map(|(name, version)| format!("{name} {version}"))
+                    .collect();
+                writeln!(f, "{title} to remove: {}", entries.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Process `prune` command.
+///
+/// Entries to be removed are always printed, and are removed only if `apply`
+/// flag is set.
+pub fn prune(database: &sled::Db, apply: bool) -> Result<()> {
+    let prune_plan = PrunePlan::get(database)?;
+    print!("{prune_plan}");
+    if prune_plan.is_empty() {
+        return Ok(());
+    }
+    if apply {
+        prune_plan.apply(database)?;
+        println!("Entries removed.");
+    } else {
+        println!("Nothing removed, use `--apply` to remove the entries.");
+    }
+    Ok(())
+}
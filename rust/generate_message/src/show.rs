@@ -216,11 +216,16 @@ pub fn show_block_history(database: &sled::Db) -> Result<()> {
     }
     println!("Database has following metadata fetch history:\n");
     for x in meta_history_set.iter() {
+        let metadata_version_insert = match x.metadata_version {
+            Some(v) => format!(", metadata V{v}"),
+            None => String::new(),
+        };
         println!(
-            "{} {}, fetched at block {}",
+            "{} {}, fetched at block {}{}",
             x.name,
             x.version,
             hex::encode(x.block_hash),
+            metadata_version_insert,
         );
     }
     Ok(())
@@ -6,6 +6,7 @@
 use definitions::{crypto::Encryption, keyring::NetworkSpecsKey, metadata::AddressBookEntry};
 use std::path::Path;
 
+use crate::chain_spec::specs_from_chain_spec;
 use crate::error::{Error, Result};
 use crate::helpers::{
     add_specs_print, address_book_content, db_upd_network, filter_address_book_by_url,
@@ -18,6 +19,29 @@ use crate::parser::{Content, InstructionSpecs, Override, Set, Token};
 /// Process `add-specs` command according to the [`InstructionSpecs`] received
 /// from the command line.
 pub fn gen_add_specs(instruction: InstructionSpecs) -> Result<()> {
+    // `$ cargo run add-specs -d --chain-spec <chain_spec_file>
+    // <encryption override> <optional properties> <optional signer title
+    // override>`
+    //
+    // Produce `add_specs` payload from the raw chain spec file, for networks
+    // with no RPC endpoint. Database is not used, so only `-d` setting key
+    // makes sense.
+    //
+    // Command line **must** contain encryption override.
+    if let Some(chain_spec) = instruction.chain_spec {
+        return match (instruction.set.into(), instruction.over.encryption) {
+            (Set::D, Some(encryption)) => specs_d_chain_spec(
+                &chain_spec,
+                encryption,
+                instruction.base58_prefix,
+                instruction.over.token(),
+                instruction.over.title.clone(),
+                &instruction.files_dir,
+            ),
+            (Set::D, None) => Err(Error::ChainSpecNoEncryption(chain_spec)),
+            _ => Err(Error::NotSupported),
+        };
+    }
     match instruction.set.into() {
         // `-f` setting key: produce `add_specs` payload files from existing
         // database entries.
@@ -337,6 +361,31 @@ where
     add_specs_print(&specs, &files_dir)
 }
 
+/// `add-specs -d --chain-spec <chain_spec_file> <override(s)>`
+///
+/// Make [`NetworkSpecs`](definitions::network_specs::NetworkSpecs) from the
+/// raw chain spec file without RPC calls and print `add_specs` payload file.
+fn specs_d_chain_spec<P>(
+    chain_spec: &Path,
+    encryption: Encryption,
+    optional_base58prefix: Option<u16>,
+    optional_token: Option<Token>,
+    optional_signer_title_override: Option<String>,
+    files_dir: P,
+) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    let specs = specs_from_chain_spec(
+        chain_spec,
+        encryption,
+        optional_base58prefix,
+        optional_token,
+        optional_signer_title_override,
+    )?;
+    add_specs_print(&specs, &files_dir)
+}
+
 /// `add-specs <-p/-t> -n <address_book_title> <override(s)>`
 ///
 /// Encryption and title overrides are possible. Token override is possible if
@@ -0,0 +1,135 @@
+//! Per-network report for `load-metadata` with `-a` reference key
+//!
+//! Each network processed through RPC calls gets a [`NetworkReport`] with the
+//! result: new metadata fetched, previously known metadata fetched, or failure
+//! with a reason. Summary is printed after processing, and, if `--summary` key
+//! is used, written into a JSON file, for example:
+//!
+//! ```json
+//! {
+//!   "updated": 1,
+//!   "unchanged": 0,
+//!   "failed": 1,
+//!   "networks": [
+//!     {
+//!       "name": "polkadot",
+//!       "address": "wss://rpc.polkadot.io",
+//!       "status": "updated",
+//!       "version": 9430,
+//!       "attempts": 1
+//!     },
+//!     {
+//!       "name": "westend",
+//!       "address": "wss://westend-rpc.polkadot.io",
+//!       "status": "failed",
+//!       "reason": "...",
+//!       "attempts": 3
+//!     }
+//!   ]
+//! }
+//! ```
+use serde::Serialize;
+use std::path::Path;
+
+use crate::error::Result;
+
+/// Result of processing a single network.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// Fetched metadata was not in the hot database before
+    ///
+    /// With `-d` setting key the hot database metadata is not consulted, and
+    /// all fetched metadata is reported as updated.
+    Updated,
+
+    /// Fetched metadata was already in the hot database
+    Unchanged,
+
+    /// Fetch or processing of fetched data failed
+    Failed,
+}
+
+/// Report for a single network.
+#[derive(Debug, Serialize)]
+pub struct NetworkReport {
+    pub name: String,
+    pub address: String,
+    pub status: Status,
+
+    /// Fetched metadata version, if fetch was successful
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+
+    /// Error text, for failed networks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// Number of fetch attempts made
+    pub attempts: u32,
+}
+
+/// Reports for all processed networks, with the number of networks with each
+/// [`Status`].
+#[derive(Debug, Default, Serialize)]
+pub struct FetchSummary {
+    pub updated: usize,
+    pub unchanged: usize,
+    pub failed: usize,
+    pub networks: Vec<NetworkReport>,
+}
+
+impl FetchSummary {
+    /// Add report for a network.
+    pub fn push(&mut self, report: NetworkReport) {
+        match report.status {
+            Status::Updated => self.updated += 1,
+            Status::Unchanged => self.unchanged += 1,
+            Status::Failed => self.failed += 1,
+        }
+        self.networks.push(report)
+    }
+
+    /// Write the summary into a JSON file.
+    pub fn write<P>(&self, path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for FetchSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Summary: {} updated, {} unchanged, {} failed.",
+            self.updated, self.unchanged, self.failed
+        )?;
+        for report in self.networks.iter() {
+            match report.status {
+                Status::Updated => writeln!(
+                    f,
+                    "{}: updated to version {}",
+                    report.name,
+                    report.version.unwrap_or_default()
+                )?,
+                Status::Unchanged => writeln!(
+                    f,
+                    "{}: unchanged, version {}",
+                    report.name,
+                    report.version.unwrap_or_default()
+                )?,
+                Status::Failed => writeln!(
+                    f,
+                    "{}: failed after {} attempt(s). {}",
+                    report.name,
+                    report.attempts,
+                    report.reason.as_deref().unwrap_or_default()
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+//! Verify existing update payload
+//!
+//! Complete update, as a hexadecimal text file produced with `--goal text`, a
+//! raw bytes file, or a QR code `.png` file (static or animated), is checked
+//! the same way the Vault checks it: update is parsed with [`Update`], the
+//! signature, if any, is validated, and the content is decoded.
+//!
+//! `$ cargo run verify <FILE> [--json]`
+use serde::Serialize;
+use sp_core::blake2_256;
+use std::path::Path;
+
+use definitions::{
+    crypto::SufficientCrypto,
+    helpers::unhex,
+    metadata::MetaValues,
+    payload::{Update, UpdateContent},
+    qr_transfers::ContentLoadMeta,
+};
+use qr_reader_pc::read_qr_frames;
+use qr_reader_phone::process_payload::{process_decoded_payload, InProgress, Ready};
+
+use crate::error::{Error, Result};
+
+/// Verification report for an update.
+#[derive(Debug, Serialize)]
+pub struct PayloadReport {
+    /// Update type: `load_metadata`, `load_types` or `add_specs`
+    pub payload: String,
+
+    /// Compression of `load_metadata` content, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compression: Option<String>,
+
+    /// Verifier, `None` for unsigned updates
+    pub verifier: Option<VerifierReport>,
+
+    /// Decoded update content
+    pub content: ContentReport,
+}
+
+/// Verifier of the update, with signature already validated.
+#[derive(Debug, Serialize)]
+pub struct VerifierReport {
+    pub public_key: String,
+    pub encryption: String,
+}
+
+/// Decoded update content.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentReport {
+    LoadMetadata {
+        name: String,
+        version: u32,
+        genesis_hash: String,
+
+        /// Hexadecimal blake2 hash of the metadata
+        metadata_hash: String,
+    },
+    LoadTypes {
+        entries: usize,
+
+        /// Hexadecimal blake2 hash of the types information
+        types_hash: String,
+    },
+    AddSpecs {
+        name: String,
+        title: String,
+        encryption: String,
+        genesis_hash: String,
+        base58prefix: u16,
+        decimals: u8,
+        unit: String,
+        path_id: String,
+    },
+}
+
+impl std::fmt::Display for PayloadReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.compression {
+            Some(compression) => writeln!(
+                f,
+                "Payload: {}, compressed with {}",
+                self.payload, compression
+            )?,
+            None => writeln!(f, "Payload: {}", self.payload)?,
+        }
+        match &self.verifier {
+            Some(v) => writeln!(
+                f,
+                "Verifier: public key: {}, encryption: {}, signature is valid",
+                v.public_key, v.encryption
+            )?,
+            None => writeln!(f, "Verifier: none, payload is not signed")?,
+        }
+        match &self.content {
+            ContentReport::LoadMetadata {
+                name,
+                version,
+                genesis_hash,
+                metadata_hash,
+            } => write!(
+                f,
+                "Metadata: {name} version {version}, genesis hash {genesis_hash}, metadata hash {metadata_hash}"
+            ),
+            ContentReport::LoadTypes {
+                entries,
+                types_hash,
+            } => write!(f, "Types: {entries} entries, types hash {types_hash}"),
+            ContentReport::AddSpecs {
+                name,
+                title,
+                encryption,
+                genesis_hash,
+                base58prefix,
+                decimals,
+                unit,
+                path_id,
+            } => write!(
+                f,
+                "Network specs: {name}, title {title}, encryption {encryption}, genesis hash {genesis_hash}, base58 prefix {base58prefix}, decimals {decimals}, unit {unit}, path {path_id}"
+            ),
+        }
+    }
+}
+
+/// Read, check and decode the update from file.
+pub fn verify_update<P>(path: P) -> Result<PayloadReport>
+where
+    P: AsRef<Path>,
+{
+    let data_hex = match path.as_ref().extension() {
+        Some(ext) if ext == "png" => hex::encode(read_qr_png(&path)?),
+        _ => {
+            let bytes = std::fs::read(&path)?;
+            match std::str::from_utf8(&bytes).ok().map(|s| unhex(s.trim())) {
+                Some(Ok(decoded)) => hex::encode(decoded),
+                _ => hex::encode(bytes),
+            }
+        }
+    };
+    verify_update_hex(&data_hex)
+}
+
+/// Check and decode the update from hexadecimal string.
+pub fn verify_update_hex(data_hex: &str) -> Result<PayloadReport> {
+    let data = unhex(data_hex)?;
+    let (update, _) = Update::from_slice_with_tail(&data)?;
+    let message = update.content.to_sign()?;
+    if !update.verify_message(&message) {
+        return Err(Error::BadSignature);
+    }
+
+    let (payload, compression) = match &update.content {
+        UpdateContent::LoadMetadata(_) => ("load_metadata", None),
+        UpdateContent::LoadMetadataCompressed(a) => {
+            ("load_metadata", Some(a.compression()?.show()))
+        }
+        UpdateContent::LoadTypes(_) => ("load_types", None),
+        UpdateContent::AddSpecs(_) => ("add_specs", None),
+    };
+
+    let content = match &update.content {
+        UpdateContent::LoadMetadata(_) | UpdateContent::LoadMetadataCompressed(_) => {
+            let (meta, genesis_hash) = ContentLoadMeta::from_slice(&message).meta_genhash()?;
+            let meta_values = MetaValues::from_slice_metadata(&meta)?;
+            ContentReport::LoadMetadata {
+                name: meta_values.name,
+                version: meta_values.version,
+                genesis_hash: hex::encode(genesis_hash),
+                metadata_hash: hex::encode(blake2_256(&meta)),
+            }
+        }
+        UpdateContent::LoadTypes(content) => ContentReport::LoadTypes {
+            entries: content.types()?.len(),
+            types_hash: content.show().0,
+        },
+        UpdateContent::AddSpecs(content) => {
+            let specs = content.specs()?;
+            ContentReport::AddSpecs {
+                name: specs.name,
+                title: specs.title,
+                encryption: specs.encryption.show(),
+                genesis_hash: hex::encode(specs.genesis_hash),
+                base58prefix: specs.base58prefix,
+                decimals: specs.decimals,
+                unit: specs.unit,
+                path_id: specs.path_id,
+            }
+        }
+    };
+
+    Ok(PayloadReport {
+        payload: payload.to_string(),
+        compression,
+        verifier: update.signature.map(|s| verifier_report(&s)),
+        content,
+    })
+}
+
+fn verifier_report(signature: &SufficientCrypto) -> VerifierReport {
+    let details = signature.verifier_value().show_card();
+    VerifierReport {
+        public_key: details.public_key,
+        encryption: details.encryption,
+    }
+}
+
+/// Collect the update from QR code `.png` file, static or animated.
+fn read_qr_png<P>(path: P) -> Result<Vec<u8>>
+where
+    P: AsRef<Path>,
+{
+    let mut decoding = InProgress::None;
+    for frame in read_qr_frames(&path)? {
+        match process_decoded_payload(frame, &None, decoding)? {
+            Ready::NotYet(in_progress) => decoding = in_progress,
+            Ready::Yes(payload) => return Ok(payload),
+            Ready::BananaSplitPasswordRequest | Ready::BananaSplitReady(_) => {
+                return Err(Error::QrNotUpdate(path.as_ref().to_path_buf()))
+            }
+        }
+    }
+    Err(Error::QrNotUpdate(path.as_ref().to_path_buf()))
+}
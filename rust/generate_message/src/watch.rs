@@ -0,0 +1,307 @@
+//! Watch networks for runtime upgrades and prepare `load_metadata` payloads
+//!
+//! `$ cargo run watch`
+//!
+//! For each network in [`ADDRESS_BOOK`](constants::ADDRESS_BOOK) tree of the
+//! hot database a runtime version subscription is made at the network address.
+//! Whenever the node reports runtime version with no corresponding metadata
+//! entry in [`METATREE`], the metadata is fetched same way as with
+//! `$ cargo run load-metadata -k -n <network_name>`: the hot database is
+//! updated and the `load_metadata` payload file is produced in the files
+//! directory, by default [`FOLDER`](constants::FOLDER).
+//!
+//! If `--notify-dir` key is used, a JSON notification file
+//! `load_metadata_<network_name>V<version>.json` is written into the given
+//! directory for each produced payload, with network name, version and path to
+//! the payload file.
+//!
+//! Subscription is made for each network only once, even if there are several
+//! address book entries for the network with different encryptions. Dropped
+//! subscriptions are restored after a delay. Only WebSocket addresses support
+//! subscriptions.
+//!
+//! Metadata version could be capped with `--metadata-version` key, same as for
+//! `load-metadata`.
+//!
+//! The command runs until interrupted.
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Sender};
+use std::time::Duration;
+
+use constants::METATREE;
+use db_handling::helpers::open_tree;
+use definitions::keyring::MetaKey;
+
+use crate::error::{Error, Result};
+use crate::fetch_metadata::{spec_version, subscribe_runtime_version};
+use crate::helpers::{address_book_content, load_metadata_file_name, Write};
+use crate::load::meta_kpt_n;
+
+/// Delay before restoring dropped subscription.
+const RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Runtime version reported by the node.
+struct RuntimeUpgrade {
+    name: String,
+    spec_version: u32,
+}
+
+/// Watch all hot database networks for runtime upgrades.
+pub fn watch<P>(
+    database: &sled::Db,
+    files_dir: P,
+    notify_dir: Option<PathBuf>,
+    max_metadata_version: Option<u32>,
+) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    let mut networks: Vec<(String, String)> = Vec::new();
+    for (_, address_book_entry) in address_book_content(database)?.into_iter() {
+        if !networks
+            .iter()
+            .any(|(name, _)| name == &address_book_entry.name)
+        {
+            networks.push((address_book_entry.name, address_book_entry.address))
+        }
+    }
+    if networks.is_empty() {
+        return Err(Error::AddressBookEmpty);
+    }
+
+    let (sender, receiver) = channel();
+    let runtime = tokio::runtime::Runtime::new()?;
+    for (name, address) in networks.into_iter() {
+        println!("Watching {name} at {address}");
+        runtime.spawn(watch_network(name, address, sender.clone()));
+    }
+    drop(sender);
+
+    // Database updates are done here, one at a time, outside of the
+    // subscriptions runtime.
+    for upgrade in receiver.iter() {
+        if let Err(e) = process_upgrade(
+            database,
+            &upgrade,
+            &files_dir,
+            notify_dir.as_deref(),
+            max_metadata_version,
+        ) {
+            println!(
+                "Error processing runtime version {}{}: {e}",
+                upgrade.name, upgrade.spec_version
+            )
+        }
+    }
+    Ok(())
+}
+
+/// Keep runtime version subscription for a single network, send all reported
+/// runtime versions through `sender`.
+async fn watch_network(name: String, address: String, sender: Sender<RuntimeUpgrade>) {
+    loop {
+        // errors are turned into text right away, not to be held across
+        // `await` points
+        let subscribed = subscribe_runtime_version(&address).await.map_err(|e| {
+            (
+                matches!(e, Error::SubscriptionNotSupported { .. }),
+                e.to_string(),
+            )
+        });
+        let mut subscription = match subscribed {
+            Ok(a) => a,
+            Err((true, e)) => {
+                println!("Unable to watch {name}: {e}");
+                return;
+            }
+            Err((false, e)) => {
+                println!("Unable to subscribe to {name} at {address}: {e}");
+                tokio::time::sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+        loop {
+            let response = subscription.next().await.map(|a| {
+                a.and_then(|value| spec_version(&value))
+                    .map_err(|e| e.to_string())
+            });
+            match response {
+                Some(Ok(spec_version)) => {
+                    let upgrade = RuntimeUpgrade {
+                        name: name.to_string(),
+                        spec_version,
+                    };
+                    if sender.send(upgrade).is_err() {
+                        return;
+                    }
+                }
+                Some(Err(e)) => {
+                    println!("Subscription to {name} at {address} failed: {e}");
+                    break;
+                }
+                None => {
+                    println!("Subscription to {name} at {address} closed");
+                    break;
+                }
+            }
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// Fetch new metadata if the reported runtime version is not yet in the hot
+/// database, and write the notification if requested.
+fn process_upgrade<P>(
+    database: &sled::Db,
+    upgrade: &RuntimeUpgrade,
+    files_dir: P,
+    notify_dir: Option<&Path>,
+    max_metadata_version: Option<u32>,
+) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    let meta_key = MetaKey::from_parts(&upgrade.name, upgrade.spec_version);
+    if open_tree(database, METATREE)?.contains_key(meta_key.key())? {
+        return Ok(());
+    }
+    println!(
+        "Runtime version {}{} reported",
+        upgrade.name, upgrade.spec_version
+    );
+    if let Some(version) = meta_kpt_n(
+        database,
+        &upgrade.name,
+        &Write::OnlyNew,
+        &files_dir,
+        max_metadata_version,
+    )? {
+        let payload = files_dir
+            .as_ref()
+            .join(load_metadata_file_name(&upgrade.name, version));
+        if let Some(notify_dir) = notify_dir {
+            let notification = json!({
+                "name": upgrade.name,
+                "version": version,
+                "payload": payload,
+            });
+            std::fs::write(
+                notify_dir.join(format!("load_metadata_{}V{version}.json", upgrade.name)),
+                serde_json::to_string_pretty(&notification)? + "\n",
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use db_handling::{db_transactions::TrDbHot, default_hot};
+    use definitions::keyring::AddressBookKey;
+    use parity_scale_codec::Encode;
+    use serde_json::Value;
+    use sled::Batch;
+
+    #[test]
+    fn spec_version_from_subscription() {
+        let runtime_version: Value = serde_json::from_str(
+            r#"{"specName": "polkadot", "implName": "parity-polkadot", "authoringVersion": 0, "specVersion": 9430, "implVersion": 0, "transactionVersion": 24, "stateVersion": 0}"#,
+        )
+        .unwrap();
+        assert_eq!(spec_version(&runtime_version).unwrap(), 9430);
+
+        for damaged in [
+            r#"{"specName": "polkadot"}"#,
+            r#"{"specVersion": "9430"}"#,
+            r#"{"specVersion": -1}"#,
+            r#"{"specVersion": 4294967296}"#,
+            r#"[9430]"#,
+        ] {
+            assert!(matches!(
+                spec_version(&serde_json::from_str(damaged).unwrap()),
+                Err(Error::UnexpectedRuntimeVersionFormat)
+            ));
+        }
+    }
+
+    #[test]
+    fn upgrade_fetched_only_for_new_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = sled::open(dir.path().join("db")).unwrap();
+        default_hot(Some(&database)).unwrap();
+
+        // polkadot address book entries are pointed to recorded node responses
+        let fixture = dir.path().join("polkadot.json");
+        let mut address_book = Batch::default();
+        for (title, mut address_book_entry) in address_book_content(&database).unwrap() {
+            if address_book_entry.name == "polkadot" {
+                address_book_entry.address = format!("file://{}", fixture.to_string_lossy());
+                address_book.insert(
+                    AddressBookKey::from_title(&title).key(),
+                    address_book_entry.encode(),
+                );
+            }
+        }
+        TrDbHot::new()
+            .set_address_book(address_book)
+            .apply(&database)
+            .unwrap();
+
+        let meta = defaults::test_metadata()
+            .unwrap()
+            .into_iter()
+            .find(|x| x.name == "polkadot" && x.version == 30)
+            .unwrap()
+            .meta;
+        let block_hash = format!("0x{}", "5a".repeat(32));
+        let responses = json!([
+            {"method": "chain_getBlockHash", "result": block_hash},
+            {"method": "state_getMetadata", "params": [block_hash], "result": format!("0x{}", hex::encode(meta))},
+            {"method": "chain_getBlockHash", "params": [0], "result": "0x91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3"},
+        ]);
+        std::fs::write(&fixture, responses.to_string()).unwrap();
+
+        let files_dir = dir.path().join("files");
+        let notify_dir = dir.path().join("notify");
+        std::fs::create_dir(&files_dir).unwrap();
+        std::fs::create_dir(&notify_dir).unwrap();
+        let notification = notify_dir.join("load_metadata_polkadotV30.json");
+        let upgrade = RuntimeUpgrade {
+            name: String::from("polkadot"),
+            spec_version: 30,
+        };
+
+        // new version is fetched, payload and notification are written
+        process_upgrade(&database, &upgrade, &files_dir, Some(&notify_dir), None).unwrap();
+        assert!(open_tree(&database, METATREE)
+            .unwrap()
+            .contains_key(MetaKey::from_parts("polkadot", 30).key())
+            .unwrap());
+        let payload = files_dir.join("sign_me_load_metadata_polkadotV30");
+        assert!(payload.exists());
+        let notified: Value =
+            serde_json::from_slice(&std::fs::read(&notification).unwrap()).unwrap();
+        assert_eq!(
+            notified,
+            json!({"name": "polkadot", "version": 30, "payload": payload})
+        );
+
+        // known version is not fetched again, node is not contacted
+        std::fs::remove_file(&fixture).unwrap();
+        std::fs::remove_file(&notification).unwrap();
+        process_upgrade(&database, &upgrade, &files_dir, Some(&notify_dir), None).unwrap();
+        assert!(!notification.exists());
+
+        // unknown version is fetched
+        let upgrade = RuntimeUpgrade {
+            name: String::from("polkadot"),
+            spec_version: 31,
+        };
+        assert!(matches!(
+            process_upgrade(&database, &upgrade, &files_dir, Some(&notify_dir), None),
+            Err(Error::Io(_))
+        ));
+    }
+}
@@ -1,9 +1,37 @@
 pub mod common;
 
-use crate::common::{assert_cmd_stdout, setup};
+use crate::common::{assert_cmd_stdout, base_cmd, setup};
+use definitions::{crypto::Encryption, network_specs::NetworkSpecs, qr_transfers::ContentAddSpecs};
+use parity_scale_codec::{Compact, Encode};
+use sp_core::{blake2_256, H256};
 use std::path::PathBuf;
 use tempfile::tempdir;
 
+/// Genesis hash for genesis storage with runtime code only, state version 0.
+///
+/// Computed here independently of the trie implementation: trie with single
+/// entry is a single leaf node, with `:code` key (10 nibbles) and inline value.
+/// Genesis header has zero parent hash, block number 0, state root, root of
+/// empty extrinsics trie and empty digest.
+fn genesis_hash_code_only(code: &[u8]) -> H256 {
+    let leaf = [
+        &[0x40 | 10][..],
+        b":code",
+        &Compact(code.len() as u32).encode(),
+        code,
+    ]
+    .concat();
+    let header = [
+        &[0; 32][..],
+        &Compact(0u32).encode(),
+        &blake2_256(&leaf),
+        &blake2_256(&[0]),
+        &Vec::<u8>::new().encode(),
+    ]
+    .concat();
+    blake2_256(&header).into()
+}
+
 #[test]
 fn it_adds_specs() {
     let files_dir = tempdir().unwrap();
@@ -21,3 +49,73 @@ fn it_adds_specs() {
     // TODO
     // assert_files_eq(specs, expected_specs);
 }
+
+#[test]
+fn it_adds_specs_from_chain_spec() {
+    let files_dir = tempdir().unwrap();
+    let code = std::fs::read("./tests/for_tests/polkadot.wasm").unwrap();
+    let chain_spec = files_dir.path().join("chain_spec_raw.json");
+    let mut content = serde_json::json!({
+        "name": "Polkadot",
+        "id": "polkadot",
+        "properties": {"ss58Format": 0, "tokenDecimals": 10, "tokenSymbol": "DOT"},
+        "genesis": {"raw": {"top": {"0x3a636f6465": format!("0x{}", hex::encode(&code))}}}
+    });
+    std::fs::write(&chain_spec, content.to_string()).unwrap();
+
+    // encryption is not in the chain spec and must be set
+    let cmd = format!(
+        "add-specs -d --chain-spec {0} --files-dir {1}",
+        chain_spec.to_string_lossy(),
+        files_dir.path().to_string_lossy()
+    );
+    base_cmd()
+        .args(cmd.split(' ').collect::<Vec<&str>>())
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(format!(
+            "An error occurred: Chain spec {} could not be used without `--encryption` key, encryption is not in the chain spec.\n",
+            chain_spec.display()
+        ));
+
+    let cmd = format!(
+        "add-specs -d --chain-spec {0} --encryption sr25519 --files-dir {1}",
+        chain_spec.to_string_lossy(),
+        files_dir.path().to_string_lossy()
+    );
+    assert_cmd_stdout(&cmd, "");
+
+    let payload =
+        std::fs::read(files_dir.path().join("sign_me_add_specs_polkadot_sr25519")).unwrap();
+    let specs = ContentAddSpecs::from_slice(&payload).specs().unwrap();
+    assert_eq!(
+        specs,
+        NetworkSpecs {
+            base58prefix: 0,
+            color: "#660D35".to_string(),
+            decimals: 10,
+            encryption: Encryption::Sr25519,
+            genesis_hash: genesis_hash_code_only(&code),
+            logo: "polkadot".to_string(),
+            name: "polkadot".to_string(),
+            path_id: "//polkadot".to_string(),
+            secondary_color: "#262626".to_string(),
+            title: "polkadot-sr25519".to_string(),
+            unit: "DOT".to_string(),
+        }
+    );
+
+    // genesis that is not raw could not be used
+    content["genesis"] = serde_json::json!({"runtimeGenesis": {}});
+    std::fs::write(&chain_spec, content.to_string()).unwrap();
+    base_cmd()
+        .args(cmd.split(' ').collect::<Vec<&str>>())
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(format!(
+            "An error occurred: Chain spec {} has no raw genesis storage, use `build-spec --raw` output.\n",
+            chain_spec.display()
+        ));
+}
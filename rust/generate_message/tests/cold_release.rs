@@ -0,0 +1,80 @@
+pub mod common;
+
+use crate::common::{assert_files_eq, base_cmd};
+
+use constants::METATREE;
+use tempfile::tempdir;
+
+#[test]
+fn it_makes_reproducible_cold_release_with_manifest() {
+    let tmp_dir = tempdir().unwrap();
+    let mut manifests = Vec::new();
+    let mut dumps = Vec::new();
+    for i in 0..2 {
+        let db_path = tmp_dir.path().join(format!("cold_release_{i}"));
+        let manifest = tmp_dir.path().join(format!("manifest_{i}.json"));
+        let dump = tmp_dir.path().join(format!("dump_{i}.scale"));
+        base_cmd()
+            .arg("make-cold-release")
+            .arg(&db_path)
+            .arg("--manifest")
+            .arg(&manifest)
+            .arg("--dump")
+            .arg(&dump)
+            .assert()
+            .success()
+            .stderr("");
+        base_cmd()
+            .arg("verify-cold-release")
+            .arg("--cold-db")
+            .arg(&db_path)
+            .arg("--manifest")
+            .arg(&manifest)
+            .arg("--dump")
+            .arg(&dump)
+            .assert()
+            .success()
+            .stderr("");
+        manifests.push(manifest);
+        dumps.push(dump);
+    }
+    assert_files_eq(&manifests[0], &manifests[1]);
+    assert_files_eq(&dumps[0], &dumps[1]);
+
+    // dump does not match the manifest
+    let damaged_dump = tmp_dir.path().join("dump_damaged.scale");
+    let mut dump_content = std::fs::read(&dumps[0]).unwrap();
+    dump_content.push(0);
+    std::fs::write(&damaged_dump, dump_content).unwrap();
+    base_cmd()
+        .arg("verify-cold-release")
+        .arg("--cold-db")
+        .arg(tmp_dir.path().join("cold_release_0"))
+        .arg("--manifest")
+        .arg(&manifests[0])
+        .arg("--dump")
+        .arg(&damaged_dump)
+        .assert()
+        .failure()
+        .stderr("An error occurred: Cold release database does not match the manifest: database dump differ.\n");
+
+    // remove a metadata entry after the manifest was made
+    {
+        let database = sled::open(tmp_dir.path().join("cold_release_0")).unwrap();
+        let metadata = database.open_tree(METATREE).unwrap();
+        let (key, _) = metadata.first().unwrap().unwrap();
+        metadata.remove(key).unwrap();
+        database.flush().unwrap();
+    }
+    base_cmd()
+        .arg("verify-cold-release")
+        .arg("--cold-db")
+        .arg(tmp_dir.path().join("cold_release_0"))
+        .arg("--manifest")
+        .arg(&manifests[0])
+        .arg("--dump")
+        .arg(&dumps[0])
+        .assert()
+        .failure()
+        .stderr("An error occurred: Cold release database does not match the manifest: metadata differ.\n");
+}
@@ -1,7 +1,19 @@
 pub mod common;
-use crate::common::{assert_cmd_stdout, assert_files_eq, setup};
+use crate::common::{assert_cmd_stdout, assert_files_eq, base_cmd, setup};
 
+use constants::METATREE;
+use db_handling::db_transactions::TrDbHot;
+use defaults::test_metadata;
+use definitions::{
+    crypto::Encryption,
+    keyring::{AddressBookKey, MetaKey},
+    metadata::AddressBookEntry,
+};
+use parity_scale_codec::Encode;
+use sled::Batch;
+use sp_core::H256;
 use std::path::PathBuf;
+use std::str::FromStr;
 use tempfile::tempdir;
 
 #[test]
@@ -21,3 +33,110 @@ fn it_loads_metadata() {
     let expected_file = PathBuf::from("./tests/for_tests/load_metadata_polkadotV30");
     assert_files_eq(&result_file, &expected_file);
 }
+
+#[test]
+fn it_loads_metadata_for_all_with_summary() {
+    let files_dir = tempdir().unwrap();
+    let db = sled::open(&files_dir).unwrap();
+    setup(&db);
+
+    // polkadot address book entry is pointed to recorded node responses
+    let fixture = files_dir.path().join("polkadot.json");
+    let genesis_hash =
+        H256::from_str("91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3").unwrap();
+    let mut address_book = Batch::default();
+    address_book.insert(
+        AddressBookKey::from_title("polkadot").key(),
+        AddressBookEntry {
+            name: String::from("polkadot"),
+            genesis_hash,
+            address: format!("file://{}", fixture.to_string_lossy()),
+            encryption: Encryption::Sr25519,
+            def: false,
+        }
+        .encode(),
+    );
+    TrDbHot::new()
+        .set_address_book(address_book)
+        .apply(&db)
+        .unwrap();
+    drop(db);
+
+    let meta = test_metadata()
+        .unwrap()
+        .into_iter()
+        .find(|x| x.name == "polkadot" && x.version == 30)
+        .unwrap()
+        .meta;
+    let block_hash = format!("0x{}", "5a".repeat(32));
+    let responses = serde_json::json!([
+        {"method": "chain_getBlockHash", "result": block_hash},
+        {"method": "state_getMetadata", "params": [block_hash], "result": format!("0x{}", hex::encode(meta))},
+        {"method": "chain_getBlockHash", "params": [0], "result": format!("0x{}", hex::encode(genesis_hash))},
+    ]);
+    std::fs::write(&fixture, responses.to_string()).unwrap();
+
+    let path = files_dir.path().to_string_lossy();
+    let summary_file = files_dir.path().join("summary.json");
+    let cmd = format!(
+        "load-metadata -k -a --pass-errors --hot-db-path {path} --files-dir {path} --summary {}",
+        summary_file.to_string_lossy()
+    );
+    assert_cmd_stdout(
+        &cmd,
+        "Fetched previously known metadata polkadot30
+Summary: 0 updated, 1 unchanged, 0 failed.
+polkadot: unchanged, version 30
+",
+    );
+    let summary: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&summary_file).unwrap()).unwrap();
+    assert_eq!(summary["unchanged"], 1);
+    assert_eq!(summary["networks"][0]["name"], "polkadot");
+    assert_eq!(summary["networks"][0]["status"], "unchanged");
+    assert_eq!(summary["networks"][0]["version"], 30);
+    assert_eq!(summary["networks"][0]["attempts"], 1);
+
+    // node is unavailable
+    std::fs::remove_file(&fixture).unwrap();
+    let cmd = format!("{cmd} --retries 0");
+    let reason = "No such file or directory (os error 2)";
+    base_cmd()
+        .args(cmd.split(' ').collect::<Vec<&str>>())
+        .assert()
+        .success()
+        .stdout(format!(
+            "Error encountered. {reason} Skipping it.
+Summary: 0 updated, 0 unchanged, 1 failed.
+polkadot: failed after 1 attempt(s). {reason}
+"
+        ));
+    let summary: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&summary_file).unwrap()).unwrap();
+    assert_eq!(summary["failed"], 1);
+    assert_eq!(summary["networks"][0]["status"], "failed");
+    assert_eq!(summary["networks"][0]["reason"], reason);
+    // `-d` key does not consult the metadata in the database, damaged entry
+    // is not noticed
+    std::fs::write(&fixture, responses.to_string()).unwrap();
+    let db = sled::open(&files_dir).unwrap();
+    db.open_tree(METATREE)
+        .unwrap()
+        .insert(
+            MetaKey::from_parts("polkadot", 30).key(),
+            b"damaged".to_vec(),
+        )
+        .unwrap();
+    drop(db);
+    let cmd = format!("load-metadata -d -a --hot-db-path {path} --files-dir {path}");
+    assert_cmd_stdout(
+        &cmd,
+        "Fetched new metadata polkadot30 at block hash 5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a
+Summary: 1 updated, 0 unchanged, 0 failed.
+polkadot: updated to version 30
+",
+    );
+    let result_file = files_dir.path().join("sign_me_load_metadata_polkadotV30");
+    let expected_file = PathBuf::from("./tests/for_tests/load_metadata_polkadotV30");
+    assert_files_eq(&result_file, &expected_file);
+}
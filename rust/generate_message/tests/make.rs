@@ -1,6 +1,8 @@
 pub mod common;
-use crate::common::{assert_cmd_stdout, assert_files_eq, setup};
+use crate::common::{assert_cmd_stdout, assert_files_eq, base_cmd, setup};
 
+use constants::ALICE_SEED_PHRASE;
+use definitions::qr_transfers::ContentLoadMetaCompressed;
 use std::path::PathBuf;
 use tempfile::tempdir;
 
@@ -38,3 +40,108 @@ fn it_signs() {
     assert_cmd_stdout(&sign_cmd, "");
     // Signing result is not deterministic, so we can't compare the result to a known
 }
+
+#[test]
+fn it_signs_with_keystore() {
+    let files_dir = tempdir().unwrap();
+    let db = sled::open(&files_dir).unwrap();
+
+    setup(&db);
+    drop(db);
+    let cmd = format!(
+        "load-metadata -f -a --hot-db-path {0} --files-dir {0}",
+        files_dir.path().to_string_lossy()
+    );
+    assert_cmd_stdout(&cmd, "");
+
+    let keystore = files_dir.path().join("alice.json");
+    base_cmd()
+        .args(["make-keystore", "--encryption", "ed25519", "--output"])
+        .arg(&keystore)
+        .write_stdin(format!("{ALICE_SEED_PHRASE}//Alice\npassword\n"))
+        .assert()
+        .success()
+        .stdout(format!(
+            "Keystore {} made for verifier \
+            88dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee\n",
+            keystore.display()
+        ));
+    assert!(!std::fs::read_to_string(&keystore)
+        .unwrap()
+        .contains(ALICE_SEED_PHRASE));
+
+    let sign_cmd = format!(
+        "make --goal text --msg load-metadata --verifier-alice ed25519 \
+        --payload sign_me_load_metadata_polkadotV30 --files-dir {0} --export-dir {0}",
+        files_dir.path().to_string_lossy()
+    );
+    assert_cmd_stdout(&sign_cmd, "");
+
+    let sign_cmd = format!(
+        "make --goal text --msg load-metadata --keystore {1} \
+        --payload sign_me_load_metadata_polkadotV30 --files-dir {0} --export-dir {0}",
+        files_dir.path().to_string_lossy(),
+        keystore.to_string_lossy()
+    );
+    base_cmd()
+        .args(sign_cmd.split(' ').collect::<Vec<&str>>())
+        .write_stdin("password\n")
+        .assert()
+        .success()
+        .stdout("");
+
+    // ed25519 signatures are deterministic
+    let alice = files_dir
+        .path()
+        .join("load_metadata_polkadotV30_Alice-ed25519.txt");
+    let signed = files_dir.path().join("load_metadata_polkadotV30.txt");
+    assert_files_eq(&signed, &alice);
+
+    base_cmd()
+        .args(sign_cmd.split(' ').collect::<Vec<&str>>())
+        .write_stdin("wrong password\n")
+        .assert()
+        .failure()
+        .stderr("An error occurred: Keystore could not be decrypted, wrong password.\n");
+}
+
+#[test]
+fn it_makes_compressed() {
+    let files_dir = tempdir().unwrap();
+    let db = sled::open(&files_dir).unwrap();
+
+    setup(&db);
+    drop(db);
+    let cmd = format!(
+        "load-metadata -f -a --hot-db-path {0} --files-dir {0}",
+        files_dir.path().to_string_lossy()
+    );
+    assert_cmd_stdout(&cmd, "");
+
+    let make_cmd = format!(
+        "make --goal text --msg load-metadata --compress zstd \
+        --payload sign_me_load_metadata_polkadotV30 --files-dir {0} --export-dir {0}",
+        files_dir.path().to_string_lossy()
+    );
+    assert_cmd_stdout(&make_cmd, "");
+
+    let compressed = std::fs::read_to_string(
+        files_dir
+            .path()
+            .join("load_metadata_polkadotV30_zstd_unverified.txt"),
+    )
+    .unwrap();
+    let compressed = hex::decode(compressed).unwrap();
+    assert_eq!(compressed[..3], [0x53, 0xff, 0x82]);
+
+    let expected =
+        std::fs::read_to_string("./tests/for_tests/load_metadata_polkadotV30_unverified.txt")
+            .unwrap();
+    let expected = hex::decode(expected).unwrap();
+    assert!(compressed.len() < expected.len());
+
+    let (content, tail) =
+        ContentLoadMetaCompressed::from_slice_with_tail(&compressed[3..]).unwrap();
+    assert!(tail.is_empty());
+    assert_eq!(content.decompress().unwrap().to_transfer(), expected[3..]);
+}
@@ -0,0 +1,58 @@
+pub mod common;
+
+use crate::common::{assert_cmd_stdout, assert_files_eq, setup};
+
+use tempfile::tempdir;
+
+#[test]
+fn it_exports_and_imports_networks() {
+    let tmp_dir = tempdir().unwrap();
+    let db_path = tmp_dir.path().join("hot_db");
+    let db = sled::open(&db_path).unwrap();
+    setup(&db);
+    drop(db);
+
+    for format in ["toml", "json"] {
+        let exported = tmp_dir.path().join(format!("networks.{format}"));
+        assert_cmd_stdout(
+            &format!(
+                "export --file {} --hot-db-path {}",
+                exported.to_string_lossy(),
+                db_path.to_string_lossy()
+            ),
+            "",
+        );
+
+        let new_db_path = tmp_dir.path().join(format!("hot_db_{format}"));
+        assert_cmd_stdout(
+            &format!(
+                "import --file {} --hot-db-path {}",
+                exported.to_string_lossy(),
+                new_db_path.to_string_lossy()
+            ),
+            "",
+        );
+        assert_cmd_stdout(
+            &format!(
+                "show networks --hot-db-path {}",
+                new_db_path.to_string_lossy()
+            ),
+            "Address book has entries for following networks:\n
+polkadot at wss://rpc.polkadot.io, encryption sr25519, Vault display title Polkadot\n",
+        );
+
+        let reexported = tmp_dir.path().join(format!("networks_again.{format}"));
+        assert_cmd_stdout(
+            &format!(
+                "export --file {} --hot-db-path {}",
+                reexported.to_string_lossy(),
+                new_db_path.to_string_lossy()
+            ),
+            "",
+        );
+        assert_files_eq(&exported, &reexported);
+    }
+
+    let toml = std::fs::read_to_string(tmp_dir.path().join("networks.toml")).unwrap();
+    assert!(toml.starts_with("[[network]]\nname = \"polkadot\"\nencryption = \"sr25519\"\n"));
+}
@@ -0,0 +1,80 @@
+pub mod common;
+use crate::common::{assert_cmd_stdout, base_cmd, setup};
+
+use tempfile::tempdir;
+
+#[test]
+fn it_prunes_by_retention_policy() {
+    let files_dir = tempdir().unwrap();
+    let db = sled::open(&files_dir).unwrap();
+
+    setup(&db);
+    drop(db);
+    let db_path = files_dir.path().to_string_lossy();
+
+    assert_cmd_stdout(
+        &format!("prune --hot-db-path {db_path}"),
+        "Metadata entries to remove: none
+Block history entries to remove: none
+",
+    );
+
+    assert_cmd_stdout(&format!("retention keep 1 --hot-db-path {db_path}"), "");
+    assert_cmd_stdout(
+        &format!("prune --hot-db-path {db_path}"),
+        "Metadata entries to remove: westend 9000
+Block history entries to remove: none
+Nothing removed, use `--apply` to remove the entries.
+",
+    );
+
+    assert_cmd_stdout(
+        &format!("retention pin --name westend --version 9000 --hot-db-path {db_path}"),
+        "",
+    );
+    assert_cmd_stdout(
+        &format!("retention show --hot-db-path {db_path}"),
+        "Keep 1 latest metadata versions for each network.
+Pinned metadata: westend 9000
+Block ranges: none
+",
+    );
+    assert_cmd_stdout(
+        &format!("prune --hot-db-path {db_path}"),
+        "Metadata entries to remove: none
+Block history entries to remove: none
+",
+    );
+
+    assert_cmd_stdout(
+        &format!("retention unpin --name westend --version 9000 --hot-db-path {db_path}"),
+        "",
+    );
+    assert_cmd_stdout(
+        &format!("prune --apply --hot-db-path {db_path}"),
+        "Metadata entries to remove: westend 9000
+Block history entries to remove: none
+Entries removed.
+",
+    );
+    assert_cmd_stdout(
+        &format!("show metadata --hot-db-path {db_path}"),
+        "Database has metadata information for following networks:
+
+kusama 2030, metadata hash efaa97434a2e971067e5819f6f80e892daeb2711ac0544a4e8260d4ff0c14270, no block hash on record
+westend 9010, metadata hash 70c99738c27fb32c87883f1c9c94ee454bf0b3d88e4a431a2bbfe1222b46ebdf, no block hash on record
+polkadot 30, metadata hash 93b9065e4a6b8327ca1ce90e9ac3d7d967a660dcc5cda408e2595aa3e5c1ab46, no block hash on record
+",
+    );
+
+    // block ranges need the network in the address book
+    base_cmd()
+        .args(
+            format!("retention keep-blocks --name westend --from 1 --to 2 --hot-db-path {db_path}")
+                .split(' '),
+        )
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr("An error occurred: Could not find address book entry for network name westend\n");
+}
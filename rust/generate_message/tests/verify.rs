@@ -0,0 +1,74 @@
+pub mod common;
+use crate::common::{assert_cmd_stdout, base_cmd, setup};
+
+use tempfile::tempdir;
+
+#[test]
+fn it_verifies_unsigned() {
+    assert_cmd_stdout(
+        "verify ./tests/for_tests/load_metadata_polkadotV30_unverified.txt",
+        "Payload: load_metadata
+Verifier: none, payload is not signed
+Metadata: polkadot version 30, genesis hash 91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3, metadata hash 93b9065e4a6b8327ca1ce90e9ac3d7d967a660dcc5cda408e2595aa3e5c1ab46
+",
+    );
+}
+
+#[test]
+fn it_verifies_signed_and_qr() {
+    let files_dir = tempdir().unwrap();
+    let db = sled::open(&files_dir).unwrap();
+
+    setup(&db);
+    drop(db);
+    let cmd = format!(
+        "load-metadata -f -a --hot-db-path {0} --files-dir {0}",
+        files_dir.path().to_string_lossy()
+    );
+    assert_cmd_stdout(&cmd, "");
+
+    let sign_cmd = format!(
+        "make --msg load-metadata --verifier-alice sr25519 \
+        --payload sign_me_load_metadata_polkadotV30 --files-dir {0} --export-dir {0}",
+        files_dir.path().to_string_lossy()
+    );
+    assert_cmd_stdout(&sign_cmd, "");
+
+    let text = files_dir
+        .path()
+        .join("load_metadata_polkadotV30_Alice-sr25519.txt");
+    let qr = files_dir
+        .path()
+        .join("load_metadata_polkadotV30_Alice-sr25519.png");
+    let mut reports = Vec::new();
+    for file in [&text, &qr] {
+        let output = base_cmd()
+            .arg("verify")
+            .arg(file)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        reports.push(String::from_utf8(output).unwrap());
+    }
+    assert_eq!(reports[0], reports[1]);
+    assert!(reports[0].starts_with(
+        "Payload: load_metadata
+Verifier: public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d, encryption: sr25519, signature is valid
+Metadata: polkadot version 30, "
+    ));
+
+    // damage the signature
+    let mut damaged = std::fs::read_to_string(&text).unwrap();
+    let last = if damaged.ends_with('0') { "1" } else { "0" };
+    damaged.replace_range(damaged.len() - 1.., last);
+    std::fs::write(&text, damaged).unwrap();
+    base_cmd()
+        .arg("verify")
+        .arg(&text)
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr("An error occurred: Bad signature.\n");
+}
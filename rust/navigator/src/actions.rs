@@ -2,9 +2,12 @@
 
 //use super::screens::Screen;
 //use crate::navstate::{Navstate, State};
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
 
 ///All actions
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Action {
     Start,
     NavbarLog,
@@ -44,3 +47,52 @@ pub enum Action {
     PushWord,
     Nothing,
 }
+
+/// Action by its variant name, case-insensitive.
+impl FromStr for Action {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let action = match s.to_lowercase().as_str() {
+            "start" => Action::Start,
+            "navbarlog" => Action::NavbarLog,
+            "navbarscan" => Action::NavbarScan,
+            "navbarkeys" => Action::NavbarKeys,
+            "navbarsettings" => Action::NavbarSettings,
+            "goback" => Action::GoBack,
+            "goforward" => Action::GoForward,
+            "selectseed" => Action::SelectSeed,
+            "selectkey" => Action::SelectKey,
+            "newkey" => Action::NewKey,
+            "rightbuttonaction" => Action::RightButtonAction,
+            "shield" => Action::Shield,
+            "newseed" => Action::NewSeed,
+            "recoverseed" => Action::RecoverSeed,
+            "backupseed" => Action::BackupSeed,
+            "networkselector" => Action::NetworkSelector,
+            "checkpassword" => Action::CheckPassword,
+            "transactionfetched" => Action::TransactionFetched,
+            "removenetwork" => Action::RemoveNetwork,
+            "removemetadata" => Action::RemoveMetadata,
+            "removetypes" => Action::RemoveTypes,
+            "signnetworkspecs" => Action::SignNetworkSpecs,
+            "signmetadata" => Action::SignMetadata,
+            "signtypes" => Action::SignTypes,
+            "managenetworks" => Action::ManageNetworks,
+            "viewgeneralverifier" => Action::ViewGeneralVerifier,
+            "managemetadata" => Action::ManageMetadata,
+            "removekey" => Action::RemoveKey,
+            "removeseed" => Action::RemoveSeed,
+            "clearlog" => Action::ClearLog,
+            "createlogcomment" => Action::CreateLogComment,
+            "showlogdetails" => Action::ShowLogDetails,
+            "increment" => Action::Increment,
+            "showdocuments" => Action::ShowDocuments,
+            "textentry" => Action::TextEntry,
+            "pushword" => Action::PushWord,
+            "nothing" => Action::Nothing,
+            _ => return Err(Error::UnknownAction(s.to_string())),
+        };
+        Ok(action)
+    }
+}
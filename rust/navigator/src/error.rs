@@ -49,4 +49,18 @@ pub enum Error {
 
     #[error(transparent)]
     BananaSplit(#[from] banana_recovery::Error),
+
+    #[error("Unknown action {0}")]
+    UnknownAction(String),
+
+    #[error("Scenario line {line}: {reason}")]
+    ScenarioSyntax { line: usize, reason: String },
+
+    #[error("Scenario line {line}: expected {key} `{expected}`, found `{found}`")]
+    ScenarioMismatch {
+        line: usize,
+        key: String,
+        expected: String,
+        found: String,
+    },
 }
@@ -4,7 +4,9 @@
 #![deny(unused_crate_dependencies)]
 #![deny(rustdoc::broken_intra_doc_links)]
 
-use db_handling::identities::{export_key_set_addrs, SignaturesBulk, SignaturesBulkV1};
+use db_handling::identities::{
+    export_key_set_addrs, get_key_label, SignaturesBulk, SignaturesBulkV1,
+};
 //do we support mutex?
 use lazy_static::lazy_static;
 use sp_runtime::MultiSignature;
@@ -18,6 +20,7 @@ use definitions::navigation::{
     MSignedTransaction, MSufficientCryptoReady, MTransaction, QrData, TransactionAction,
     TransactionSignAction, TransactionType,
 };
+use definitions::payload::{PayloadCrypto, PayloadKind, Prelude};
 use parity_scale_codec::Encode;
 use qrcode_rtx::make_data_packs;
 
@@ -34,8 +37,10 @@ use definitions::keyring::AddressKey;
 pub mod alerts;
 pub mod modals;
 mod navstate;
+pub mod scenario;
+pub mod session;
 mod states;
-use navstate::State;
+pub use session::Session;
 use transaction_parsing::parse_transaction::parse_dd_transaction;
 
 pub mod screens;
@@ -46,11 +51,13 @@ pub use crate::error::{Error, Result};
 
 //TODO: multithread here some day!
 lazy_static! {
-    /// Navigation state of the app
+    /// Navigation session of the app, used by global functions
     ///
     /// Navigation state is unsafe either way, since it has to persist
     /// No matter if here or beyond FFI
-    pub static ref STATE: Mutex<Option<State>> = Mutex::new(
+    ///
+    /// Independent sessions could be made with [`Session`]
+    pub static ref STATE: Mutex<Option<Session>> = Mutex::new(
         None
     );
 }
@@ -65,7 +72,7 @@ pub fn do_action(
     secret_seed_phrase: &str,
 ) -> Result<ActionResult> {
     let mut navstate = STATE.lock().map_err(|_| Error::MutexPoisoned)?;
-    navstate.as_mut().ok_or(Error::DbNotInitialized)?.perform(
+    navstate.as_mut().ok_or(Error::DbNotInitialized)?.do_action(
         action,
         details_str,
         secret_seed_phrase,
@@ -75,7 +82,7 @@ pub fn do_action(
 /// Should be called in the beginning to recall things stored only by phone
 pub fn init_navigation(db: sled::Db, seed_names: Vec<String>) -> Result<()> {
     let mut navstate = STATE.lock().map_err(|_| Error::MutexPoisoned)?;
-    *navstate = Some(State::init_navigation(db, seed_names));
+    *navstate = Some(Session::new(db, seed_names));
     Ok(())
 }
 
@@ -98,7 +105,12 @@ pub fn export_key_info(
 ) -> Result<MKeysInfoExport> {
     let export_all_addrs = export_key_set_addrs(database, seed_name, exported_set)?;
 
-    let data = [&[0x53, 0xff, 0xde], export_all_addrs.encode().as_slice()].concat();
+    let prelude = Prelude::new(PayloadCrypto::Unsigned, PayloadKind::Derivations);
+    let data = [
+        prelude.to_bytes().as_slice(),
+        export_all_addrs.encode().as_slice(),
+    ]
+    .concat();
     let frames = make_data_packs(&data, 128).map_err(|e| Error::DataPacking(e.to_string()))?;
 
     Ok(MKeysInfoExport { frames })
@@ -249,6 +261,7 @@ pub fn sign_sufficient_content(
                 ),
                 seed_name: address_details.seed_name,
                 secret_exposed: address_details.secret_exposed,
+                label: get_key_label(database, address_key)?,
             },
         },
         sufficient,
@@ -24,7 +24,7 @@ use crate::screens::{
     SufficientCryptoState,
 };
 use crate::states::{SignResult, TransactionState};
-use db_handling::interface_signer::{get_all_seed_names_with_identicons, guess};
+use db_handling::interface_signer::get_all_seed_names_with_identicons;
 use definitions::{
     keyring::{AddressKey, NetworkSpecsKey},
     network_specs::Verifier,
@@ -78,6 +78,10 @@ impl State {
         self.seed_names = seed_names;
     }
 
+    pub fn db(&self) -> &sled::Db {
+        &self.db
+    }
+
     fn handle_navbar_log(&self) -> (Navstate, String) {
         let mut new_navstate = self.navstate.to_owned();
         let errorline = String::new();
@@ -1233,7 +1237,7 @@ impl State {
             Screen::RecoverSeedPhrase(ref recover_seed_phrase_state) => {
                 let draft = recover_seed_phrase_state.draft();
                 let user_input = draft.user_input();
-                let guess_set = guess(user_input);
+                let guess_set = draft.guess(user_input);
                 let ready_seed = draft.try_finalize();
                 let mut draft = draft.draft();
 
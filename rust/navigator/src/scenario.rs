@@ -0,0 +1,402 @@
+//! Navigation scenarios
+//!
+//! Scenario is a text description of a navigation flow: user actions and the
+//! expected results, one step per line. Scenarios are replayed with
+//! [`Scenario::run`] and recorded from a session with [`Recorder`], so that the
+//! flows could be added and reviewed without writing Rust.
+//!
+//! Lines:
+//!
+//! - `# <comment>`, empty lines are skipped as well
+//! - `seeds <seed_name> <seed_name> ...`: seed names known to the phone, as in
+//!   [`update_seed_names`](crate::update_seed_names)
+//! - `secret <seed_phrase>`: seed phrase for the next action; several
+//!   `secret` lines are joined into multiline secret, as is needed for
+//!   signing several transactions
+//! - `action <action> [details]`: perform [`Action`], by variant name, with
+//!   optional details string, the rest of the line
+//! - `expect <key> [value]`: check the result of the last action
+//!
+//! Keys for `expect`:
+//!
+//! - `screen`: screen data variant, for example `SeedSelector`
+//! - `modal`, `alert`: modal data and alert data variant, or `none`
+//! - `label`: screen label, could be empty
+//! - `back`, `footer`: `true` or `false`
+//! - `footer_button`, `right_button`: button variant, or `none`
+//! - `contains`: text that must be found in the screen, modal or alert data
+//!   in `Debug` format
+//! - `error`: the action must fail with error containing the text
+//!
+//! Action failure without `expect error` check fails the scenario.
+//!
+//! Example:
+//!
+//! ```text
+//! # new seed with a given seed phrase
+//! action Start
+//! expect screen SeedSelector
+//! expect modal NewSeedMenu
+//! action NewSeed
+//! action GoForward Alice
+//! expect modal NewSeedBackup
+//! secret bottom drive obey lake curtain smoke basket hold race lonely fit walk
+//! action GoForward true
+//! expect screen Keys
+//! ```
+use std::fmt::Write;
+
+use definitions::navigation::ActionResult;
+
+use crate::actions::Action;
+use crate::error::{Error, Result};
+use crate::session::Session;
+
+/// Parsed scenario.
+#[derive(Debug)]
+pub struct Scenario {
+    steps: Vec<Step>,
+}
+
+/// Single scenario step, with the line number in scenario text.
+#[derive(Debug)]
+struct Step {
+    line: usize,
+    content: StepContent,
+}
+
+#[derive(Debug)]
+enum StepContent {
+    SeedNames(Vec<String>),
+    Action {
+        action: Action,
+        details: String,
+        secret: String,
+    },
+    Expect {
+        key: Key,
+        value: String,
+    },
+}
+
+/// Checked part of the action result.
+#[derive(Debug, Clone, Copy)]
+enum Key {
+    Screen,
+    Modal,
+    Alert,
+    Label,
+    Back,
+    Footer,
+    FooterButton,
+    RightButton,
+    Contains,
+    Error,
+}
+
+impl Key {
+    fn from_name(name: &str) -> Option<Self> {
+        let key = match name {
+            "screen" => Key::Screen,
+            "modal" => Key::Modal,
+            "alert" => Key::Alert,
+            "label" => Key::Label,
+            "back" => Key::Back,
+            "footer" => Key::Footer,
+            "footer_button" => Key::FooterButton,
+            "right_button" => Key::RightButton,
+            "contains" => Key::Contains,
+            "error" => Key::Error,
+            _ => return None,
+        };
+        Some(key)
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Key::Screen => "screen",
+            Key::Modal => "modal",
+            Key::Alert => "alert",
+            Key::Label => "label",
+            Key::Back => "back",
+            Key::Footer => "footer",
+            Key::FooterButton => "footer_button",
+            Key::RightButton => "right_button",
+            Key::Contains => "contains",
+            Key::Error => "error",
+        }
+    }
+
+    /// Value of the key in action result, as written in scenario.
+    fn found(&self, action_result: &ActionResult) -> String {
+        match self {
+            Key::Screen => variant_name(&action_result.screen_data),
+            Key::Modal => optional_variant_name(&action_result.modal_data),
+            Key::Alert => optional_variant_name(&action_result.alert_data),
+            Key::Label => action_result.screen_label.to_string(),
+            Key::Back => action_result.back.to_string(),
+            Key::Footer => action_result.footer.to_string(),
+            Key::FooterButton => optional_variant_name(&action_result.footer_button),
+            Key::RightButton => optional_variant_name(&action_result.right_button),
+            Key::Contains => format!(
+                "{:?} {:?} {:?}",
+                action_result.screen_data, action_result.modal_data, action_result.alert_data
+            ),
+            Key::Error => String::from("no error"),
+        }
+    }
+}
+
+/// Keys recorded by [`Recorder`] after each action.
+const RECORDED_KEYS: [Key; 4] = [Key::Screen, Key::Label, Key::Modal, Key::Alert];
+
+impl Scenario {
+    /// Parse scenario text.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut steps = Vec::new();
+        let mut secret: Vec<&str> = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (word, rest) = match line.split_once(' ') {
+                Some((word, rest)) => (word, rest.trim()),
+                None => (line, ""),
+            };
+            let content = match word {
+                "seeds" => {
+                    StepContent::SeedNames(rest.split_whitespace().map(String::from).collect())
+                }
+                "secret" => {
+                    secret.push(rest);
+                    continue;
+                }
+                "action" => {
+                    let (action, details) = rest.split_once(' ').unwrap_or((rest, ""));
+                    let action = action
+                        .parse::<Action>()
+                        .map_err(|e| Error::ScenarioSyntax {
+                            line: line_number,
+                            reason: e.to_string(),
+                        })?;
+                    StepContent::Action {
+                        action,
+                        details: details.to_string(),
+                        secret: secret.join("\n"),
+                    }
+                }
+                "expect" => {
+                    let (key, value) = rest.split_once(' ').unwrap_or((rest, ""));
+                    let key = Key::from_name(key).ok_or_else(|| Error::ScenarioSyntax {
+                        line: line_number,
+                        reason: format!("unknown key {key}"),
+                    })?;
+                    StepContent::Expect {
+                        key,
+                        value: value.trim().to_string(),
+                    }
+                }
+                _ => {
+                    return Err(Error::ScenarioSyntax {
+                        line: line_number,
+                        reason: format!("unknown step {word}"),
+                    })
+                }
+            };
+            if !secret.is_empty() && !matches!(content, StepContent::Action { .. }) {
+                return Err(Error::ScenarioSyntax {
+                    line: line_number,
+                    reason: String::from("secret must be followed by an action"),
+                });
+            }
+            if let StepContent::Action { .. } = content {
+                secret.clear();
+            }
+            steps.push(Step {
+                line: line_number,
+                content,
+            });
+        }
+        if !secret.is_empty() {
+            return Err(Error::ScenarioSyntax {
+                line: text.lines().count(),
+                reason: String::from("secret must be followed by an action"),
+            });
+        }
+        Ok(Self { steps })
+    }
+
+    /// Replay scenario in the session.
+    ///
+    /// Session is expected to start with a fresh database.
+    pub fn run(&self, session: &mut Session) -> Result<()> {
+        // result of the last action, with errors turned into text, and the
+        // line of the action
+        let mut last: Option<(usize, std::result::Result<ActionResult, String>)> = None;
+        let mut error_checked = false;
+        for step in self.steps.iter() {
+            match step.content {
+                StepContent::SeedNames(ref seed_names) => {
+                    session.update_seed_names(seed_names.to_vec())
+                }
+                StepContent::Action {
+                    action,
+                    ref details,
+                    ref secret,
+                } => {
+                    check_error_expected(&last, error_checked)?;
+                    let result = session
+                        .do_action(action, details, secret)
+                        .map_err(|e| e.to_string());
+                    last = Some((step.line, result));
+                    error_checked = false;
+                }
+                StepContent::Expect { key, ref value } => {
+                    let found = match last {
+                        None => String::from("no action"),
+                        Some((_, Ok(ref action_result))) => key.found(action_result),
+                        Some((_, Err(ref e))) => {
+                            if let Key::Error = key {
+                                error_checked = true;
+                            }
+                            e.to_string()
+                        }
+                    };
+                    let matched = match (key, &last) {
+                        (Key::Contains, Some((_, Ok(_)))) | (Key::Error, Some((_, Err(_)))) => {
+                            found.contains(value.as_str())
+                        }
+                        _ => &found == value,
+                    };
+                    if !matched {
+                        return Err(Error::ScenarioMismatch {
+                            line: step.line,
+                            key: key.name().to_string(),
+                            expected: value.to_string(),
+                            found,
+                        });
+                    }
+                }
+            }
+        }
+        check_error_expected(&last, error_checked)
+    }
+}
+
+/// Failed action must be followed by `expect error` check.
+fn check_error_expected(
+    last: &Option<(usize, std::result::Result<ActionResult, String>)>,
+    error_checked: bool,
+) -> Result<()> {
+    match last {
+        Some((line, Err(e))) if !error_checked => Err(Error::ScenarioMismatch {
+            line: *line,
+            key: Key::Error.name().to_string(),
+            expected: String::from("no error"),
+            found: e.to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Session wrapper, recording performed actions and their results as
+/// scenario.
+///
+/// Seed phrases passed into actions are recorded as well, recorder is
+/// intended for test seeds only.
+pub struct Recorder {
+    session: Session,
+    text: String,
+}
+
+impl Recorder {
+    /// Start recording the session.
+    pub fn new(session: Session) -> Self {
+        Self {
+            session,
+            text: String::new(),
+        }
+    }
+
+    /// Perform action in the session and record it.
+    pub fn do_action(
+        &mut self,
+        action: Action,
+        details_str: &str,
+        secret_seed_phrase: &str,
+    ) -> Result<ActionResult> {
+        record_action(&mut self.text, action, details_str, secret_seed_phrase);
+        let result = self
+            .session
+            .do_action(action, details_str, secret_seed_phrase);
+        record_result(&mut self.text, &result);
+        result
+    }
+
+    /// Synchronize seed names in the session and record them.
+    pub fn update_seed_names(&mut self, seed_names: Vec<String>) {
+        record_seed_names(&mut self.text, &seed_names);
+        self.session.update_seed_names(seed_names)
+    }
+
+    /// Recorded scenario text.
+    pub fn scenario(&self) -> &str {
+        &self.text
+    }
+
+    /// Recorded session.
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+}
+
+/// Record action with its seed phrase and details.
+fn record_action(text: &mut String, action: Action, details_str: &str, secret_seed_phrase: &str) {
+    for secret_line in secret_seed_phrase.lines() {
+        let _ = writeln!(text, "secret {secret_line}");
+    }
+    let _ = writeln!(
+        text,
+        "{}",
+        format!("action {action:?} {details_str}").trim_end()
+    );
+}
+
+/// Record action result as `expect` checks.
+fn record_result(text: &mut String, result: &Result<ActionResult>) {
+    match result {
+        Ok(ref action_result) => {
+            for key in RECORDED_KEYS.iter() {
+                let line = format!("expect {} {}", key.name(), key.found(action_result));
+                let _ = writeln!(text, "{}", line.trim_end());
+            }
+        }
+        Err(ref e) => {
+            let _ = writeln!(text, "expect error {e}");
+        }
+    }
+}
+
+/// Record seed names synchronization.
+fn record_seed_names(text: &mut String, seed_names: &[String]) {
+    let _ = writeln!(text, "seeds {}", seed_names.join(" "));
+}
+
+/// Name of the enum variant, as in `Debug` format.
+fn variant_name<T: std::fmt::Debug>(value: &T) -> String {
+    format!("{value:?}")
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Name of the enum variant, or `none`.
+fn optional_variant_name<T: std::fmt::Debug>(value: &Option<T>) -> String {
+    match value {
+        Some(a) => variant_name(a),
+        None => String::from("none"),
+    }
+}
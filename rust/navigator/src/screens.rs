@@ -441,6 +441,7 @@ impl SufficientCryptoState {
                 path: address_details.path.clone(),
                 has_pwd: address_details.has_pwd,
                 secret_exposed: address_details.secret_exposed,
+                label: None,
             },
         };
         Self {
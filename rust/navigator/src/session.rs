@@ -0,0 +1,54 @@
+//! Navigation session
+//!
+//! [`Session`] owns the database and the navigation state, so that several
+//! independent sessions could exist in one process, for example for several
+//! simulated devices in a test harness.
+//!
+//! Global functions [`do_action`](crate::do_action),
+//! [`init_navigation`](crate::init_navigation) and
+//! [`update_seed_names`](crate::update_seed_names) work with the single
+//! session stored in [`STATE`](crate::STATE).
+
+use definitions::navigation::ActionResult;
+
+use crate::actions::Action;
+use crate::error::Result;
+use crate::navstate::State;
+
+/// Navigation session, with its own database and navigation state.
+#[derive(Clone)]
+pub struct Session {
+    state: State,
+}
+
+impl Session {
+    /// Start new session.
+    ///
+    /// Accepts list of seed names stored by the phone.
+    pub fn new(db: sled::Db, seed_names: Vec<String>) -> Self {
+        Self {
+            state: State::init_navigation(db, seed_names),
+        }
+    }
+
+    /// User actions handler, same as [`do_action`](crate::do_action) for the
+    /// global session.
+    pub fn do_action(
+        &mut self,
+        action: Action,
+        details_str: &str,
+        secret_seed_phrase: &str,
+    ) -> Result<ActionResult> {
+        self.state.perform(action, details_str, secret_seed_phrase)
+    }
+
+    /// Synchronize seed names modified in native.
+    pub fn update_seed_names(&mut self, seed_names: Vec<String>) {
+        self.state.update_seed_names(seed_names)
+    }
+
+    /// Database of the session.
+    pub fn db(&self) -> &sled::Db {
+        self.state.db()
+    }
+}
@@ -60,9 +60,10 @@ use transaction_signing::SufficientContent;
 use crate::{
     handle_dd_sign, keys_by_seed_name,
     navstate::State,
+    scenario::{Recorder, Scenario},
     sign_dd_transaction, sign_sufficient_content,
     states::{SignResult, TransactionState},
-    Action, Error,
+    Action, Error, Session,
 };
 
 const ALICE: [u8; 32] = [
@@ -754,6 +755,109 @@ fn export_import_substrate_and_ethereum_addrs() {
     assert_eq!(addrs_new, addrs_expected);
 }
 
+#[test]
+fn independent_sessions() {
+    let db_a = sled::open(tempdir().unwrap().into_path()).unwrap();
+    populate_cold_nav_test(&db_a).unwrap();
+    init_db(&db_a, verifier_alice_sr25519()).unwrap();
+    let db_b = sled::open(tempdir().unwrap().into_path()).unwrap();
+    populate_cold_nav_test(&db_b).unwrap();
+    init_db(&db_b, verifier_alice_sr25519()).unwrap();
+
+    let mut session_a = Session::new(db_a, vec![]);
+    let mut session_b = Session::new(db_b, vec![]);
+    session_a.do_action(Action::Start, "", "").unwrap();
+    session_b.do_action(Action::Start, "", "").unwrap();
+
+    let action = session_a.do_action(Action::NavbarSettings, "", "").unwrap();
+    assert!(matches!(action.screen_data, ScreenData::Settings { .. }));
+
+    // other session stays where it was
+    let action = session_b.do_action(Action::Nothing, "", "").unwrap();
+    assert!(matches!(
+        action.screen_data,
+        ScreenData::SeedSelector { .. }
+    ));
+}
+
+fn scenario_db() -> sled::Db {
+    let db = sled::open(tempdir().unwrap().into_path()).unwrap();
+    populate_cold_nav_test(&db).unwrap();
+    init_db(&db, Verifier { v: None }).unwrap();
+    db
+}
+
+#[test]
+fn scenarios_replay() {
+    let scenarios_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/scenarios");
+    let mut replayed = 0;
+    for entry in fs::read_dir(scenarios_dir).unwrap() {
+        let path = entry.unwrap().path();
+        let scenario = Scenario::parse(&fs::read_to_string(&path).unwrap()).unwrap();
+        let mut session = Session::new(scenario_db(), vec![]);
+        if let Err(e) = scenario.run(&mut session) {
+            panic!("Scenario {} failed. {e}", path.display())
+        }
+        replayed += 1;
+    }
+    assert!(replayed > 0);
+}
+
+#[test]
+fn recorded_scenario_replays() {
+    let mut recorder = Recorder::new(Session::new(scenario_db(), vec![]));
+    recorder.do_action(Action::Start, "", "").unwrap();
+    recorder.do_action(Action::NewSeed, "", "").unwrap();
+    recorder.do_action(Action::GoForward, "Alice", "").unwrap();
+    recorder
+        .do_action(Action::GoForward, "true", ALICE_SEED_PHRASE)
+        .unwrap();
+    recorder.update_seed_names(vec![String::from("Alice")]);
+    recorder.do_action(Action::NavbarLog, "", "").unwrap();
+    recorder.do_action(Action::NavbarKeys, "", "").unwrap();
+
+    let text = recorder.scenario().to_string();
+    assert!(text.contains(&format!(
+        "secret {ALICE_SEED_PHRASE}\naction GoForward true\nexpect screen Keys\n"
+    )));
+    assert!(text.contains("seeds Alice\n"));
+
+    let scenario = Scenario::parse(&text).unwrap();
+    let mut session = Session::new(scenario_db(), vec![]);
+    scenario.run(&mut session).unwrap();
+}
+
+#[test]
+fn scenario_mismatch() {
+    let text = "action Start\nexpect screen SeedSelector\nexpect modal none\n";
+    let scenario = Scenario::parse(text).unwrap();
+    let mut session = Session::new(scenario_db(), vec![]);
+    match scenario.run(&mut session) {
+        Err(Error::ScenarioMismatch {
+            line,
+            key,
+            expected,
+            found,
+        }) => {
+            assert_eq!(line, 3);
+            assert_eq!(key, "modal");
+            assert_eq!(expected, "none");
+            assert_eq!(found, "NewSeedMenu");
+        }
+        other => panic!("Unexpected scenario result: {other:?}"),
+    }
+
+    let text = "action Start\nexpect screen SeedSelector\nsecret word\nexpect screen Keys\n";
+    assert!(matches!(
+        Scenario::parse(text),
+        Err(Error::ScenarioSyntax { line: 4, .. })
+    ));
+    assert!(matches!(
+        Scenario::parse("action Jump\n"),
+        Err(Error::ScenarioSyntax { line: 1, .. })
+    ));
+}
+
 #[test]
 #[ignore]
 fn flow_test_1() {
@@ -3180,6 +3284,7 @@ fn flow_test_1() {
                     path: "//polkadot".to_string(),
                     has_pwd: false,
                     secret_exposed: false,
+                    label: None,
                 },
                 base58: "16Zaf6BT6xc6WeYCX6YNAf67RumWaEiumwawt7cTdKMU7HqW".to_string(),
                 network_info: MSCNetworkInfo {
@@ -4033,6 +4138,7 @@ fn flow_test_1() {
                     path: String::new(),
                     has_pwd: false,
                     secret_exposed: false,
+                    label: None,
                 },
                 network_info: MSCNetworkInfo {
                     network_title: "Westend".to_string(),
@@ -4215,6 +4321,7 @@ fn flow_test_1() {
                             has_pwd: false,
                             path: "//westend".to_string(),
                             secret_exposed: false,
+                            label: None,
                         },
                         network_logo: "westend".to_string(),
                     },
@@ -4233,6 +4340,7 @@ fn flow_test_1() {
                             has_pwd: false,
                             path: "".to_string(),
                             secret_exposed: false,
+                            label: None,
                         },
                         network_logo: "westend".to_string(),
                     },
@@ -4245,6 +4353,7 @@ fn flow_test_1() {
                             has_pwd: false,
                             path: "//kusama".to_string(),
                             secret_exposed: false,
+                            label: None,
                         },
                         address_key:
                             "0164a31235d4bf9b37cfed3afa8aa60754675f9c4915430454d365c05112784d05"
@@ -4269,6 +4378,7 @@ fn flow_test_1() {
                             has_pwd: false,
                             path: "//Alice/secret//secret".to_string(),
                             secret_exposed: false,
+                            label: None,
                         },
                         network_logo: "westend".to_string(),
                     },
@@ -4287,6 +4397,7 @@ fn flow_test_1() {
                             has_pwd: false,
                             path: "//Alice".to_string(),
                             secret_exposed: false,
+                            label: None,
                         },
                         network_logo: "westend".to_string(),
                     },
@@ -4299,6 +4410,7 @@ fn flow_test_1() {
                             has_pwd: true,
                             path: "//secret//path".to_string(),
                             secret_exposed: false,
+                            label: None,
                         },
                         address_key:
                             "01e83f1549880f33524079201c5c7aed839f56c73adb2f61d9b271ae2d692dfe2c"
@@ -4317,6 +4429,7 @@ fn flow_test_1() {
                             has_pwd: false,
                             path: "//polkadot".to_string(),
                             secret_exposed: false,
+                            label: None,
                         },
                         address_key:
                             "01f606519cb8726753885cd4d0f518804a69a5e0badf36fee70feadd8044081730"
@@ -4366,6 +4479,7 @@ fn flow_test_1() {
                     has_pwd: false,
 
                     secret_exposed: false,
+                    label: None,
                 },
             },
             sufficient: vec![],
@@ -4542,6 +4656,7 @@ fn flow_test_1() {
                             has_pwd: false,
                             path: "//westend".to_string(),
                             secret_exposed: false,
+                            label: None,
                         },
                         address_key:
                             "013efeca331d646d8a2986374bb3bb8d6e9e3cfcdd7c45c2b69104fab5d61d3f34"
@@ -4560,6 +4675,7 @@ fn flow_test_1() {
                             has_pwd: false,
                             path: "".to_string(),
                             secret_exposed: false,
+                            label: None,
                         },
                         address_key:
                             "0146ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a"
@@ -4578,6 +4694,7 @@ fn flow_test_1() {
                             has_pwd: false,
                             path: "//kusama".to_string(),
                             secret_exposed: false,
+                            label: None,
                         },
                         address_key:
                             "0164a31235d4bf9b37cfed3afa8aa60754675f9c4915430454d365c05112784d05"
@@ -4596,6 +4713,7 @@ fn flow_test_1() {
                             has_pwd: false,
                             path: "//Alice/secret//secret".to_string(),
                             secret_exposed: false,
+                            label: None,
                         },
                         address_key:
                             "018266a693d6872d2b6437215c198ee25cabf2e4256df9ad00e979e84b00b5235e"
@@ -4614,6 +4732,7 @@ fn flow_test_1() {
                             has_pwd: false,
                             path: "//Alice".to_string(),
                             secret_exposed: false,
+                            label: None,
                         },
                         address_key:
                             "01d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
@@ -4632,6 +4751,7 @@ fn flow_test_1() {
                             has_pwd: true,
                             path: "//secret//path".to_string(),
                             secret_exposed: false,
+                            label: None,
                         },
                         address_key:
                             "01e83f1549880f33524079201c5c7aed839f56c73adb2f61d9b271ae2d692dfe2c"
@@ -4650,6 +4770,7 @@ fn flow_test_1() {
                             has_pwd: false,
                             path: "//polkadot".to_string(),
                             secret_exposed: false,
+                            label: None,
                         },
                         address_key:
                             "01f606519cb8726753885cd4d0f518804a69a5e0badf36fee70feadd8044081730"
@@ -4679,6 +4800,7 @@ fn flow_test_1() {
                         path: String::new(),
                         has_pwd: false,
                         secret_exposed: false,
+                        label: None,
                     },
                 },
                 sufficient: vec![],
@@ -4810,6 +4932,7 @@ fn flow_test_1() {
                     has_pwd: false,
 
                     secret_exposed: false,
+                    label: None,
                 },
             },
             sufficient: vec![],
@@ -5108,6 +5231,7 @@ fn flow_test_1() {
                         path: "//Alice/secret//secret".to_string(),
                         has_pwd: false,
                         secret_exposed: false,
+                        label: None,
                     },
                 }),
                 network_info: Some(MSCNetworkInfo {
@@ -5264,6 +5388,7 @@ fn flow_test_1() {
                         path: "//westend".to_string(),
                         has_pwd: false,
                         secret_exposed: false,
+                        label: None,
                     },
                 }),
                 network_info: Some(MSCNetworkInfo {
@@ -5669,6 +5794,7 @@ fn flow_test_1() {
                         path: "//westend".to_string(),
                         has_pwd: false,
                         secret_exposed: false,
+                        label: None,
                     },
                 }),
                 network_info: Some(MSCNetworkInfo {
@@ -5861,6 +5987,7 @@ fn flow_test_1() {
                         path: "//0".to_string(),
                         has_pwd: true,
                         secret_exposed: false,
+                        label: None,
                     },
                 }),
                 network_info: Some(MSCNetworkInfo {
@@ -5902,6 +6029,7 @@ fn flow_test_1() {
                     path: "//0".to_string(),
                     has_pwd: true,
                     secret_exposed: false,
+                    label: None,
                 },
             },
             counter: 1,
@@ -5927,6 +6055,7 @@ fn flow_test_1() {
                     path: "//0".to_string(),
                     has_pwd: true,
                     secret_exposed: false,
+                    label: None,
                 },
             },
             counter: 2,
@@ -5954,6 +6083,7 @@ fn flow_test_1() {
                     path: "//0".to_string(),
                     has_pwd: true,
                     secret_exposed: false,
+                    label: None,
                 },
             },
             counter: 3,
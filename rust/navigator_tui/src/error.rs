@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Navigator(#[from] navigator::Error),
+
+    #[error(transparent)]
+    DbHandling(#[from] db_handling::Error),
+
+    #[error(transparent)]
+    QrReader(#[from] qr_reader_phone::Error),
+
+    #[error(transparent)]
+    Definitions(#[from] definitions::error::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+
+    #[error(transparent)]
+    Sled(#[from] sled::Error),
+
+    #[error("Unknown command {0}. Type `help` for the list of commands.")]
+    UnknownCommand(String),
+
+    #[error("Command {command} needs argument {argument}.")]
+    MissingArgument {
+        command: &'static str,
+        argument: &'static str,
+    },
+
+    #[error("No seed phrase for seed {0}, add it with `seed` command.")]
+    NoSeedPhrase(String),
+
+    #[error("No seed is selected.")]
+    NoSeedSelected,
+
+    #[error("File {} has no QR code with complete payload.", .0.display())]
+    QrNotFound(PathBuf),
+
+    #[error("Scanned {0} is not processed by the navigation, it is handled by the app itself.")]
+    NotNavigationPayload(&'static str),
+}
@@ -0,0 +1,316 @@
+//! Headless terminal frontend for the Vault navigation
+//!
+//! The phone apps render navigation [`ActionResult`] and send user actions
+//! into [`Session::do_action`]. This crate does the same in terminal, so that
+//! the backend could be exercised on a desktop without a phone: each input
+//! line is a command, and the resulting screen is printed as text.
+//!
+//! `$ cargo run -p navigator_tui -- --db <DB_PATH> [--new] [--record <FILE>]`
+//!
+//! With `--new` key the database is created from the release defaults and
+//! initiated with the default general verifier, as on the first start of the
+//! app. Commands are read from standard input, so that the flows could also be
+//! scripted.
+//!
+//! With `--record` key the navigation is recorded with [`Recorder`] and saved
+//! into the file on exit, as a [scenario](navigator::scenario) that could be
+//! replayed in the navigator tests. Only the navigation actions and the seed
+//! names are recorded, `derive` command is not.
+//!
+//! ## Commands
+//!
+//! - `<action> [details]`: perform navigation action, for example
+//!   `NavbarKeys`, `SelectSeed Alice` or `GoForward true`. Action names are
+//!   the [`Action`] variant names, case-insensitive.
+//! - `scan <hex|file>`: scan the payload, from a hexadecimal string, a text
+//!   file with hexadecimal string, or a QR code image file, static or animated
+//!   `.png`
+//! - `seed <seed_name> <seed_phrase>`: add seed phrase into the keychain, for
+//!   the seeds created in the database beforehand
+//! - `seeds`: list seed names in the keychain
+//! - `keys`: show keys for the seed currently selected
+//! - `derive <path> <network_specs_key>`: derive a key for the seed currently
+//!   selected
+//! - `show`: show the current screen again
+//! - `help`, `quit`
+//!
+//! ## Seed phrases
+//!
+//! The phone keeps seed phrases in the secure storage, and passes them into
+//! navigation when needed. Here the seed phrases are kept in memory for the
+//! frontend lifetime, in [`Keychain`]. Seed phrase is added automatically when
+//! a seed is created or recovered through the navigation, and removed when the
+//! seed is removed. Seed phrases are passed into actions that need them:
+//! creating and recovering seeds, signing transactions and `SufficientCrypto`.
+use clap::Parser;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use db_handling::identities::try_create_address;
+use definitions::{
+    keyring::{AddressKey, NetworkSpecsKey},
+    navigation::{ActionResult, ModalData, ScreenData, TransactionType},
+};
+use navigator::{keys_by_seed_name, scenario::Recorder, Action, Session};
+
+mod error;
+pub use error::{Error, Result};
+pub mod render;
+use render::render;
+pub mod scan;
+use scan::scanned_payload;
+
+/// Command line arguments.
+#[derive(Debug, Parser)]
+#[command(about = "Terminal frontend for Vault navigation")]
+pub struct Args {
+    /// Path to the cold database
+    #[arg(long = "db", value_name = "DB_PATH")]
+    pub db_path: PathBuf,
+
+    /// Create the database from the release defaults
+    #[arg(long)]
+    pub new: bool,
+
+    /// Record the navigation and save it into the file as scenario on exit
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+}
+
+/// Seed phrases known to the frontend, by seed name.
+#[derive(Debug, Default)]
+pub struct Keychain(BTreeMap<String, String>);
+
+impl Keychain {
+    /// Seed names, same as the phone reports into navigation.
+    pub fn seed_names(&self) -> Vec<String> {
+        self.0.keys().cloned().collect()
+    }
+
+    fn seed_phrase(&self, seed_name: &str) -> Result<&str> {
+        self.0
+            .get(seed_name)
+            .map(|x| x.as_str())
+            .ok_or_else(|| Error::NoSeedPhrase(seed_name.to_string()))
+    }
+}
+
+/// Terminal frontend state.
+pub struct Frontend {
+    session: Recorder,
+    keychain: Keychain,
+    current: Option<ActionResult>,
+}
+
+/// Outcome of a single input line.
+pub enum Outcome {
+    /// Text to print
+    Show(String),
+
+    /// Frontend should stop
+    Quit,
+}
+
+impl Frontend {
+    /// Open the database and start the navigation.
+    pub fn open(args: &Args) -> Result<Self> {
+        let database = sled::open(&args.db_path)?;
+        if args.new {
+            db_handling::default_cold_release(Some(&database))?;
+            db_handling::cold_default::signer_init_with_cert(&database)?;
+        }
+        Ok(Self::new(database))
+    }
+
+    /// Start the navigation with existing database.
+    pub fn new(database: sled::Db) -> Self {
+        Self {
+            session: Recorder::new(Session::new(database, Vec::new())),
+            keychain: Keychain::default(),
+            current: None,
+        }
+    }
+
+    /// Process a single input line.
+    pub fn execute(&mut self, line: &str) -> Result<Outcome> {
+        let line = line.trim();
+        let (command, argument) = match line.split_once(' ') {
+            Some((command, argument)) => (command, argument.trim()),
+            None => (line, ""),
+        };
+        let text = match command.to_lowercase().as_str() {
+            "" => String::new(),
+            "quit" | "exit" => return Ok(Outcome::Quit),
+            "help" => HELP.to_string(),
+            "show" => self.current.as_ref().map(render).unwrap_or_default(),
+            "scan" => {
+                if argument.is_empty() {
+                    return Err(Error::MissingArgument {
+                        command: "scan",
+                        argument: "<hex|file>",
+                    });
+                }
+                let payload = scanned_payload(argument)?;
+                self.action(Action::TransactionFetched, &payload)?
+            }
+            "seed" => {
+                let (seed_name, seed_phrase) =
+                    argument.split_once(' ').ok_or(Error::MissingArgument {
+                        command: "seed",
+                        argument: "<seed_name> <seed_phrase>",
+                    })?;
+                self.keychain
+                    .0
+                    .insert(seed_name.to_string(), seed_phrase.trim().to_string());
+                self.session.update_seed_names(self.keychain.seed_names());
+                format!("Seed {seed_name} added.\n")
+            }
+            "seeds" => self
+                .keychain
+                .seed_names()
+                .iter()
+                .map(|seed_name| format!("{seed_name}\n"))
+                .collect(),
+            "keys" => {
+                let keys = keys_by_seed_name(self.db(), &self.selected_seed()?)?;
+                format!("{keys:#?}\n")
+            }
+            "derive" => {
+                let (path, network_specs_key) =
+                    argument.split_once(' ').ok_or(Error::MissingArgument {
+                        command: "derive",
+                        argument: "<path> <network_specs_key>",
+                    })?;
+                let seed_name = self.selected_seed()?;
+                try_create_address(
+                    self.db(),
+                    &seed_name,
+                    self.keychain.seed_phrase(&seed_name)?,
+                    path,
+                    &NetworkSpecsKey::from_hex(network_specs_key.trim())?,
+                )?;
+                format!("Key {path} derived for seed {seed_name}.\n")
+            }
+            _ => {
+                let action = command
+                    .parse::<Action>()
+                    .map_err(|_| Error::UnknownCommand(command.to_string()))?;
+                self.action(action, argument)?
+            }
+        };
+        Ok(Outcome::Show(text))
+    }
+
+    /// Perform navigation action, with the seed phrase if the action needs
+    /// it, and render the result.
+    fn action(&mut self, action: Action, details: &str) -> Result<String> {
+        let (secret, new_seed) = self.secret_for(&action, details)?;
+        let removed_seed = match action {
+            Action::RemoveSeed => self.selected_seed().ok(),
+            _ => None,
+        };
+        let action_result = self.session.do_action(action, details, &secret)?;
+
+        if action_result.alert_data.is_none() {
+            let mut seeds_changed = false;
+            if let Some((seed_name, seed_phrase)) = new_seed {
+                if let ScreenData::Keys { .. } = action_result.screen_data {
+                    self.keychain.0.insert(seed_name, seed_phrase);
+                    seeds_changed = true;
+                }
+            }
+            if let Some(seed_name) = removed_seed {
+                if let ScreenData::Log { .. } = action_result.screen_data {
+                    self.keychain.0.remove(&seed_name);
+                    seeds_changed = true;
+                }
+            }
+            if seeds_changed {
+                self.session.update_seed_names(self.keychain.seed_names());
+            }
+        }
+
+        let text = render(&action_result);
+        self.current = Some(action_result);
+        Ok(text)
+    }
+
+    /// Seed phrase for the action, as the phone would provide it, and the
+    /// seed that would be added into the keychain if the action succeeds.
+    fn secret_for(
+        &self,
+        action: &Action,
+        details: &str,
+    ) -> Result<(String, Option<(String, String)>)> {
+        let current = match (action, &self.current) {
+            (Action::GoForward, Some(current)) => current,
+            _ => return Ok((String::new(), None)),
+        };
+        if let Some(ModalData::NewSeedBackup { ref f }) = current.modal_data {
+            let new_seed = (f.seed.to_string(), f.seed_phrase.to_string());
+            return Ok((f.seed_phrase.to_string(), Some(new_seed)));
+        }
+        match current.screen_data {
+            ScreenData::RecoverSeedPhrase { ref f } => {
+                let seed_phrase = f.ready_seed.clone().unwrap_or_default();
+                let new_seed = (f.seed_name.to_string(), seed_phrase.to_string());
+                Ok((seed_phrase, Some(new_seed)))
+            }
+            ScreenData::Transaction { ref f } => {
+                let mut seed_phrases = Vec::new();
+                for transaction in f.iter() {
+                    if let (TransactionType::Sign, Some(author_info)) =
+                        (&transaction.ttype, &transaction.author_info)
+                    {
+                        seed_phrases.push(
+                            self.keychain
+                                .seed_phrase(&author_info.address.seed_name)?
+                                .to_string(),
+                        )
+                    }
+                }
+                Ok((seed_phrases.join("\n"), None))
+            }
+            ScreenData::SignSufficientCrypto { .. } if current.modal_data.is_none() => {
+                let address_key = AddressKey::from_hex(details)?;
+                let address_details =
+                    db_handling::helpers::get_address_details(self.db(), &address_key)?;
+                let seed_phrase = self.keychain.seed_phrase(&address_details.seed_name)?;
+                Ok((seed_phrase.to_string(), None))
+            }
+            _ => Ok((String::new(), None)),
+        }
+    }
+
+    /// Navigation recorded so far, as scenario.
+    pub fn scenario(&self) -> &str {
+        self.session.scenario()
+    }
+
+    fn db(&self) -> &sled::Db {
+        self.session.session().db()
+    }
+
+    /// Seed name for the screens that are related to a single seed.
+    fn selected_seed(&self) -> Result<String> {
+        match self.current.as_ref().map(|x| &x.screen_data) {
+            Some(ScreenData::Keys { f }) => Ok(f.to_string()),
+            Some(ScreenData::KeyDetails {
+                f: Some(key_details),
+            }) => Ok(key_details.address.seed_name.to_string()),
+            _ => Err(Error::NoSeedSelected),
+        }
+    }
+}
+
+const HELP: &str = "Commands:
+  <action> [details]        perform navigation action, e.g. `SelectSeed Alice`
+  scan <hex|file>           scan payload from hex string, hex file or QR image
+  seed <name> <phrase>      add seed phrase into the keychain
+  seeds                     list seed names in the keychain
+  keys                      show keys for the selected seed
+  derive <path> <network>   derive key for the selected seed
+  show                      show the current screen again
+  help                      show this message
+  quit                      exit
+";
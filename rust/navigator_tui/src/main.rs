@@ -0,0 +1,39 @@
+use clap::Parser;
+use std::io::{BufRead, IsTerminal, Write};
+
+use navigator_tui::{Args, Frontend, Outcome};
+
+fn main() {
+    let args = Args::parse();
+    let mut frontend = match Frontend::open(&args) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("An error occurred: {e}");
+            std::process::exit(1);
+        }
+    };
+    let interactive = std::io::stdin().is_terminal();
+    let mut lines = std::io::stdin().lock().lines();
+    let mut line = String::from("start");
+    loop {
+        match frontend.execute(&line) {
+            Ok(Outcome::Show(text)) => print!("{text}"),
+            Ok(Outcome::Quit) => break,
+            Err(e) => println!("Error: {e}"),
+        }
+        if interactive {
+            print!("> ");
+            let _ = std::io::stdout().flush();
+        }
+        line = match lines.next() {
+            Some(Ok(a)) => a,
+            _ => break,
+        };
+    }
+    if let Some(path) = args.record {
+        if let Err(e) = std::fs::write(&path, frontend.scenario()) {
+            eprintln!("An error occurred: {e}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Text rendering of [`ActionResult`]
+//!
+//! Screen, modal and alert data are printed in `Debug` format, with long byte
+//! arrays collapsed. Data that the phone would show as QR codes, i.e.
+//! signatures, signed `SufficientCrypto` and key export, is printed
+//! separately, as text if the QR data is text, and as hexadecimal string
+//! otherwise, so that it could be copied into the hot side tools.
+use std::fmt::Write;
+
+use definitions::navigation::{ActionResult, ModalData, QrData, ScreenData};
+
+/// Byte lists with more elements are collapsed in rendered data.
+const MAX_BYTES_SHOWN: usize = 8;
+
+/// Render [`ActionResult`] as text.
+pub fn render(action_result: &ActionResult) -> String {
+    let mut out = String::new();
+    let title = if action_result.screen_label.is_empty() {
+        variant_name(&action_result.screen_data)
+    } else {
+        action_result.screen_label.to_string()
+    };
+    let _ = writeln!(out, "== {title} ==");
+
+    let mut buttons = Vec::new();
+    if action_result.back {
+        buttons.push("back".to_string())
+    }
+    if action_result.footer {
+        buttons.push("footer".to_string())
+    }
+    if let Some(ref footer_button) = action_result.footer_button {
+        buttons.push(format!("footer button {footer_button:?}"))
+    }
+    if let Some(ref right_button) = action_result.right_button {
+        buttons.push(format!("right button {right_button:?}"))
+    }
+    if !buttons.is_empty() {
+        let _ = writeln!(out, "[{}]", buttons.join(", "));
+    }
+
+    let _ = writeln!(
+        out,
+        "screen: {}",
+        collapse_byte_lists(&format!("{:#?}", action_result.screen_data))
+    );
+    if let Some(ref modal_data) = action_result.modal_data {
+        let _ = writeln!(
+            out,
+            "modal: {}",
+            collapse_byte_lists(&format!("{modal_data:#?}"))
+        );
+    }
+    if let Some(ref alert_data) = action_result.alert_data {
+        let _ = writeln!(out, "alert: {alert_data:?}");
+    }
+
+    for (i, qr) in qr_set(action_result).iter().enumerate() {
+        let _ = writeln!(out, "qr {}: {qr}", i + 1);
+    }
+    out
+}
+
+/// Data that would be shown as QR codes on the phone.
+fn qr_set(action_result: &ActionResult) -> Vec<String> {
+    let mut set = Vec::new();
+    if let ScreenData::KeyDetails {
+        f: Some(ref key_details),
+    } = action_result.screen_data
+    {
+        set.push(qr_text(&key_details.qr))
+    }
+    match action_result.modal_data {
+        Some(ModalData::SignatureReady { ref f }) => set.extend(f.signatures.iter().map(qr_text)),
+        Some(ModalData::SufficientCryptoReady { ref f }) => set.push(hex::encode(&f.sufficient)),
+        _ => (),
+    }
+    set
+}
+
+/// Show [`QrData`] as text if possible, as hexadecimal string otherwise.
+fn qr_text(qr_data: &QrData) -> String {
+    match std::str::from_utf8(qr_data.data()) {
+        Ok(text) if text.chars().all(|c| c.is_ascii_graphic()) => text.to_string(),
+        _ => hex::encode(qr_data.data()),
+    }
+}
+
+/// Name of the enum variant, as in `Debug` format.
+fn variant_name<T: std::fmt::Debug>(value: &T) -> String {
+    format!("{value:?}")
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Replace long lists of numbers in `Debug` output with their length.
+fn collapse_byte_lists(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        out.push_str(&rest[..start]);
+        let list = &rest[start..];
+        let collapsed = list.find(']').and_then(|end| {
+            let inner = &list[1..end];
+            let numbers = inner
+                .split(',')
+                .map(|x| x.trim())
+                .filter(|x| !x.is_empty())
+                .try_fold(0, |count, x| x.parse::<u8>().map(|_| count + 1).ok())?;
+            (numbers > MAX_BYTES_SHOWN).then(|| (format!("<{numbers} bytes>"), end + 1))
+        });
+        match collapsed {
+            Some((replacement, len)) => {
+                out.push_str(&replacement);
+                rest = &list[len..];
+            }
+            None => {
+                out.push('[');
+                rest = &list[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,46 @@
+//! Payloads for the Scan screen
+//!
+//! The phone collects QR code frames with the camera, and passes the decoded
+//! sequence to the navigation as hexadecimal string. Here the frames are taken
+//! from a QR image file, static or animated `.png`, or from a hexadecimal
+//! string, entered directly or stored in a text file.
+use std::path::Path;
+
+use definitions::navigation::DecodeSequenceResult;
+use qr_reader_pc::read_qr_frames;
+use qr_reader_phone::decode_sequence;
+
+use crate::error::{Error, Result};
+
+/// Get hexadecimal payload for `TransactionFetched` action from user input,
+/// a file path or a hexadecimal string.
+pub fn scanned_payload(input: &str) -> Result<String> {
+    let path = Path::new(input);
+    let frames = if path.is_file() {
+        let content = std::fs::read(path)?;
+        match std::str::from_utf8(&content) {
+            Ok(text) if hex::decode(text.trim()).is_ok() => vec![text.trim().to_string()],
+            _ => {
+                let frames: Vec<String> = read_qr_frames(path)?.iter().map(hex::encode).collect();
+                if frames.is_empty() {
+                    return Err(Error::QrNotFound(path.to_path_buf()));
+                }
+                frames
+            }
+        }
+    } else {
+        vec![input.trim_start_matches("0x").to_string()]
+    };
+    match decode_sequence(&frames, &None, true)? {
+        DecodeSequenceResult::Other { s } => Ok(s),
+        DecodeSequenceResult::BBananaSplitRecoveryResult { .. } => {
+            Err(Error::NotNavigationPayload("Banana Split share"))
+        }
+        DecodeSequenceResult::DynamicDerivations { .. } => {
+            Err(Error::NotNavigationPayload("dynamic derivations request"))
+        }
+        DecodeSequenceResult::DynamicDerivationTransaction { .. } => Err(
+            Error::NotNavigationPayload("dynamic derivation transaction"),
+        ),
+    }
+}
@@ -0,0 +1,54 @@
+use db_handling::cold_default::{init_db, populate_cold_nav_test};
+use definitions::network_specs::Verifier;
+use tempfile::tempdir;
+
+use navigator::{scenario::Scenario, Session};
+use navigator_tui::{Error, Frontend, Outcome};
+
+fn test_db() -> sled::Db {
+    let database = sled::open(tempdir().unwrap().into_path()).unwrap();
+    populate_cold_nav_test(&database).unwrap();
+    init_db(&database, Verifier { v: None }).unwrap();
+    database
+}
+
+fn shown(frontend: &mut Frontend, line: &str) -> String {
+    match frontend.execute(line).unwrap() {
+        Outcome::Show(text) => text,
+        Outcome::Quit => panic!("unexpected quit"),
+    }
+}
+
+#[test]
+fn it_creates_seed_through_navigation() {
+    let mut frontend = Frontend::new(test_db());
+
+    let text = shown(&mut frontend, "start");
+    assert!(text.starts_with("== Select seed ==\n"));
+    assert!(text.contains("screen: SeedSelector"));
+
+    shown(&mut frontend, "NewSeed");
+    let text = shown(&mut frontend, "goforward Alice");
+    assert!(text.contains("modal: NewSeedBackup"));
+
+    // seed phrase from the backup modal is used and kept in the keychain
+    let text = shown(&mut frontend, "GoForward true");
+    assert!(text.contains("screen: Keys"), "{text}");
+    assert_eq!(shown(&mut frontend, "seeds"), "Alice\n");
+
+    assert!(matches!(
+        frontend.execute("jump"),
+        Err(Error::UnknownCommand(command)) if command == "jump"
+    ));
+    assert!(matches!(frontend.execute("quit"), Ok(Outcome::Quit)));
+
+    // recorded navigation replays in a new session
+    let text = frontend.scenario();
+    assert!(text.starts_with("action Start\nexpect screen SeedSelector\n"));
+    assert!(text.contains("action GoForward true\nexpect screen Keys\n"));
+    assert!(text.contains("seeds Alice\n"));
+    let scenario = Scenario::parse(text).unwrap();
+    scenario
+        .run(&mut Session::new(test_db(), Vec::new()))
+        .unwrap();
+}
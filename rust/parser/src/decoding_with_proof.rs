@@ -14,9 +14,13 @@ use crate::{
     types::{CheckMetadataHashMode, MetadataProof},
 };
 
-use merkleized_metadata::{types::Hash, verify_metadata_digest, TypeResolver};
+use definitions::{metadata::MetaValues, network_specs::ShortSpecs};
+use frame_metadata_current::RuntimeMetadataPrefixed;
+use merkleized_metadata::{
+    generate_metadata_digest, types::Hash, verify_metadata_digest, ExtraInfo, TypeResolver,
+};
 
-use parity_scale_codec::DecodeLimit;
+use parity_scale_codec::{Decode, DecodeLimit};
 use scale_decode::visitor::decode_with_visitor;
 
 /// To avoid out of memory issues during scale decoding
@@ -76,6 +80,26 @@ pub fn decode_metadata_proof(data: &mut &[u8]) -> Result<MetadataProof, Error> {
         .map_err(|_| Error::Decoding(ParserDecodingError::MetadataProofExpected))
 }
 
+/// Calculates metadata digest, as defined in RFC-0078, for the metadata and
+/// the network specs.
+///
+/// This is the digest the network commits to in `CheckMetadataHash`
+/// extension. Documentation is not a part of the digest.
+pub fn metadata_digest(meta_values: &MetaValues, short_specs: &ShortSpecs) -> Result<Hash, Error> {
+    let RuntimeMetadataPrefixed(_, runtime_metadata) =
+        RuntimeMetadataPrefixed::decode(&mut &meta_values.meta[..])?;
+    let extra_info = ExtraInfo {
+        spec_version: meta_values.version,
+        spec_name: meta_values.name.to_string(),
+        base58_prefix: short_specs.base58prefix,
+        decimals: short_specs.decimals,
+        token_symbol: short_specs.unit.to_string(),
+    };
+    generate_metadata_digest(&runtime_metadata, extra_info)
+        .map(|digest| digest.hash())
+        .map_err(Error::MetadataDigest)
+}
+
 pub fn decode_call(
     data: &mut &[u8],
     metadata_proof: &MetadataProof,
@@ -78,6 +78,9 @@ pub enum Error {
     #[error("Can't verify extrinsic as metadata hash extension is disabled")]
     MetadataHashDisabled,
 
+    #[error("Unable to calculate metadata digest: {0}")]
+    MetadataDigest(String),
+
     #[error("Metadata hash must be provided in the CheckMetadataHash extension")]
     MetadataHashMissing,
 
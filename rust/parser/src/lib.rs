@@ -33,7 +33,9 @@ mod types;
 pub use types::MetadataProof;
 mod decoding_with_proof;
 mod utils;
-pub use decoding_with_proof::{decode_call, decode_extensions, decode_metadata_proof};
+pub use decoding_with_proof::{
+    decode_call, decode_extensions, decode_metadata_proof, metadata_digest,
+};
 mod error;
 pub mod method;
 use method::OlderMeta;
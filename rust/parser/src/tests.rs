@@ -6,12 +6,12 @@ use crate::method::OlderMeta;
 use crate::Error;
 use crate::{
     decoding_with_proof::{decode_call, decode_extensions},
-    parse_set,
+    metadata_digest, parse_set,
     types::MetadataProof,
     MetadataBundle,
 };
 use defaults::default_types_vec;
-use definitions::metadata::info_from_metadata;
+use definitions::metadata::{info_from_metadata, MetaValues};
 use definitions::network_specs::ShortSpecs;
 use frame_metadata::RuntimeMetadata;
 use merkleized_metadata::types::Hash;
@@ -605,3 +605,15 @@ fn parse_raw_extrinsic_with_proof() {
 
     assert!(extension_result.is_ok());
 }
+
+#[test]
+fn trimmed_metadata_digest() {
+    let metadata_hex = std::fs::read_to_string("for_tests/westend9111").unwrap();
+    let meta_values =
+        MetaValues::from_slice_metadata(&hex::decode(metadata_hex.trim()).unwrap()).unwrap();
+    let trimmed = meta_values.trim().unwrap();
+    assert_eq!(
+        metadata_digest(&trimmed, &specs()).unwrap(),
+        metadata_digest(&meta_values, &specs()).unwrap()
+    );
+}
@@ -0,0 +1,50 @@
+//! QR codes from image files
+//!
+//! Vault payloads are shared as QR code images as well: static images, or
+//! animated `.png` files for multiframe payloads.
+
+use image::{codecs::png::PngDecoder, AnimationDecoder, DynamicImage, GrayImage, ImageResult};
+use std::path::Path;
+
+/// Grayscale frames of the image file.
+///
+/// All frames are collected for animated `.png` files, other images have a
+/// single frame.
+pub fn read_image_frames<P>(path: P) -> ImageResult<Vec<GrayImage>>
+where
+    P: AsRef<Path>,
+{
+    match PngDecoder::new(std::fs::File::open(&path)?) {
+        Ok(decoder) if decoder.is_apng() => decoder
+            .apng()
+            .into_frames()
+            .map(|frame| frame.map(|x| DynamicImage::ImageRgba8(x.into_buffer()).to_luma8()))
+            .collect(),
+        Ok(decoder) => Ok(vec![DynamicImage::from_decoder(decoder)?.to_luma8()]),
+        Err(_) => Ok(vec![image::open(path)?.to_luma8()]),
+    }
+}
+
+/// Content of the QR codes in the image file frames.
+///
+/// QR codes are in the order of appearance, repeated QR codes are skipped.
+/// Unreadable QR codes are skipped as well, so the output is empty if the
+/// image has no readable QR codes.
+pub fn read_qr_frames<P>(path: P) -> ImageResult<Vec<Vec<u8>>>
+where
+    P: AsRef<Path>,
+{
+    let mut frames = Vec::new();
+    for image in read_image_frames(path)?.iter() {
+        let mut qr_decoder = quircs::Quirc::new();
+        let codes = qr_decoder.identify(image.width() as usize, image.height() as usize, image);
+        for code in codes {
+            if let Some(decoded) = code.ok().and_then(|code| code.decode().ok()) {
+                if !frames.contains(&decoded.payload) {
+                    frames.push(decoded.payload)
+                }
+            }
+        }
+    }
+    Ok(frames)
+}
@@ -1,6 +1,6 @@
 use image::open;
-use qr_reader_pc::process_qr_image;
-use qr_reader_phone::process_payload::{InProgress, Ready};
+use qr_reader_pc::{process_qr_image, read_qr_frames};
+use qr_reader_phone::process_payload::{process_decoded_payload, InProgress, Ready};
 
 #[test]
 fn check_single_qr_hex() -> Result<(), String> {
@@ -35,3 +35,21 @@ fn check_single_qr_hex() -> Result<(), String> {
         Ok(())
     }
 }
+
+#[test]
+fn read_single_qr_from_file() {
+    let frames = read_qr_frames("./tests/test_qr_1.jpg").unwrap();
+    assert_eq!(frames.len(), 1);
+    let mut decoding = InProgress::None;
+    for frame in frames {
+        decoding = match process_decoded_payload(frame, &None, decoding).unwrap() {
+            Ready::Yes(a) => {
+                assert!(hex::encode(a).starts_with("01d43593c715fdd31c61141abd0"));
+                return;
+            }
+            Ready::NotYet(in_progress) => in_progress,
+            _ => panic!("unexpected banana split"),
+        }
+    }
+    panic!("payload not complete")
+}
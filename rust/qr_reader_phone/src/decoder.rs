@@ -0,0 +1,296 @@
+//! Stateful decoder accepting QR frames one at a time.
+//!
+//! Unlike [`decode_sequence`](crate::decode_sequence), which is given the
+//! whole set of frames on each call and restarts decoding from scratch, the
+//! [`QrDecoder`] keeps the collected data between calls, so long animated
+//! QR sequences are processed in linear time.
+use constants::ENABLE_DYNAMIC_DERIVATIONS;
+use definitions::navigation::{
+    BananaSplitRecoveryResult, DecodeSequenceResult, QrDecodeStatus, QrDecodingProgress,
+};
+use transaction_parsing::decode_payload;
+
+use crate::process_payload::{process_payload_in_place, recover_banana, InProgress, Step};
+use crate::{get_payload, Error, Result};
+
+/// QR sequence decoder that is fed one frame at a time.
+pub struct QrDecoder {
+    /// Whether the frames have QR prefix already stripped.
+    cleaned: bool,
+
+    /// Whether a frame from another stream drops the collected data and
+    /// starts decoding the new stream.
+    switch_streams: bool,
+
+    /// Data collected so far.
+    decoding: InProgress,
+
+    /// Decoded result, once the sequence is complete.
+    result: Option<DecodeSequenceResult>,
+
+    /// Number of frames that were collected already.
+    duplicates: u32,
+
+    /// Number of frames that do not belong to the sequence.
+    foreign: u32,
+}
+
+impl QrDecoder {
+    pub fn new(cleaned: bool, switch_streams: bool) -> Self {
+        Self {
+            cleaned,
+            switch_streams,
+            decoding: InProgress::None,
+            result: None,
+            duplicates: 0,
+            foreign: 0,
+        }
+    }
+
+    /// Feed a single frame into the decoder.
+    ///
+    /// Frames not belonging to the sequence being decoded are counted and
+    /// ignored, the collected data remains intact. If the decoder was created
+    /// with `switch_streams`, such frame instead drops the collected data and
+    /// starts decoding of its own sequence. Once the sequence is
+    /// decoded, the same result is returned for any further frame, until
+    /// the decoder is [`reset`](Self::reset).
+    pub fn push_frame(&mut self, line: &str, password: &Option<String>) -> Result<QrDecodeStatus> {
+        if let Some(r) = &self.result {
+            return Ok(QrDecodeStatus::Done { r: r.clone() });
+        }
+        let payload = get_payload(line, self.cleaned)?;
+        let step = match process_payload_in_place(payload.clone(), password, &mut self.decoding) {
+            Err(e) if e.is_foreign_frame() && self.switch_streams => {
+                *self = Self::new(self.cleaned, self.switch_streams);
+                process_payload_in_place(payload, password, &mut self.decoding)
+            }
+            step => step,
+        };
+        let r = match step {
+            Ok(Step::Collected) => return Ok(self.in_progress()),
+            Ok(Step::Duplicate) => {
+                self.duplicates += 1;
+                return Ok(self.in_progress());
+            }
+            Err(e) if e.is_foreign_frame() => {
+                self.foreign += 1;
+                return Ok(self.in_progress());
+            }
+            Err(e) => return Err(e),
+            Ok(Step::Yes(v)) => decode_payload(&hex::encode(v), ENABLE_DYNAMIC_DERIVATIONS)?,
+            Ok(Step::BananaSplitPasswordRequest) => {
+                return Ok(QrDecodeStatus::Done {
+                    r: DecodeSequenceResult::BBananaSplitRecoveryResult {
+                        b: BananaSplitRecoveryResult::RequestPassword,
+                    },
+                })
+            }
+            Ok(Step::BananaSplitReady(s)) => DecodeSequenceResult::BBananaSplitRecoveryResult {
+                b: BananaSplitRecoveryResult::RecoveredSeed { s },
+            },
+        };
+        self.result = Some(r.clone());
+        Ok(QrDecodeStatus::Done { r })
+    }
+
+    /// Try recovering banana split seed with the password, after all shares
+    /// were collected.
+    pub fn try_password(&mut self, password: &str) -> Result<DecodeSequenceResult> {
+        let s = match &self.decoding {
+            InProgress::BananaRecovery(recovery) => recover_banana(recovery, password)?,
+            _ => return Err(Error::UnableToDecode),
+        };
+        let r = DecodeSequenceResult::BBananaSplitRecoveryResult {
+            b: BananaSplitRecoveryResult::RecoveredSeed { s },
+        };
+        self.result = Some(r.clone());
+        Ok(r)
+    }
+
+    /// Current decoding progress.
+    pub fn progress(&self) -> QrDecodingProgress {
+        let (collected, total) = match &self.decoding {
+            InProgress::None => (0, 0),
+            InProgress::Fountain(fountain) => (fountain.collected() as u32, fountain.total),
+            InProgress::LegacyMulti(legacy) => (legacy.collected() as u32, legacy.total()),
+            InProgress::BananaRecovery(recovery) => (recovery.collected() as u32, recovery.total),
+        };
+        QrDecodingProgress {
+            collected,
+            total,
+            duplicates: self.duplicates,
+            foreign: self.foreign,
+        }
+    }
+
+    /// Drop all collected data and start over.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.cleaned, self.switch_streams);
+    }
+
+    fn in_progress(&self) -> QrDecodeStatus {
+        QrDecodeStatus::InProgress { p: self.progress() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Split payload into cleaned fountain frames.
+    fn fountain_frames(payload: &[u8], repair: u32) -> Vec<String> {
+        let encoder = raptorq::Encoder::with_defaults(payload, 32);
+        let size = (payload.len() as u32 | 0x8000_0000).to_be_bytes();
+        encoder
+            .get_encoded_packets(repair)
+            .into_iter()
+            .map(|packet| {
+                let mut frame = size.to_vec();
+                frame.extend_from_slice(&packet.serialize());
+                hex::encode(frame)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fountain_one_frame_at_a_time() {
+        let payload = [vec![0x53, 0x01, 0x80], vec![0xab; 200]].concat();
+        let frames = fountain_frames(&payload, 2);
+        let mut decoder = QrDecoder::new(true, false);
+
+        let status = decoder.push_frame(&frames[0], &None).unwrap();
+        let p = match status {
+            QrDecodeStatus::InProgress { p } => p,
+            QrDecodeStatus::Done { .. } => panic!("Expected decoding to be in progress"),
+        };
+        assert_eq!(p.collected, 1);
+        assert!(p.total > 1);
+
+        decoder.push_frame(&frames[0], &None).unwrap();
+        assert_eq!(decoder.progress().duplicates, 1);
+
+        let mut result = None;
+        for frame in frames.iter().skip(1) {
+            if let QrDecodeStatus::Done { r } = decoder.push_frame(frame, &None).unwrap() {
+                result = Some(r);
+                break;
+            }
+        }
+        assert_eq!(
+            result,
+            Some(DecodeSequenceResult::Other {
+                s: hex::encode(payload)
+            })
+        );
+
+        decoder.reset();
+        assert_eq!(decoder.progress(), QrDecodingProgress::default());
+    }
+
+    #[test]
+    fn foreign_frames_are_counted() {
+        let payload = [vec![0x53, 0x01, 0x80], vec![0xab; 200]].concat();
+        let other = [vec![0x53, 0x01, 0x80], vec![0xcd; 300]].concat();
+        let frames = fountain_frames(&payload, 2);
+        let other_frames = fountain_frames(&other, 2);
+        let mut decoder = QrDecoder::new(true, false);
+
+        decoder.push_frame(&frames[0], &None).unwrap();
+        decoder.push_frame(&other_frames[0], &None).unwrap();
+        decoder.push_frame("0000020000ff", &None).unwrap();
+
+        let p = decoder.progress();
+        assert_eq!(p.collected, 1);
+        assert_eq!(p.foreign, 2);
+    }
+
+    #[test]
+    fn switch_to_newer_stream() {
+        let payload = [vec![0x53, 0x01, 0x80], vec![0xab; 200]].concat();
+        let other = [vec![0x53, 0x01, 0x80], vec![0xcd; 300]].concat();
+        let frames = fountain_frames(&payload, 2);
+        let other_frames = fountain_frames(&other, 2);
+        let mut decoder = QrDecoder::new(true, true);
+
+        decoder.push_frame(&frames[0], &None).unwrap();
+        decoder.push_frame(&frames[1], &None).unwrap();
+
+        let mut result = None;
+        for frame in other_frames.iter() {
+            if let QrDecodeStatus::Done { r } = decoder.push_frame(frame, &None).unwrap() {
+                result = Some(r);
+                break;
+            }
+        }
+        assert_eq!(
+            result,
+            Some(DecodeSequenceResult::Other {
+                s: hex::encode(other)
+            })
+        );
+    }
+
+    #[test]
+    fn foreign_stream_error() {
+        let payload = [vec![0x53, 0x01, 0x80], vec![0xab; 200]].concat();
+        let other = [vec![0x53, 0x01, 0x80], vec![0xcd; 300]].concat();
+        let mut decoding = InProgress::None;
+
+        let frame = hex::decode(&fountain_frames(&payload, 2)[0]).unwrap();
+        process_payload_in_place(frame, &None, &mut decoding).unwrap();
+
+        let frame = hex::decode(&fountain_frames(&other, 2)[0]).unwrap();
+        let result = process_payload_in_place(frame, &None, &mut decoding);
+        assert!(matches!(result, Err(Error::ForeignStream(..))));
+    }
+
+    #[test]
+    fn banana_split_wrong_password_keeps_share() {
+        let seed = "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+        let shares: Vec<Vec<u8>> = banana_recovery::encrypt(seed, "Alice", "password", 3, 2)
+            .unwrap()
+            .into_iter()
+            .map(|share| share.into_bytes())
+            .collect();
+        let wrong_password = Some(String::from("wrong password"));
+        let mut decoding = InProgress::None;
+
+        let step = process_payload_in_place(shares[0].to_vec(), &wrong_password, &mut decoding);
+        assert!(matches!(step, Ok(Step::Collected)));
+        let step = process_payload_in_place(shares[1].to_vec(), &wrong_password, &mut decoding);
+        assert!(matches!(step, Err(Error::BananaSplitWrongPassword)));
+
+        // share is collected despite the error, recovery could be retried
+        match decoding {
+            InProgress::BananaRecovery(ref recovery) => {
+                assert_eq!(recovery.collected(), 2);
+                assert_eq!(recover_banana(recovery, "password").unwrap(), seed);
+            }
+            _ => panic!("Expected banana split recovery in progress"),
+        }
+    }
+
+    #[test]
+    fn banana_split_duplicate_share() {
+        let seed = "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+        let shares: Vec<Vec<u8>> = banana_recovery::encrypt(seed, "Alice", "password", 3, 2)
+            .unwrap()
+            .into_iter()
+            .map(|share| share.into_bytes())
+            .collect();
+        let mut decoding = InProgress::None;
+
+        let step = process_payload_in_place(shares[0].to_vec(), &None, &mut decoding);
+        assert!(matches!(step, Ok(Step::Collected)));
+        let step = process_payload_in_place(shares[0].to_vec(), &None, &mut decoding);
+        assert!(matches!(step, Ok(Step::Duplicate)));
+        let step = process_payload_in_place(shares[1].to_vec(), &None, &mut decoding);
+        assert!(matches!(step, Ok(Step::BananaSplitPasswordRequest)));
+
+        match decoding {
+            InProgress::BananaRecovery(ref recovery) => assert_eq!(recovery.collected(), 2),
+            _ => panic!("Expected banana split recovery in progress"),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::process_payload::StreamId;
+
 /// Qr code handling parsing result.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -28,8 +30,11 @@ pub enum Error {
     #[error("Unable to decode on given dataset")]
     UnableToDecode,
 
-    #[error("Was decoding fountain qr code with message length {0}, got interrupted by fountain qr code with message length {1}")]
-    ConflictingPayloads(u32, u32),
+    #[error("Was decoding fountain qr stream {0}, got interrupted by fountain qr stream {1}")]
+    ForeignStream(StreamId, StreamId),
+
+    #[error("Collected {0} fountain qr packets without decoding, the animated qr codes scanned are likely mixed")]
+    MixedStreams(u32),
 
     #[error("Was decoding legacy multi-element qr, and got interrupted by a fountain one.")]
     LegacyInterruptedByFountain,
@@ -57,3 +62,19 @@ pub enum Error {
     #[error("Parsed mnemonic is invalid")]
     InvalidMnemonic,
 }
+
+impl Error {
+    /// Frame is valid on its own, but does not belong to the sequence currently
+    /// being decoded.
+    pub fn is_foreign_frame(&self) -> bool {
+        matches!(
+            self,
+            Error::ForeignStream(..)
+                | Error::LegacyInterruptedByFountain
+                | Error::LegacyInterruptedByBanana
+                | Error::FountainInterruptedByLegacy
+                | Error::ConflictingLegacyLengths(..)
+                | Error::DynamicInterruptedByStatic
+        )
+    }
+}
@@ -8,11 +8,13 @@ use constants::ENABLE_DYNAMIC_DERIVATIONS;
 
 use definitions::navigation::{BananaSplitRecoveryResult, DecodeSequenceResult};
 
+mod decoder;
 mod error;
 mod parser;
 pub mod process_payload;
 
 use crate::parser::{parse_qr_payload, LegacyFrame, RaptorqFrame};
+pub use decoder::QrDecoder;
 pub use error::{Error, Result};
 use process_payload::{process_decoded_payload, InProgress, Ready};
 use transaction_parsing::decode_payload;
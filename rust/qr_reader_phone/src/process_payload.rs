@@ -2,12 +2,37 @@ use crate::{Error, LegacyFrame, RaptorqFrame, Result};
 use banana_recovery::{NextAction, Share, ShareSet};
 use db_handling::helpers::validate_mnemonic;
 use raptorq::{self, EncodingPacket};
-use std::{collections::HashSet, convert::TryFrom};
+use std::{collections::HashSet, convert::TryFrom, fmt};
+
+/// Decoding of fountain stream is abandoned if the number of distinct packets
+/// collected exceeds estimated total this many times.
+const MAX_PACKETS_OVERHEAD: u32 = 2;
+
+/// Identifier of a fountain stream, derived from the transmission parameters
+/// every frame of the stream carries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StreamId {
+    /// Size of the encoded message in bytes.
+    pub length: u32,
+
+    /// Size of a single encoding symbol in bytes.
+    pub symbol_size: u16,
+}
+
+impl fmt::Display for StreamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "(message length {}, symbol size {})",
+            self.length, self.symbol_size
+        )
+    }
+}
 
 #[derive(PartialEq, Eq)]
 pub struct Fountain {
     decoder: raptorq::Decoder,
-    length: u32,
+    stream_id: StreamId,
     pub total: u32,
     collected: HashSet<usize>,
 }
@@ -18,6 +43,11 @@ impl Fountain {
         self.collected.len()
     }
 
+    /// Return the identifier of the stream being decoded.
+    pub fn stream_id(&self) -> StreamId {
+        self.stream_id
+    }
+
     /// Called to inform that the packet with the id has been collected.
     pub fn collect(&mut self, frame_index: usize) {
         self.collected.insert(frame_index);
@@ -30,8 +60,29 @@ pub struct LegacyMulti {
     elements: Vec<Element>,
 }
 
+impl LegacyMulti {
+    /// Return the number of elements collected.
+    pub fn collected(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Return the number of elements in the sequence.
+    pub fn total(&self) -> u32 {
+        self.length as u32
+    }
+}
+
 pub struct BananaRecovery {
     share_set: ShareSet,
+    pub total: u32,
+    collected: HashSet<Vec<u8>>,
+}
+
+impl BananaRecovery {
+    /// Return the number of shares collected.
+    pub fn collected(&self) -> usize {
+        self.collected.len()
+    }
 }
 
 #[derive(PartialEq, Eq)]
@@ -54,42 +105,76 @@ pub enum Ready {
     BananaSplitReady(String),
 }
 
+/// Outcome of feeding a single frame into an [`InProgress`] state in place.
+pub enum Step {
+    /// Frame was accepted, more frames are needed.
+    Collected,
+
+    /// Frame was already collected earlier and was ignored.
+    Duplicate,
+
+    /// Payload is fully decoded.
+    Yes(Vec<u8>),
+
+    /// Banana split shares are collected, password is needed to recover the seed.
+    BananaSplitPasswordRequest,
+
+    /// Banana split seed is recovered.
+    BananaSplitReady(String),
+}
+
 pub fn process_decoded_payload(
     payload: Vec<u8>,
     password: &Option<String>,
     mut decoding: InProgress,
 ) -> Result<Ready> {
+    match process_payload_in_place(payload, password, &mut decoding)? {
+        Step::Collected | Step::Duplicate => Ok(Ready::NotYet(decoding)),
+        Step::Yes(v) => Ok(Ready::Yes(v)),
+        Step::BananaSplitPasswordRequest => Ok(Ready::BananaSplitPasswordRequest),
+        Step::BananaSplitReady(s) => Ok(Ready::BananaSplitReady(s)),
+    }
+}
+
+/// Feed a single frame into the decoding state.
+///
+/// State is modified only if the frame is accepted, so on error the
+/// `decoding` remains exactly as it was before the call, with one exception:
+/// Banana Split share is accepted before the seed recovery with `password` is
+/// attempted. If the recovery fails, for example because of the wrong
+/// password, the error is returned and the share remains collected, so that
+/// the recovery could be retried with another password.
+pub fn process_payload_in_place(
+    payload: Vec<u8>,
+    password: &Option<String>,
+    decoding: &mut InProgress,
+) -> Result<Step> {
     if let Ok(share) = Share::new(payload.clone()) {
         match decoding {
             InProgress::None => {
+                let total = share.required_shards() as u32;
                 let share_set = ShareSet::init(share);
-                decoding = InProgress::BananaRecovery(BananaRecovery { share_set });
-                Ok(Ready::NotYet(decoding))
+                *decoding = InProgress::BananaRecovery(BananaRecovery {
+                    share_set,
+                    total,
+                    collected: HashSet::from([payload]),
+                });
+                Ok(Step::Collected)
             }
-            InProgress::BananaRecovery(ref mut recovery) => {
+            InProgress::BananaRecovery(recovery) => {
+                if recovery.collected.contains(&payload) {
+                    return Ok(Step::Duplicate);
+                }
                 recovery.share_set.try_add_share(share)?;
+                recovery.collected.insert(payload);
                 let next = recovery.share_set.next_action();
                 match next {
-                    NextAction::MoreShares { .. } => Ok(Ready::NotYet(decoding)),
+                    NextAction::MoreShares { .. } => Ok(Step::Collected),
                     NextAction::AskUserForPassword => {
                         if let Some(password) = password {
-                            let result = match recovery.share_set.recover_with_passphrase(password)
-                            {
-                                Ok(seed) => {
-                                    if validate_mnemonic(&seed) {
-                                        seed
-                                    } else {
-                                        return Err(Error::InvalidMnemonic);
-                                    }
-                                }
-                                Err(banana_recovery::Error::DecodingFailed) => {
-                                    return Err(Error::BananaSplitWrongPassword);
-                                }
-                                Err(e) => return Err(e.into()),
-                            };
-                            Ok(Ready::BananaSplitReady(result))
+                            Ok(Step::BananaSplitReady(recover_banana(recovery, password)?))
                         } else {
-                            Ok(Ready::BananaSplitPasswordRequest)
+                            Ok(Step::BananaSplitPasswordRequest)
                         }
                     }
                 }
@@ -97,42 +182,57 @@ pub fn process_decoded_payload(
             _ => Err(Error::DynamicInterruptedByStatic),
         }
     } else if let Ok(frame) = RaptorqFrame::try_from(payload.as_ref()) {
-        let length = frame.size;
         let total = frame.total();
         let new_packet = frame.payload;
         let decoded_packet = EncodingPacket::deserialize(&new_packet);
         let block_number = decoded_packet.payload_id().encoding_symbol_id() as usize;
+        let stream_id = StreamId {
+            length: frame.size,
+            symbol_size: decoded_packet.data().len() as u16,
+        };
         match decoding {
             InProgress::None => {
                 let config = raptorq::ObjectTransmissionInformation::with_defaults(
-                    length as u64,
-                    decoded_packet.data().len() as u16,
+                    stream_id.length as u64,
+                    stream_id.symbol_size,
                 );
                 let mut decoder = raptorq::Decoder::new(config);
                 match try_fountain(decoded_packet, &mut decoder) {
-                    Some(v) => Ok(Ready::Yes(v)),
+                    Some(v) => Ok(Step::Yes(v)),
                     None => {
                         let mut in_progress = Fountain {
                             decoder,
-                            length,
+                            stream_id,
                             total,
                             collected: HashSet::new(),
                         };
                         in_progress.collect(block_number);
-                        decoding = InProgress::Fountain(in_progress);
-                        Ok(Ready::NotYet(decoding))
+                        *decoding = InProgress::Fountain(in_progress);
+                        Ok(Step::Collected)
                     }
                 }
             }
-            InProgress::Fountain(mut in_progress) => {
-                if in_progress.length != length {
-                    return Err(Error::ConflictingPayloads(in_progress.length, length));
+            InProgress::Fountain(in_progress) => {
+                if in_progress.stream_id != stream_id {
+                    return Err(Error::ForeignStream(in_progress.stream_id, stream_id));
+                }
+
+                if in_progress.collected.contains(&block_number) {
+                    return Ok(Step::Duplicate);
+                }
+
+                // Frames from two streams with identical transmission parameters can not be
+                // told apart. Distinct packets in that case keep coming, but the decoder never
+                // succeeds, so decoding is stopped once the number of packets is way over
+                // the estimate.
+                if in_progress.collected() as u32 >= in_progress.total * MAX_PACKETS_OVERHEAD {
+                    return Err(Error::MixedStreams(in_progress.collected() as u32));
                 }
 
                 in_progress.collect(block_number);
                 match try_fountain(decoded_packet, &mut in_progress.decoder) {
-                    Some(v) => Ok(Ready::Yes(v)),
-                    None => Ok(Ready::NotYet(InProgress::Fountain(in_progress))),
+                    Some(v) => Ok(Step::Yes(v)),
+                    None => Ok(Step::Collected),
                 }
             }
             InProgress::LegacyMulti(_) => Err(Error::LegacyInterruptedByFountain),
@@ -153,13 +253,16 @@ pub fn process_decoded_payload(
                     elements: vec![new_element],
                 };
                 match try_legacy(&mut collected) {
-                    Some(v) => Ok(Ready::Yes(v)),
-                    None => Ok(Ready::NotYet(InProgress::LegacyMulti(collected))),
+                    Some(v) => Ok(Step::Yes(v)),
+                    None => {
+                        *decoding = InProgress::LegacyMulti(collected);
+                        Ok(Step::Collected)
+                    }
                 }
             }
             InProgress::Fountain(_) => Err(Error::FountainInterruptedByLegacy),
             InProgress::BananaRecovery(_) => Err(Error::LegacyInterruptedByFountain),
-            InProgress::LegacyMulti(mut collected) => {
+            InProgress::LegacyMulti(collected) => {
                 if collected.length != length {
                     return Err(Error::ConflictingLegacyLengths(collected.length, length));
                 }
@@ -170,22 +273,37 @@ pub fn process_decoded_payload(
                         }
                     }
                     collected.elements.push(new_element);
-                    match try_legacy(&mut collected) {
-                        Some(v) => Ok(Ready::Yes(v)),
-                        None => Ok(Ready::NotYet(InProgress::LegacyMulti(collected))),
+                    match try_legacy(collected) {
+                        Some(v) => Ok(Step::Yes(v)),
+                        None => Ok(Step::Collected),
                     }
                 } else {
-                    Ok(Ready::NotYet(InProgress::LegacyMulti(collected)))
+                    Ok(Step::Duplicate)
                 }
             }
         }
     } else if let InProgress::None = decoding {
-        Ok(Ready::Yes(payload))
+        Ok(Step::Yes(payload))
     } else {
         Err(Error::DynamicInterruptedByStatic)
     }
 }
 
+/// Recover the seed phrase from collected banana split shares.
+pub fn recover_banana(recovery: &BananaRecovery, password: &str) -> Result<String> {
+    match recovery.share_set.recover_with_passphrase(password) {
+        Ok(seed) => {
+            if validate_mnemonic(&seed) {
+                Ok(seed)
+            } else {
+                Err(Error::InvalidMnemonic)
+            }
+        }
+        Err(banana_recovery::Error::DecodingFailed) => Err(Error::BananaSplitWrongPassword),
+        Err(e) => Err(e.into()),
+    }
+}
+
 fn try_fountain(packet: EncodingPacket, decoder: &mut raptorq::Decoder) -> Option<Vec<u8>> {
     decoder.add_new_packet(packet);
     decoder.get_result()
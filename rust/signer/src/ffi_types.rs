@@ -8,11 +8,12 @@ pub use definitions::{
         NetworkSpecsExport, NetworkVerifierDisplay, SignDisplay, SignMessageDisplay, TypesDisplay,
         TypesExport,
     },
-    metadata::MetaValues,
+    metadata::{MetaStorage, MetaValues},
     navigation::*,
     network_specs::{
         NetworkSpecs, OrderedNetworkSpecs, ValidCurrentVerifier, Verifier, VerifierValue,
     },
+    users::SeedLanguage,
 };
 pub use navigator::Action;
 
@@ -35,7 +35,7 @@ use std::{
     collections::HashMap,
     fmt::Display,
     str::FromStr,
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
 };
 use transaction_parsing::dynamic_derivations::process_dynamic_derivations;
 use transaction_parsing::entry_to_transactions_with_decoding;
@@ -188,8 +188,16 @@ impl Display for ErrorDisplayed {
 #[derive(Debug)]
 pub enum QrSequenceDecodeError {
     BananaSplitWrongPassword,
-    BananaSplit { s: String },
-    GenericError { s: String },
+    BananaSplit {
+        s: String,
+    },
+    /// Frames from several animated QR codes were scanned together
+    MixedStreams {
+        s: String,
+    },
+    GenericError {
+        s: String,
+    },
 }
 
 impl Display for QrSequenceDecodeError {
@@ -203,6 +211,8 @@ impl From<qr_reader_phone::Error> for QrSequenceDecodeError {
         match value {
             qr_reader_phone::Error::BananaSplitWrongPassword => Self::BananaSplitWrongPassword,
             qr_reader_phone::Error::BananaSplitError(e) => Self::BananaSplit { s: format!("{e}") },
+            e @ (qr_reader_phone::Error::ForeignStream(..)
+            | qr_reader_phone::Error::MixedStreams(_)) => Self::MixedStreams { s: format!("{e}") },
             other => QrSequenceDecodeError::GenericError {
                 s: format!("{other}"),
             },
@@ -283,6 +293,63 @@ fn qrparser_try_decode_qr_sequence(
     Ok(res?)
 }
 
+/// Stateful QR sequence decoder, fed one frame at a time
+///
+/// Should be preferred over [`qrparser_try_decode_qr_sequence`] for long animated QR sequences,
+/// as collected frames are not re-processed on each call
+pub struct QrDecoder {
+    inner: Mutex<qr_reader_phone::QrDecoder>,
+}
+
+impl QrDecoder {
+    /// `cleaned` is platform-specific flag indicating whether QR payloads have QR prefix stripped
+    /// by QR parsing code
+    ///
+    /// `switch_streams` makes a frame from another animated QR drop the collected frames and start
+    /// decoding the new QR instead of being ignored
+    fn new(cleaned: bool, switch_streams: bool) -> Self {
+        Self {
+            inner: Mutex::new(qr_reader_phone::QrDecoder::new(cleaned, switch_streams)),
+        }
+    }
+
+    /// Feed single QR frame into decoder, report progress or decoded result
+    fn push_frame(
+        &self,
+        data: &str,
+        password: Option<String>,
+    ) -> Result<QrDecodeStatus, QrSequenceDecodeError> {
+        Ok(self.lock()?.push_frame(data, &password)?)
+    }
+
+    /// Recover banana split seed with password once all shares are collected
+    fn try_password(&self, password: &str) -> Result<DecodeSequenceResult, QrSequenceDecodeError> {
+        Ok(self.lock()?.try_password(password)?)
+    }
+
+    fn progress(&self) -> QrDecodingProgress {
+        self.inner
+            .lock()
+            .map(|decoder| decoder.progress())
+            .unwrap_or_default()
+    }
+
+    /// Drop collected frames and start over
+    fn reset(&self) {
+        if let Ok(mut decoder) = self.inner.lock() {
+            decoder.reset()
+        }
+    }
+
+    fn lock(
+        &self,
+    ) -> Result<std::sync::MutexGuard<'_, qr_reader_phone::QrDecoder>, QrSequenceDecodeError> {
+        self.inner
+            .lock()
+            .map_err(|e| QrSequenceDecodeError::GenericError { s: e.to_string() })
+    }
+}
+
 fn get_db() -> Result<sled::Db, ErrorDisplayed> {
     DB.read()
         .unwrap()
@@ -312,6 +379,39 @@ fn generate_secret_key_qr(
     .map_err(|e| e.to_string().into())
 }
 
+/// Exports secret (private) key as password-protected Polkadot-JS keystore,
+/// in multi-frame QR code
+///
+/// Arguments are same as in [`generate_secret_key_qr`], `keystore_password` is
+/// used for keystore encryption
+fn generate_keystore_qr(
+    public_key: &str,
+    expected_seed_name: &str,
+    network_specs_key: &str,
+    seed_phrase: &str,
+    key_password: Option<String>,
+    keystore_password: &str,
+) -> Result<MKeystoreExport, ErrorDisplayed> {
+    db_handling::keystore::export_keystore(
+        &get_db()?,
+        public_key,
+        expected_seed_name,
+        network_specs_key,
+        seed_phrase,
+        key_password,
+        keystore_password,
+    )
+    .map_err(|e| e.to_string().into())
+}
+
+/// Imports Polkadot-JS keystore of a known key, marks the key as exposed
+///
+/// `payload` is keystore JSON, as is or in hex, as it comes from QR code
+fn import_keystore(payload: &str, keystore_password: &str) -> Result<MAddressCard, ErrorDisplayed> {
+    db_handling::keystore::import_keystore(&get_db()?, payload, keystore_password)
+        .map_err(|e| e.to_string().into())
+}
+
 fn import_derivations(seed_derived_keys: Vec<SeedKeysPreview>) -> Result<(), ErrorDisplayed> {
     import_all_addrs(&get_db()?, seed_derived_keys).map_err(|e| e.to_string().into())
 }
@@ -531,6 +631,21 @@ fn remove_key_set(seed_name: &str) -> anyhow::Result<(), ErrorDisplayed> {
         .map_err(|e| ErrorDisplayed::from(e.to_string()))
 }
 
+/// Renames key set, seed phrase in the device key storage must be renamed
+/// accordingly
+fn rename_key_set(seed_name: &str, new_seed_name: &str) -> anyhow::Result<(), ErrorDisplayed> {
+    db_handling::identities::rename_key_set(&get_db()?, seed_name, new_seed_name)
+        .map_err(|e| ErrorDisplayed::from(e.to_string()))
+}
+
+/// Sets key label, or removes it if the label is empty
+fn set_key_label(address: &str, label: Option<String>) -> anyhow::Result<(), ErrorDisplayed> {
+    let address_key =
+        AddressKey::from_hex(address).map_err(|e| ErrorDisplayed::from(e.to_string()))?;
+    db_handling::identities::set_key_label(&get_db()?, &address_key, label)
+        .map_err(|e| ErrorDisplayed::from(e.to_string()))
+}
+
 fn get_managed_network_details(
     network_key: &str,
 ) -> anyhow::Result<MNetworkDetails, ErrorDisplayed> {
@@ -551,6 +666,17 @@ fn remove_metadata_on_managed_network(
         .map_err(|e| ErrorDisplayed::from(e.to_string()))
 }
 
+/// Form in which the network metadata is stored
+fn get_metadata_storage() -> anyhow::Result<MetaStorage, ErrorDisplayed> {
+    db_handling::helpers::get_meta_storage(&get_db()?).map_err(|e| e.to_string().into())
+}
+
+/// Set the form in which the network metadata loaded afterwards is stored
+fn set_metadata_storage(meta_storage: MetaStorage) -> anyhow::Result<(), ErrorDisplayed> {
+    db_handling::helpers::set_meta_storage(&get_db()?, meta_storage)
+        .map_err(|e| e.to_string().into())
+}
+
 fn seed_phrase_guess_words(user_input: &str) -> Vec<String> {
     db_handling::interface_signer::guess(user_input)
         .into_iter()
@@ -558,6 +684,15 @@ fn seed_phrase_guess_words(user_input: &str) -> Vec<String> {
         .collect()
 }
 
+/// Generate random seed phrase with given number of words, in given language
+fn generate_random_phrase_in(
+    words_number: u32,
+    language: SeedLanguage,
+) -> anyhow::Result<String, ErrorDisplayed> {
+    db_handling::identities::generate_random_phrase_in(words_number, language)
+        .map_err(|e| ErrorDisplayed::from(e.to_string()))
+}
+
 fn get_verifier_details() -> anyhow::Result<MVerifierDetails, ErrorDisplayed> {
     Ok(db_handling::helpers::get_general_verifier(&get_db()?)
         .map_err(|e| e.to_string())?
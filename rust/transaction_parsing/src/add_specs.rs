@@ -3,7 +3,6 @@ use db_handling::{
     helpers::{genesis_hash_in_specs, get_general_verifier, try_get_valid_current_verifier},
 };
 use definitions::{
-    error::TransferContent,
     error_signer::GeneralVerifierForContent,
     history::Event,
     keyring::{NetworkSpecsKey, VerifierKey},
@@ -21,7 +20,7 @@ use crate::{StubNav, TransactionAction};
 use crate::holds::{GeneralHold, Hold, HoldRelease};
 
 pub fn add_specs(database: &sled::Db, data_hex: &str) -> Result<TransactionAction> {
-    let checked_info = pass_crypto(data_hex, TransferContent::AddSpecs)?;
+    let checked_info = pass_crypto(data_hex)?;
     let specs = ContentAddSpecs::from_slice(&checked_info.message).specs()?;
     let network_specs_key = NetworkSpecsKey::from_parts(&specs.genesis_hash, &specs.encryption);
     let verifier_key = VerifierKey::from_parts(specs.genesis_hash);
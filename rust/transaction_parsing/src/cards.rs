@@ -2,6 +2,7 @@ use sp_core::crypto::{Ss58AddressFormat, Ss58Codec};
 use sp_core::H256;
 use sp_runtime::{generic::Era, MultiSigner};
 
+use db_handling::identities::get_key_label;
 use definitions::helpers::{make_identicon_from_account, make_identicon_from_id20, IdenticonStyle};
 use definitions::keyring::{AddressKey, NetworkSpecsKey};
 
@@ -25,18 +26,13 @@ use definitions::{
 };
 use parser::cards::ParserCard;
 
-use crate::error::Error;
+use crate::error::{Error, Result};
 use crate::holds::{GeneralHold, Hold};
 
 #[allow(clippy::enum_variant_names)]
 pub(crate) enum Card<'a> {
     ParserCard(&'a ParserCard),
-    Author {
-        author: &'a MultiSigner,
-        base58prefix: u16,
-        genesis_hash: H256,
-        address_details: &'a AddressDetails,
-    },
+    Author(MAddressCard),
     AuthorPlain {
         author: &'a MultiSigner,
         base58prefix: u16,
@@ -208,13 +204,8 @@ impl Card<'_> {
                 },
                 ParserCard::TxVersion(x) => NavCard::TxSpecCard { f: x.clone() },
             },
-            Card::Author {
-                author,
-                base58prefix,
-                genesis_hash,
-                address_details,
-            } => NavCard::AuthorCard {
-                f: make_author_info(author, *base58prefix, *genesis_hash, address_details),
+            Card::Author(author_info) => NavCard::AuthorCard {
+                f: author_info.to_owned(),
             },
             Card::AuthorPlain {
                 author,
@@ -293,29 +284,31 @@ impl Card<'_> {
 }
 
 pub(crate) fn make_author_info(
+    database: &sled::Db,
     author: &MultiSigner,
     base58prefix: u16,
     genesis_hash: H256,
     address_details: &AddressDetails,
-) -> MAddressCard {
+) -> Result<MAddressCard> {
     let address_key = AddressKey::new(author.clone(), Some(genesis_hash));
 
-    make_author_info_with_key(author, base58prefix, address_key, address_details)
+    make_author_info_with_key(database, author, base58prefix, address_key, address_details)
 }
 
 pub(crate) fn make_author_info_with_key(
+    database: &sled::Db,
     author: &MultiSigner,
     base58prefix: u16,
     address_key: AddressKey,
     address_details: &AddressDetails,
-) -> MAddressCard {
+) -> Result<MAddressCard> {
     let base58 = print_multisigner_as_base58_or_eth_address(
         author,
         Some(base58prefix),
         address_details.encryption,
     );
     let address_key_str = hex::encode(address_key.key());
-    MAddressCard {
+    Ok(MAddressCard {
         base58,
         address_key: address_key_str,
         address: Address {
@@ -324,6 +317,7 @@ pub(crate) fn make_author_info_with_key(
             path: address_details.path.clone(),
             has_pwd: address_details.has_pwd,
             secret_exposed: address_details.secret_exposed,
+            label: get_key_label(database, &address_key)?,
         },
-    }
+    })
 }
@@ -1,5 +1,5 @@
 use db_handling::identities::{
-    get_all_addresses, is_passworded, ExportAddrs, ExportAddrsV1, ExportAddrsV2,
+    get_all_addresses, is_passworded, ExportAddrs, ExportAddrsV1, ExportAddrsV2, ExportAddrsV3,
 };
 
 use definitions::derivations::{
@@ -37,6 +37,7 @@ pub fn prepare_derivations_preview(
     match export_info {
         ExportAddrs::V1(v1) => prepare_derivations_v1(database, v1),
         ExportAddrs::V2(v2) => prepare_derivations_v2(database, v2),
+        ExportAddrs::V3(v3) => prepare_derivations_v3(database, v3),
     }
 }
 
@@ -108,6 +109,13 @@ fn prepare_derivations_v2(
     prepare_derivations_v1(database, export_info.into())
 }
 
+fn prepare_derivations_v3(
+    database: &sled::Db,
+    export_info: ExportAddrsV3,
+) -> Result<Vec<SeedKeysPreview>> {
+    prepare_derivations_v1(database, export_info.into())
+}
+
 fn get_derivation_status(
     database: &sled::Db,
     path: &str,
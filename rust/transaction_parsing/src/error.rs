@@ -564,6 +564,20 @@ pub enum Error {
         version: u32,
     },
 
+    /// Vault is set to decode transactions only with metadata proofs, and
+    /// does not store the network metadata.
+    #[error(
+        "Vault decodes transactions only with metadata proofs. \
+        Metadata for {name}{version} is not stored."
+    )]
+    MetadataNotStored {
+        /// network name from received metadata
+        name: String,
+
+        /// network version from received metadata
+        version: u32,
+    },
+
     /// [`OrderedNetworkSpecs`](definitions::network_specs::OrderedNetworkSpecs) for network in
     /// which the imported derivations are user to create addresses.
     #[error(
@@ -596,6 +610,27 @@ pub enum Error {
     HistoryUnknownNetwork { name: String },
 }
 
+impl Error {
+    /// Transform payload framing error from [`definitions::payload`] into
+    /// [`Error`], keeping the errors the Vault has always reported for
+    /// malformed payloads.
+    pub(crate) fn payload(e: definitions::error::Error) -> Self {
+        match e {
+            definitions::error::Error::PayloadTooShort => Error::TooShort,
+            definitions::error::Error::PayloadNotSubstrate(a) => {
+                Error::NotSubstrate(hex::encode([a]))
+            }
+            definitions::error::Error::PayloadKindNotSupported(a) => {
+                Error::PayloadNotSupported(hex::encode([a]))
+            }
+            definitions::error::Error::PayloadEncryptionNotSupported(a) => {
+                Error::EncryptionNotSupported(hex::encode([a]))
+            }
+            e => Error::Definitions(e),
+        }
+    }
+}
+
 fn display_parsing_errors(network_name: &str, errors: &[(u32, parser::Error)]) -> String {
     let mut insert = String::new();
     for (i, (version, parser_error)) in errors.iter().enumerate() {
@@ -12,7 +12,7 @@ use definitions::{
     network_specs::{NetworkSpecs, OrderedNetworkSpecs, ShortSpecs},
 };
 use frame_metadata::RuntimeMetadata;
-use parser::{method::OlderMeta, MetadataBundle};
+use parser::{metadata_digest, method::OlderMeta, MetadataBundle};
 use sp_core::{ecdsa, ed25519, sr25519, H256};
 use sp_runtime::MultiSigner;
 use std::convert::TryInto;
@@ -96,13 +96,24 @@ pub fn bundle_from_meta_set_element<'a>(
     }
 }
 
+/// Check if the received metadata should be added into the database.
+///
+/// Metadata is new if there is no metadata for the same network name and
+/// version in the database. Same network version could be received in
+/// another `RuntimeMetadata` version, e.g. `V15` with `V14` in the database:
+/// received metadata is accepted, replacing the stored one, only if its
+/// `RuntimeMetadata` version is higher. Different metadata with same network
+/// name, version and `RuntimeMetadata` version is an error.
 pub fn accept_meta_values(database: &sled::Db, meta_values: &MetaValues) -> Result<bool> {
     let meta_key = MetaKey::from_parts(&meta_values.name, meta_values.version);
     let metadata = open_tree(database, METATREE)?;
     match metadata.get(meta_key.key())? {
         Some(a) => {
-            if a == meta_values.meta {
+            let stored = MetaValues::from_stored(&a)?;
+            if stored.hash() == meta_values.hash() {
                 Ok(false)
+            } else if stored.metadata_version()? != meta_values.metadata_version()? {
+                Ok(meta_values.metadata_version()? > stored.metadata_version()?)
             } else {
                 Err(Error::SameNameVersionDifferentMeta {
                     name: meta_values.name.to_string(),
@@ -114,6 +125,27 @@ pub fn accept_meta_values(database: &sled::Db, meta_values: &MetaValues) -> Resu
     }
 }
 
+/// Trim metadata for storage in the database, and check that the trimmed
+/// metadata has the same metadata digest as the received one.
+///
+/// Metadata with versions below `V14` is stored in full.
+pub(crate) fn trim_checked(
+    meta_values: MetaValues,
+    short_specs: &ShortSpecs,
+) -> Result<MetaValues> {
+    let trimmed = match meta_values.trim() {
+        Ok(a) => a,
+        Err(definitions::error::Error::MetadataError(MetadataError::TrimUnsupported)) => {
+            return Ok(meta_values)
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if metadata_digest(&trimmed, short_specs)? != metadata_digest(&meta_values, short_specs)? {
+        return Err(MetadataError::TrimmedMismatch.into());
+    }
+    Ok(trimmed)
+}
+
 /// Function to check if the chain specs are already in the database
 pub fn specs_are_new(database: &sled::Db, new: &NetworkSpecs) -> Result<bool> {
     let network_specs_key = NetworkSpecsKey::from_parts(&new.genesis_hash, &new.encryption);
@@ -2,7 +2,10 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 
 use db_handling::identities::TransactionBulk;
-use definitions::helpers::unhex;
+use definitions::{
+    helpers::unhex,
+    payload::{PayloadKind, Prelude},
+};
 use parity_scale_codec::Decode;
 
 pub use definitions::navigation::{StubNav, TransactionAction};
@@ -50,26 +53,29 @@ fn handle_scanner_input(database: &sled::Db, payload: &str) -> Result<Transactio
         }
     };
 
-    if data_hex.len() < 6 {
-        return Err(Error::TooShort);
-    }
-
-    if &data_hex[..2] != "53" {
-        return Err(Error::NotSubstrate(data_hex[..2].to_string()));
+    match payload_kind(data_hex)? {
+        PayloadKind::Transaction | PayloadKind::TransactionImmortal => {
+            parse_transaction(database, data_hex)
+        }
+        PayloadKind::Message => process_concrete_chain_message(database, data_hex),
+        PayloadKind::TransactionBulk => parse_transaction_bulk(database, data_hex),
+        PayloadKind::TransactionWithProof => parse_transaction_with_proof(database, data_hex),
+        PayloadKind::AnyChainMessage => process_any_chain_message(database, data_hex),
+        PayloadKind::LoadMetadata | PayloadKind::LoadMetadataCompressed => {
+            load_metadata(database, data_hex)
+        }
+        PayloadKind::LoadTypes => load_types(database, data_hex),
+        PayloadKind::AddSpecs => add_specs(database, data_hex),
+        PayloadKind::Derivations => process_derivations(database, data_hex),
+        a => Err(Error::PayloadNotSupported(hex::encode([a.code()]))),
     }
+}
 
-    match &data_hex[4..6] {
-        "00" | "02" => parse_transaction(database, data_hex),
-        "03" => process_concrete_chain_message(database, data_hex),
-        "04" => parse_transaction_bulk(database, data_hex),
-        "06" => parse_transaction_with_proof(database, data_hex),
-        "08" => process_any_chain_message(database, data_hex),
-        "80" => load_metadata(database, data_hex),
-        "81" => load_types(database, data_hex),
-        "c1" => add_specs(database, data_hex),
-        "de" => process_derivations(database, data_hex),
-        _ => Err(Error::PayloadNotSupported(data_hex[4..6].to_string())),
-    }
+/// Get [`PayloadKind`] from the prelude of hexadecimal payload.
+fn payload_kind(data_hex: &str) -> Result<PayloadKind> {
+    let prelude = unhex(data_hex.get(..6).ok_or(Error::TooShort)?)?;
+    let (prelude, _) = Prelude::from_slice_with_tail(&prelude).map_err(Error::payload)?;
+    Ok(prelude.kind())
 }
 
 /// Decode content of payload
@@ -100,15 +106,24 @@ pub fn decode_payload(
         });
     }
 
-    match &data_hex[4..6] {
-        "04" => decode_transaction_bulk(data_hex, enable_dynamic_derivations),
-        "05" => Ok(DecodeSequenceResult::DynamicDerivationTransaction {
-            s: vec![data_hex.to_string()],
-        }),
-        "07" => Ok(DecodeSequenceResult::DynamicDerivationTransaction {
+    // payloads of unknown types are passed further as is
+    let kind = match payload_kind(data_hex) {
+        Ok(kind) => Some(kind),
+        Err(Error::PayloadNotSupported(_)) => None,
+        Err(e) => return Err(e),
+    };
+
+    match kind {
+        Some(PayloadKind::TransactionBulk) => {
+            decode_transaction_bulk(data_hex, enable_dynamic_derivations)
+        }
+        Some(
+            PayloadKind::DynamicDerivationTransaction
+            | PayloadKind::DynamicDerivationTransactionWithProof,
+        ) => Ok(DecodeSequenceResult::DynamicDerivationTransaction {
             s: vec![data_hex.to_string()],
         }),
-        "df" => decode_dynamic_derivations(data_hex),
+        Some(PayloadKind::DynamicDerivations) => decode_dynamic_derivations(data_hex),
         _ => Ok(DecodeSequenceResult::Other {
             s: payload.to_string(),
         }),
@@ -1,13 +1,15 @@
 use db_handling::{
     db_transactions::TrDbColdStub,
-    helpers::{genesis_hash_in_specs, get_general_verifier, try_get_valid_current_verifier},
+    helpers::{
+        genesis_hash_in_specs, get_general_verifier, get_meta_storage, get_network_specs,
+        try_get_valid_current_verifier,
+    },
 };
 use definitions::{
-    error::TransferContent,
     error_signer::GeneralVerifierForContent,
     history::{Event, MetaValuesDisplay},
     keyring::VerifierKey,
-    metadata::MetaValues,
+    metadata::{MetaStorage, MetaValues},
     navigation::{TransactionCard, TransactionCardSet},
     network_specs::{ValidCurrentVerifier, Verifier},
     qr_transfers::ContentLoadMeta,
@@ -16,7 +18,7 @@ use definitions::{
 use crate::cards::{Card, Warning};
 use crate::check_signature::pass_crypto;
 use crate::error::{Error, Result};
-use crate::helpers::accept_meta_values;
+use crate::helpers::{accept_meta_values, trim_checked};
 use crate::{StubNav, TransactionAction};
 
 enum FirstCard {
@@ -25,9 +27,16 @@ enum FirstCard {
 }
 
 pub fn load_metadata(database: &sled::Db, data_hex: &str) -> Result<TransactionAction> {
-    let checked_info = pass_crypto(data_hex, TransferContent::LoadMeta)?;
+    let checked_info = pass_crypto(data_hex)?;
     let (meta, genesis_hash) = ContentLoadMeta::from_slice(&checked_info.message).meta_genhash()?;
     let meta_values = MetaValues::from_slice_metadata(&meta)?;
+    let meta_storage = get_meta_storage(database)?;
+    if meta_storage == MetaStorage::ProofOnly {
+        return Err(Error::MetadataNotStored {
+            name: meta_values.name,
+            version: meta_values.version,
+        });
+    }
     let general_verifier = get_general_verifier(database)?;
     let verifier_key = VerifierKey::from_parts(genesis_hash);
     let valid_current_verifier = try_get_valid_current_verifier(database, &verifier_key)?.ok_or(
@@ -158,6 +167,16 @@ pub fn load_metadata(database: &sled::Db, data_hex: &str) -> Result<TransactionA
             FirstCard::VerifierCard(Card::Verifier(new_verifier_value).card(&mut index, 0))
         }
     };
+    let meta_values = match meta_storage {
+        MetaStorage::Trimmed => {
+            let short_specs =
+                get_network_specs(database, &specs_invariants.first_network_specs_key)?
+                    .specs
+                    .short();
+            trim_checked(meta_values, &short_specs)?
+        }
+        _ => meta_values,
+    };
     if accept_meta_values(database, &meta_values)? {
         stub = stub.add_metadata(&meta_values);
         let checksum = stub.store_and_get_checksum(database)?;
@@ -3,9 +3,8 @@ use db_handling::{
     helpers::{get_general_verifier, try_get_types},
 };
 use definitions::{
-    error::TransferContent, error_signer::GeneralVerifierForContent, history::Event,
-    navigation::TransactionCardSet, network_specs::Verifier, qr_transfers::ContentLoadTypes,
-    types::TypeEntry,
+    error_signer::GeneralVerifierForContent, history::Event, navigation::TransactionCardSet,
+    network_specs::Verifier, qr_transfers::ContentLoadTypes, types::TypeEntry,
 };
 
 use crate::cards::{Card, Warning};
@@ -15,7 +14,7 @@ use crate::holds::GeneralHold;
 use crate::{StubNav, TransactionAction};
 
 pub fn load_types(database: &sled::Db, data_hex: &str) -> Result<TransactionAction> {
-    let checked_info = pass_crypto(data_hex, TransferContent::LoadTypes)?;
+    let checked_info = pass_crypto(data_hex)?;
     let content_new_types = ContentLoadTypes::from_slice(&checked_info.message);
     let new_types = content_new_types.types()?;
     let old_types: Vec<TypeEntry> = try_get_types(database)?.unwrap_or_default();
@@ -87,11 +87,12 @@ pub fn process_concrete_chain_message(
                         let sign: TrDbColdSign = sign.into();
                         let checksum = sign.store_and_get_checksum(database)?;
                         let author_info = make_author_info(
+                            database,
                             &author_multi_signer,
                             network_specs.specs.base58prefix,
                             network_specs.specs.genesis_hash,
                             &address_details,
-                        );
+                        )?;
                         let network_info = network_specs;
                         Ok(TransactionAction::Sign {
                             actions: vec![TransactionSignAction {
@@ -108,12 +109,13 @@ pub fn process_concrete_chain_message(
                             checksum,
                         })
                     } else {
-                        let author_card = Card::Author {
-                            author: &author_multi_signer,
-                            base58prefix: network_specs.specs.base58prefix,
-                            genesis_hash: network_specs.specs.genesis_hash,
-                            address_details: &address_details,
-                        }
+                        let author_card = Card::Author(make_author_info(
+                            database,
+                            &author_multi_signer,
+                            network_specs.specs.base58prefix,
+                            network_specs.specs.genesis_hash,
+                            &address_details,
+                        )?)
                         .card(&mut index, indent);
                         let warning_card =
                             Card::Warning(Warning::NoNetworkID).card(&mut index, indent);
@@ -201,11 +203,12 @@ pub fn process_any_chain_message(database: &sled::Db, data_hex: &str) -> Result<
 
             let address_key = AddressKey::new(author_multi_signer.clone(), maybe_genesis_hash);
             let author_info = make_author_info_with_key(
+                database,
                 &author_multi_signer,
                 GENERAL_SUBSTRATE_PREFIX,
                 address_key,
                 &address_details,
-            );
+            )?;
 
             Ok(TransactionAction::Sign {
                 actions: vec![TransactionSignAction {
@@ -180,12 +180,13 @@ fn do_parse_transaction_with_proof(
             if address_details.network_id.as_ref() == Some(&network_specs_key) {
                 CardsPrep::SignProceed(address_details, None)
             } else {
-                let author_card = (Card::Author {
-                    author: &author_multi_signer,
-                    base58prefix: network_specs.specs.base58prefix,
-                    genesis_hash: network_specs.specs.genesis_hash,
-                    address_details: &address_details,
-                })
+                let author_card = Card::Author(make_author_info(
+                    database,
+                    &author_multi_signer,
+                    network_specs.specs.base58prefix,
+                    network_specs.specs.genesis_hash,
+                    &address_details,
+                )?)
                 .card(&mut index, indent);
                 CardsPrep::ShowOnly(
                     author_card,
@@ -207,16 +208,19 @@ fn do_parse_transaction_with_proof(
     let (call_data, extensions_data) = match cut_method_extensions(remained_payload) {
         Ok(v) => v,
         Err(e) => {
-            return prepare_read_transaction_action(ReadTransactionPrepareParams {
-                maybe_error: Some(e),
-                cards_prep,
-                network_specs,
-                author_multi_signer,
-                maybe_method_cards: None,
-                maybe_extension_cards: None,
-                index,
-                indent,
-            })
+            return prepare_read_transaction_action(
+                database,
+                ReadTransactionPrepareParams {
+                    maybe_error: Some(e),
+                    cards_prep,
+                    network_specs,
+                    author_multi_signer,
+                    maybe_method_cards: None,
+                    maybe_extension_cards: None,
+                    index,
+                    indent,
+                },
+            )
         }
     };
 
@@ -227,48 +231,57 @@ fn do_parse_transaction_with_proof(
     ) {
         Ok(v) => v,
         Err(e) => {
-            return prepare_read_transaction_action(ReadTransactionPrepareParams {
-                maybe_error: Some(e),
-                cards_prep,
-                network_specs,
-                author_multi_signer,
-                maybe_method_cards: None,
-                maybe_extension_cards: None,
-                index,
-                indent,
-            })
+            return prepare_read_transaction_action(
+                database,
+                ReadTransactionPrepareParams {
+                    maybe_error: Some(e),
+                    cards_prep,
+                    network_specs,
+                    author_multi_signer,
+                    maybe_method_cards: None,
+                    maybe_extension_cards: None,
+                    index,
+                    indent,
+                },
+            )
         }
     };
 
     let call_cards = match decode_call(&mut call_data.as_slice(), &metadata_proof) {
         Ok(v) => v,
         Err(e) => {
-            return prepare_read_transaction_action(ReadTransactionPrepareParams {
-                maybe_error: Some(e),
-                cards_prep,
-                network_specs,
-                author_multi_signer,
-                maybe_method_cards: None,
-                maybe_extension_cards: Some(extensions_cards),
-                index,
-                indent,
-            })
+            return prepare_read_transaction_action(
+                database,
+                ReadTransactionPrepareParams {
+                    maybe_error: Some(e),
+                    cards_prep,
+                    network_specs,
+                    author_multi_signer,
+                    maybe_method_cards: None,
+                    maybe_extension_cards: Some(extensions_cards),
+                    index,
+                    indent,
+                },
+            )
         }
     };
 
     let (address_details, possible_warning) = match cards_prep {
         CardsPrep::SignProceed(a, w) => (a, w),
         _ => {
-            return prepare_read_transaction_action(ReadTransactionPrepareParams {
-                maybe_error: None,
-                cards_prep,
-                network_specs,
-                author_multi_signer,
-                maybe_method_cards: Some(call_cards),
-                maybe_extension_cards: Some(extensions_cards),
-                index,
-                indent,
-            })
+            return prepare_read_transaction_action(
+                database,
+                ReadTransactionPrepareParams {
+                    maybe_error: None,
+                    cards_prep,
+                    network_specs,
+                    author_multi_signer,
+                    maybe_method_cards: Some(call_cards),
+                    maybe_extension_cards: Some(extensions_cards),
+                    index,
+                    indent,
+                },
+            )
         }
     };
 
@@ -288,11 +301,12 @@ fn do_parse_transaction_with_proof(
     sign.signing_bulk.push(sign_one);
     let checksum = sign.store_and_get_checksum(database)?;
     let author_info = make_author_info(
+        database,
         &author_multi_signer,
         network_specs.specs.base58prefix,
         network_specs.specs.genesis_hash,
         &address_details,
-    );
+    )?;
 
     let warning = possible_warning
         .map(|w| Card::Warning(w).card(&mut index, indent))
@@ -350,6 +364,7 @@ fn do_get_network_specs(
 }
 
 fn prepare_read_transaction_action(
+    database: &sled::Db,
     params: ReadTransactionPrepareParams,
 ) -> Result<TransactionAction> {
     match params.cards_prep {
@@ -360,12 +375,13 @@ fn prepare_read_transaction_action(
             let warning = possible_warning
                 .map(|w| Card::Warning(w).card(&mut index, indent))
                 .map(|w| vec![w]);
-            let author = Card::Author {
-                author: &params.author_multi_signer,
-                base58prefix: params.network_specs.specs.base58prefix,
-                genesis_hash: params.network_specs.specs.genesis_hash,
-                address_details: &address_details,
-            }
+            let author = Card::Author(make_author_info(
+                database,
+                &params.author_multi_signer,
+                params.network_specs.specs.base58prefix,
+                params.network_specs.specs.genesis_hash,
+                &address_details,
+            )?)
             .card(&mut index, params.indent);
             let error_cards = params
                 .maybe_error
@@ -444,12 +460,13 @@ fn do_parse_transaction(
                     if address_details.network_id.as_ref() == Some(&network_specs_key) {
                         CardsPrep::SignProceed(address_details, None)
                     } else {
-                        let author_card = (Card::Author {
-                            author: &author_multi_signer,
-                            base58prefix: network_specs.specs.base58prefix,
-                            genesis_hash: network_specs.specs.genesis_hash,
-                            address_details: &address_details,
-                        })
+                        let author_card = Card::Author(make_author_info(
+                            database,
+                            &author_multi_signer,
+                            network_specs.specs.base58prefix,
+                            network_specs.specs.genesis_hash,
+                            &address_details,
+                        )?)
                         .card(&mut index, indent);
                         CardsPrep::ShowOnly(
                             author_card,
@@ -543,11 +560,12 @@ fn do_parse_transaction(
                                         sign.signing_bulk.push(sign_one);
                                         let checksum = sign.store_and_get_checksum(database)?;
                                         let author_info = make_author_info(
+                                            database,
                                             &author_multi_signer,
                                             network_specs.specs.base58prefix,
                                             network_specs.specs.genesis_hash,
                                             &address_details,
-                                        );
+                                        )?;
                                         let warning = possible_warning
                                             .map(|w| Card::Warning(w).card(&mut index, indent))
                                             .map(|w| vec![w]);
@@ -595,12 +613,13 @@ fn do_parse_transaction(
                                         let warning = possible_warning
                                             .map(|w| Card::Warning(w).card(&mut index, indent))
                                             .map(|w| vec![w]);
-                                        let author = Card::Author {
-                                            author: &author_multi_signer,
-                                            base58prefix: network_specs.specs.base58prefix,
-                                            genesis_hash: network_specs.specs.genesis_hash,
-                                            address_details: &address_details,
-                                        }
+                                        let author = Card::Author(make_author_info(
+                                            database,
+                                            &author_multi_signer,
+                                            network_specs.specs.base58prefix,
+                                            network_specs.specs.genesis_hash,
+                                            &address_details,
+                                        )?)
                                         .card(&mut index, indent);
                                         let error = Card::Error(e.into()).card(&mut index, indent);
                                         let extensions = into_cards(&extensions_cards, &mut index);
@@ -698,11 +717,12 @@ pub fn entry_to_transactions_with_decoding(
                         (
                             verifier_details,
                             Some(make_author_info(
+                                database,
                                 m,
                                 specs_found.specs.base58prefix,
                                 specs_found.specs.genesis_hash,
                                 &address_details,
-                            )),
+                            )?),
                             Some(decode_signable_from_history(database, sign_display)?),
                         )
                     } else {
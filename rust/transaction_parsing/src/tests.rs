@@ -6,6 +6,7 @@ use constants::test_values::{
 
 use db_handling::{
     cold_default::{populate_cold, populate_cold_no_metadata, populate_cold_no_networks},
+    helpers::set_meta_storage,
     identities::try_create_address,
     manage_history::get_history,
 };
@@ -16,13 +17,15 @@ use definitions::navigation::{
 use definitions::{
     crypto::Encryption,
     history::{Entry, Event},
-    keyring::NetworkSpecsKey,
+    keyring::{MetaKey, NetworkSpecsKey},
+    metadata::{runtime_metadata_from_slice, MetaStorage, MetaValues},
     navigation::{
         Address, Card, MMetadataRecord, MSCCall, MSCCurrency, MSCEnumVariantName, MSCEraMortal,
         MSCId, MSCNameVersion, MTypesInfo, MVerifierDetails, NetworkSpecs, TransactionAction,
         TransactionCard, TransactionCardSet,
     },
     network_specs::{OrderedNetworkSpecs, Verifier, VerifierValue},
+    qr_transfers::{Compression, ContentLoadMeta, ContentLoadMetaCompressed},
 };
 use definitions::{
     derivations::{DerivedKeyPreview, DerivedKeyStatus, SeedKeysPreview},
@@ -30,13 +33,18 @@ use definitions::{
 };
 
 use crate::parse_transaction::parse_dd_transaction;
-use constants::ALICE_SEED_PHRASE;
+use constants::{ALICE_SEED_PHRASE, MAX_DECOMPRESSED_CONTENT_SIZE, METATREE};
+use frame_metadata::{
+    v14::RuntimeMetadataV14,
+    v15::{self, RuntimeMetadataV15},
+    RuntimeMetadata,
+};
 use parity_scale_codec::Encode;
 use pretty_assertions::assert_eq;
 use sp_core::sr25519::Public;
 use sp_core::H256;
 use sp_runtime::MultiSigner;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryFrom;
 use std::string::ToString;
 use std::{fs, str::FromStr};
@@ -713,6 +721,7 @@ fn parse_transaction_1() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
     let network_info_known = OrderedNetworkSpecs {
@@ -1054,6 +1063,7 @@ fn parse_transaction_2() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
     let network_info_known = westend_spec();
@@ -1222,6 +1232,7 @@ fn parse_transaction_3() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
     let network_info_known = westend_spec();
@@ -1316,6 +1327,218 @@ fn load_westend9070_alice_signed() {
     }
 }
 
+#[test]
+fn load_westend9070_compressed() {
+    let line = fs::read_to_string("for_tests/network_metadata_westendV9070_None.txt").unwrap();
+    let data = hex::decode(line.trim()).unwrap();
+    let content = ContentLoadMeta::from_slice(&data[3..]);
+    let compressed = ContentLoadMetaCompressed::compress(&content, Compression::Zstd).unwrap();
+    let line_compressed = hex::encode([vec![0x53, 0xff, 0x82], compressed.to_transfer()].concat());
+    assert!(line_compressed.len() < line.trim().len());
+
+    let dbname = &tempdir().unwrap().into_path().to_str().unwrap().to_string();
+    let db = sled::open(dbname).unwrap();
+    populate_cold_no_metadata(&db, Verifier { v: None }).unwrap();
+    let action = produce_output(&db, line.trim()).unwrap();
+
+    let dbname = &tempdir().unwrap().into_path().to_str().unwrap().to_string();
+    let db = sled::open(dbname).unwrap();
+    populate_cold_no_metadata(&db, Verifier { v: None }).unwrap();
+    let action_compressed = produce_output(&db, &line_compressed).unwrap();
+
+    if let (
+        TransactionAction::Stub { s, u: _, stub },
+        TransactionAction::Stub {
+            s: s_compressed,
+            u: _,
+            stub: stub_compressed,
+        },
+    ) = (action, action_compressed)
+    {
+        assert_eq!(s, s_compressed);
+        assert_eq!(stub, stub_compressed);
+    } else {
+        panic!("Wrong actions")
+    }
+}
+
+#[test]
+fn load_compressed_too_large() {
+    let dbname = &tempdir().unwrap().into_path().to_str().unwrap().to_string();
+    let db = sled::open(dbname).unwrap();
+    populate_cold_no_metadata(&db, Verifier { v: None }).unwrap();
+
+    let content = ContentLoadMeta::generate(
+        &vec![0; MAX_DECOMPRESSED_CONTENT_SIZE],
+        &H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e")
+            .unwrap(),
+    );
+    let compressed = ContentLoadMetaCompressed::compress(&content, Compression::Zstd).unwrap();
+    let line = hex::encode([vec![0x53, 0xff, 0x82], compressed.to_transfer()].concat());
+    let error = produce_output(&db, &line).unwrap_err();
+    assert!(matches!(
+        error,
+        Error::Definitions(definitions::error::Error::DecompressedTooLarge(_))
+    ));
+}
+
+/// Unsigned `load_metadata` payload for westend metadata from release set
+fn load_westend9230_line() -> (String, MetaValues) {
+    let meta_hex = fs::read_to_string("../defaults/release_metadata/westend9230").unwrap();
+    let meta_values =
+        MetaValues::from_slice_metadata(&hex::decode(meta_hex.trim()).unwrap()).unwrap();
+    let content = ContentLoadMeta::generate(
+        &meta_values.meta,
+        &H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e")
+            .unwrap(),
+    );
+    let line = hex::encode([vec![0x53, 0xff, 0x80], content.to_transfer()].concat());
+    (line, meta_values)
+}
+
+#[test]
+fn load_westend9230_trimmed() {
+    let dbname = &tempdir().unwrap().into_path().to_str().unwrap().to_string();
+    let db = sled::open(dbname).unwrap();
+    populate_cold_no_metadata(&db, Verifier { v: None }).unwrap();
+    set_meta_storage(&db, MetaStorage::Trimmed).unwrap();
+    let (line, meta_values) = load_westend9230_line();
+
+    // metadata card shows the hash of the full metadata
+    if let TransactionAction::Stub { s, u: _, stub: _ } = produce_output(&db, &line).unwrap() {
+        let meta_card = &s.meta.unwrap()[0];
+        if let Card::MetaCard { f } = &meta_card.card {
+            assert_eq!(f.meta_hash, hex::encode(meta_values.hash()));
+        } else {
+            panic!("Expected metadata card")
+        }
+    } else {
+        panic!("Wrong action")
+    }
+
+    // trimmed metadata in the database is matched by the full metadata hash
+    let trimmed = meta_values.trim().unwrap();
+    db.open_tree(METATREE)
+        .unwrap()
+        .insert(
+            MetaKey::from_parts(&meta_values.name, meta_values.version).key(),
+            trimmed.to_stored(),
+        )
+        .unwrap();
+    let error = produce_output(&db, &line).unwrap_err();
+    assert!(matches!(error, Error::MetadataKnown { .. }));
+}
+
+/// `V15` metadata with the same content as the `V14` one.
+fn v15_from_v14(meta_v14: RuntimeMetadataV14) -> RuntimeMetadataV15 {
+    let pallets = meta_v14
+        .pallets
+        .into_iter()
+        .map(|pallet| v15::PalletMetadata {
+            name: pallet.name,
+            storage: pallet.storage,
+            calls: pallet.calls,
+            event: pallet.event,
+            constants: pallet.constants,
+            error: pallet.error,
+            index: pallet.index,
+            docs: Vec::new(),
+        })
+        .collect();
+    let signed_extensions = meta_v14
+        .extrinsic
+        .signed_extensions
+        .into_iter()
+        .map(|signed_extension| v15::SignedExtensionMetadata {
+            identifier: signed_extension.identifier,
+            ty: signed_extension.ty,
+            additional_signed: signed_extension.additional_signed,
+        })
+        .collect();
+    let ty = meta_v14.extrinsic.ty;
+    RuntimeMetadataV15 {
+        types: meta_v14.types,
+        pallets,
+        extrinsic: v15::ExtrinsicMetadata {
+            version: meta_v14.extrinsic.version,
+            address_ty: ty,
+            call_ty: ty,
+            signature_ty: ty,
+            extra_ty: ty,
+            signed_extensions,
+        },
+        ty: meta_v14.ty,
+        apis: Vec::new(),
+        outer_enums: v15::OuterEnums {
+            call_enum_ty: ty,
+            event_enum_ty: ty,
+            error_enum_ty: ty,
+        },
+        custom: v15::CustomMetadata {
+            map: BTreeMap::new(),
+        },
+    }
+}
+
+#[test]
+fn load_westend9230_v14_v15() {
+    let dbname = &tempdir().unwrap().into_path().to_str().unwrap().to_string();
+    let db = sled::open(dbname).unwrap();
+    populate_cold_no_metadata(&db, Verifier { v: None }).unwrap();
+    set_meta_storage(&db, MetaStorage::Full).unwrap();
+    let (line_v14, meta_values_v14) = load_westend9230_line();
+
+    let meta_v14 = match runtime_metadata_from_slice(&meta_values_v14.meta).unwrap() {
+        RuntimeMetadata::V14(meta_v14) => meta_v14,
+        _ => panic!("Expected V14 metadata"),
+    };
+    let meta_v15 = [
+        b"meta".to_vec(),
+        RuntimeMetadata::V15(v15_from_v14(meta_v14)).encode(),
+    ]
+    .concat();
+    let meta_values_v15 = MetaValues::from_slice_metadata(&meta_v15).unwrap();
+    assert_eq!(meta_values_v15.version, meta_values_v14.version);
+    let content = ContentLoadMeta::generate(
+        &meta_v15,
+        &H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e")
+            .unwrap(),
+    );
+    let line_v15 = hex::encode([vec![0x53, 0xff, 0x80], content.to_transfer()].concat());
+
+    let metadata = db.open_tree(METATREE).unwrap();
+    let meta_key = MetaKey::from_parts(&meta_values_v14.name, meta_values_v14.version);
+
+    // `V15` replaces `V14` of the same network version
+    metadata
+        .insert(meta_key.key(), meta_values_v14.to_stored())
+        .unwrap();
+    assert!(matches!(
+        produce_output(&db, &line_v15).unwrap(),
+        TransactionAction::Stub { .. }
+    ));
+
+    // `V14` does not replace `V15` of the same network version
+    metadata
+        .insert(meta_key.key(), meta_values_v15.to_stored())
+        .unwrap();
+    assert!(matches!(
+        produce_output(&db, &line_v14).unwrap_err(),
+        Error::MetadataKnown { .. }
+    ));
+}
+
+#[test]
+fn load_metadata_proof_only() {
+    let dbname = &tempdir().unwrap().into_path().to_str().unwrap().to_string();
+    let db = sled::open(dbname).unwrap();
+    populate_cold_no_metadata(&db, Verifier { v: None }).unwrap();
+    set_meta_storage(&db, MetaStorage::ProofOnly).unwrap();
+    let (line, _) = load_westend9230_line();
+    let error = produce_output(&db, &line).unwrap_err();
+    assert!(matches!(error, Error::MetadataNotStored { .. }));
+}
+
 #[test]
 fn load_westend9000_already_in_db_not_signed() {
     let dbname = &tempdir().unwrap().into_path().to_str().unwrap().to_string();
@@ -2212,6 +2435,7 @@ fn parse_transaction_6_error_on_parsing() {
                         path: "//Alice".to_string(),
                         has_pwd: false,
                         secret_exposed: false,
+                        label: None,
                     },
                 },
             },
@@ -2315,6 +2539,7 @@ fn parse_transaction_7_error_on_parsing() {
                         path: "//Alice".to_string(),
                         has_pwd: false,
                         secret_exposed: false,
+                        label: None,
                     },
                 },
             },
@@ -2419,6 +2644,7 @@ fn parse_transaction_8_error_on_parsing() {
                         path: "//Alice".to_string(),
                         has_pwd: false,
                         secret_exposed: false,
+                        label: None,
                     },
                 },
             },
@@ -2526,6 +2752,7 @@ fn parse_msg_1() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
 
@@ -2584,6 +2811,7 @@ fn parse_any_chain_msg() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
 
@@ -2899,6 +3127,7 @@ fn parse_dd_transaction_1() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
     let network_info_known = OrderedNetworkSpecs {
@@ -3255,6 +3484,7 @@ fn parse_dd_transaction_2() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
     let network_info_known = westend_spec();
@@ -3434,6 +3664,7 @@ fn parse_dd_transaction_3() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
     let network_info_known = westend_spec();
@@ -3519,3 +3750,18 @@ fn parse_dd_transaction_wrong_metadata() {
         panic!("Wrong DecodeSequenceResult {:?}", output)
     }
 }
+
+#[test]
+fn decode_payload_without_dynamic_derivations() {
+    // every substrate payload is passed further as is, including the ones
+    // with unknown prelude
+    for line in ["530105", "53ff99", "530104"] {
+        let output = decode_payload(line, false).unwrap();
+        assert!(matches!(output, DecodeSequenceResult::Other { s } if s == line));
+    }
+    assert!(matches!(
+        decode_payload("540105", false),
+        Err(Error::NotSubstrate(_))
+    ));
+    assert!(matches!(decode_payload("53", false), Err(Error::TooShort)));
+}
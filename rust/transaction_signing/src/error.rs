@@ -37,4 +37,12 @@ pub enum Error {
     /// Associated data is the new checksum.
     #[error("Wrong password.")]
     WrongPasswordNewChecksum(u32),
+
+    /// Metadata is stored in the database in trimmed form, and can not be
+    /// used to generate `load_metadata` update.
+    #[error(
+        "Metadata for {name}{version} is stored trimmed \
+        and can not be signed for export."
+    )]
+    TrimmedMetadata { name: String, version: u32 },
 }
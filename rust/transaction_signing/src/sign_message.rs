@@ -1,12 +1,14 @@
 use parity_scale_codec::Encode;
-use sp_core::{ecdsa, ed25519, keccak_256, sr25519, Pair};
+use sp_core::{ecdsa, keccak_256, sr25519, Pair};
 use sp_runtime::MultiSigner;
 use zeroize::Zeroize;
 
 use crate::{Error, Result};
 use db_handling::{
+    bip32::Ed25519Key,
     db_transactions::TrDbCold,
     helpers::{get_meta_values_by_name_version, get_network_specs, prep_types},
+    identities::seed_secret_with_path,
     manage_history::events_to_batch,
 };
 use definitions::{
@@ -27,13 +29,12 @@ pub(crate) fn sign_as_address_key(
 ) -> Result<SufficientCrypto> {
     match multisigner {
         MultiSigner::Ed25519(public) => {
-            let ed25519_pair =
-                ed25519::Pair::from_string(full_address, pwd).map_err(Error::CryptoError)?;
-            if public != &ed25519_pair.public() {
+            let ed25519_key = Ed25519Key::from_string(full_address, pwd)?;
+            if public != &ed25519_key.public() {
                 return Err(Error::WrongPassword);
             }
             // secret zeroize on drop, https://docs.rs/ed25519-dalek/1.0.1/src/ed25519_dalek/secret.rs.html#43
-            let signature = ed25519_pair.sign(to_sign);
+            let signature = ed25519_key.sign(to_sign);
             Ok(SufficientCrypto::Ed25519 {
                 public: public.to_owned(),
                 signature,
@@ -87,7 +88,7 @@ fn sufficient_crypto(
             None
         }
     };
-    let mut full_address = seed_phrase.to_owned() + &address_details.path;
+    let mut full_address = seed_secret_with_path(seed_phrase, &address_details.path, encryption)?;
     match sign_as_address_key(to_sign, multisigner, &full_address, pwd, encryption) {
         Ok(a) => {
             full_address.zeroize();
@@ -153,6 +154,12 @@ pub(crate) fn sufficient_crypto_load_metadata(
     let network_specs = get_network_specs(database, network_specs_key)?.specs;
     let meta_values =
         get_meta_values_by_name_version(database, &network_specs.name, network_version)?;
+    if meta_values.full_hash.is_some() {
+        return Err(Error::TrimmedMetadata {
+            name: meta_values.name,
+            version: meta_values.version,
+        });
+    }
     let load_meta_content =
         ContentLoadMeta::generate(&meta_values.meta, &network_specs.genesis_hash);
     let sufficient = match sufficient_crypto(
@@ -5,6 +5,7 @@ use sp_runtime::MultiSignature;
 use zeroize::Zeroize;
 
 use db_handling::db_transactions::{SignContent, TrDbColdSign};
+use db_handling::identities::seed_secret_with_path;
 
 use crate::sign_message::sign_as_address_key;
 use crate::{Error, Result};
@@ -86,7 +87,8 @@ pub fn create_signature(
         } if content_vec.len() > 257 => blake2_256(&content_vec).to_vec(),
         _ => content_vec,
     };
-    let mut full_address = seed_phrase.to_owned() + &sign.signing_bulk[idx].path();
+    let mut full_address =
+        seed_secret_with_path(seed_phrase, &sign.signing_bulk[idx].path(), encryption)?;
     let signature = match sign_as_address_key(
         &content_vec,
         &sign.signing_bulk[idx].multisigner(),
@@ -371,6 +371,7 @@ fn can_sign_transaction_1() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
 
@@ -635,6 +636,7 @@ fn can_sign_message_1() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
 
@@ -2301,6 +2303,7 @@ Identities:
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
     // TODO: let network_info_known = r#""network_title":"Westend","network_logo":"westend""#;
@@ -2622,6 +2625,7 @@ Identities:
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
     // TODO let network_info_known = r#""network_title":"Westend","network_logo":"westend""#;
@@ -2847,6 +2851,7 @@ fn parse_transaction_alice_remarks_westend9122() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
     // TODO let network_info_known = r#""network_title":"Westend","network_logo":"westend""#;
@@ -3937,6 +3942,7 @@ fn can_parse_westend_trasfer_with_proof() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
 
@@ -4124,6 +4130,7 @@ fn can_parse_bulk_westend_trasfer_with_proof() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
 
@@ -4301,6 +4308,7 @@ fn can_parse_bulk_westend_trasfer() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
 
@@ -4486,6 +4494,7 @@ fn can_parse_ethereum_extrinsic_with_proof() {
             path: derivation_path.to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
 
@@ -4661,6 +4670,7 @@ fn can_parse_extrinsic_with_proof_when_no_network_and_derivation_exist() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
 
@@ -4720,6 +4730,7 @@ fn cannot_sign_if_check_genesis_modified() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
 
@@ -4779,6 +4790,7 @@ fn cannot_sign_if_proof_verification_fails_when_check_metadata_hash_modified() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
 
@@ -5072,6 +5084,7 @@ fn can_parse_westend_staking_with_proof() {
             path: "//Alice".to_string(),
             has_pwd: false,
             secret_exposed: false,
+            label: None,
         },
     };
 